@@ -2,6 +2,7 @@
 
 use std::net::{IpAddr, Ipv4Addr};
 
+use rand_core::{CryptoRngCore, OsRng};
 use zeroutils_did::{
     did_wk::{DidWebKey, WrappedDidWebKey},
     Base,
@@ -38,7 +39,10 @@ pub(crate) const fn default_election_timeout_range() -> (u64, u64) {
 }
 
 pub(crate) fn default_id() -> WrappedDidWebKey<'static> {
-    let rng = &mut rand::thread_rng();
+    default_id_with(&mut OsRng)
+}
+
+pub(crate) fn default_id_with(rng: &mut impl CryptoRngCore) -> WrappedDidWebKey<'static> {
     let key = Ed25519KeyPair::generate(rng).unwrap();
     DidWebKey::from_key(&key, Base::Base58Btc)
         .into_owned()
@@ -0,0 +1,38 @@
+use std::{env, str::FromStr};
+
+use crate::{ConfigError, ConfigResult};
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Overrides `value` with the parsed value of the environment variable `{prefix}_{name}`.
+///
+/// `value` is left unchanged if the variable is not set.
+///
+/// # Errors
+///
+/// Returns [`ConfigError::InvalidEnvValue`] if the variable is not valid unicode or cannot be parsed
+/// as `T`.
+pub fn apply_env_var<T>(value: &mut T, prefix: &str, name: &str) -> ConfigResult<()>
+where
+    T: FromStr,
+{
+    let key = format!("{prefix}_{name}");
+    match env::var(&key) {
+        Ok(var) => {
+            *value = var
+                .parse()
+                .map_err(|_| ConfigError::InvalidEnvValue(key, var))?;
+        }
+        Err(env::VarError::NotPresent) => {}
+        Err(env::VarError::NotUnicode(var)) => {
+            return Err(ConfigError::InvalidEnvValue(
+                key,
+                var.to_string_lossy().into_owned(),
+            ));
+        }
+    }
+
+    Ok(())
+}
@@ -24,6 +24,14 @@ pub enum ConfigError {
     #[error("Toml deserialization error: {0}")]
     TomlError(#[from] toml::de::Error),
 
+    /// Json deserialization error.
+    #[error("Json deserialization error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    /// An environment variable override has an invalid value.
+    #[error("Invalid value for environment variable {0}: {1:?}")]
+    InvalidEnvValue(String, String),
+
     /// Custom error.
     #[error("Custom error: {0}")]
     Custom(#[from] AnyError),
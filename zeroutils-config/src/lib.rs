@@ -5,6 +5,7 @@
 #![warn(missing_docs)]
 #![allow(clippy::module_inception)]
 
+mod env;
 mod error;
 mod traits;
 
@@ -15,5 +16,6 @@ mod traits;
 pub mod default;
 pub mod network;
 
+pub use env::*;
 pub use error::*;
 pub use traits::*;
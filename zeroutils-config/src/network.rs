@@ -11,7 +11,7 @@ use structstruck::strike;
 use typed_builder::TypedBuilder;
 use zeroutils_did::did_wk::WrappedDidWebKey;
 
-use crate::{ConfigError, ConfigResult};
+use crate::{apply_env_var, ConfigError, ConfigResult, MainConfig};
 
 use super::default::{DEFAULT_ELECTION_TIMEOUT_RANGE, DEFAULT_HEARTBEAT_INTERVAL};
 
@@ -94,11 +94,50 @@ strike! {
     }
 }
 
+/// A partial [`NetworkConfig`] used to override the values of another config.
+///
+/// Fields that are `None` keep the value of the config being overridden. See [`NetworkConfig::merge`].
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialNetworkConfig<'a> {
+    /// The id of the node.
+    pub id: Option<WrappedDidWebKey<'a>>,
+
+    /// Name of the node.
+    pub name: Option<String>,
+
+    /// The host to listen on.
+    pub host: Option<IpAddr>,
+
+    /// The port to listen on for users.
+    pub user_port: Option<u16>,
+
+    /// The port to listen on for peers.
+    pub peer_port: Option<u16>,
+
+    /// The peers to connect to. Replaces the seeds of the config being overridden.
+    pub seeds: Option<HashMap<WrappedDidWebKey<'a>, SocketAddr>>,
+
+    /// The consensus configuration.
+    pub consensus: PartialConsensusConfig,
+}
+
+/// A partial [`ConsensusConfig`] used to override the values of another config.
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialConsensusConfig {
+    /// The interval at which heartbeats are sent.
+    pub heartbeat_interval: Option<u64>,
+
+    /// The range of election timeouts.
+    pub election_timeout_range: Option<(u64, u64)>,
+}
+
 //--------------------------------------------------------------------------------------------------
 // Methods
 //--------------------------------------------------------------------------------------------------
 
-impl<D> NetworkConfig<'_, D>
+impl<'a, D> NetworkConfig<'a, D>
 where
     D: PortDefaults,
 {
@@ -112,6 +151,22 @@ where
         Ok(())
     }
 
+    /// Overrides the values of this config with the `Some` values of `overrides`.
+    ///
+    /// This allows layering configs, e.g. defaults, then a config file, then command line flags.
+    pub fn merge(self, overrides: PartialNetworkConfig<'a>) -> Self {
+        Self {
+            d: PhantomData,
+            id: overrides.id.unwrap_or(self.id),
+            name: overrides.name.unwrap_or(self.name),
+            host: overrides.host.unwrap_or(self.host),
+            user_port: overrides.user_port.unwrap_or(self.user_port),
+            peer_port: overrides.peer_port.unwrap_or(self.peer_port),
+            seeds: overrides.seeds.unwrap_or(self.seeds),
+            consensus: self.consensus.merge(overrides.consensus),
+        }
+    }
+
     /// Gets the peer address.
     pub fn get_peer_address(&self) -> SocketAddr {
         SocketAddr::new(self.host, self.peer_port)
@@ -123,10 +178,52 @@ where
     }
 }
 
+impl ConsensusConfig {
+    /// Overrides the values of this config with the `Some` values of `overrides`.
+    pub fn merge(self, overrides: PartialConsensusConfig) -> Self {
+        Self {
+            heartbeat_interval: overrides
+                .heartbeat_interval
+                .unwrap_or(self.heartbeat_interval),
+            election_timeout_range: overrides
+                .election_timeout_range
+                .unwrap_or(self.election_timeout_range),
+        }
+    }
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
 
+impl<D> MainConfig for NetworkConfig<'_, D>
+where
+    D: PortDefaults,
+{
+    fn validate(&self) -> ConfigResult<()> {
+        NetworkConfig::validate(self)
+    }
+
+    /// Overrides the scalar fields with environment variables.
+    ///
+    /// The supported variables are `{prefix}_ID`, `{prefix}_NAME`, `{prefix}_HOST`,
+    /// `{prefix}_USER_PORT`, `{prefix}_PEER_PORT` and `{prefix}_CONSENSUS_HEARTBEAT_INTERVAL`.
+    fn apply_env(&mut self, prefix: &str) -> ConfigResult<()> {
+        apply_env_var(&mut self.id, prefix, "ID")?;
+        apply_env_var(&mut self.name, prefix, "NAME")?;
+        apply_env_var(&mut self.host, prefix, "HOST")?;
+        apply_env_var(&mut self.user_port, prefix, "USER_PORT")?;
+        apply_env_var(&mut self.peer_port, prefix, "PEER_PORT")?;
+        apply_env_var(
+            &mut self.consensus.heartbeat_interval,
+            prefix,
+            "CONSENSUS_HEARTBEAT_INTERVAL",
+        )?;
+
+        Ok(())
+    }
+}
+
 impl<'a, D: PortDefaults> Default for NetworkConfig<'a, D> {
     fn default() -> Self {
         Self {
@@ -160,7 +257,7 @@ mod tests {
     use crate::network::tests::fixture::MockPortDefaults;
 
     use super::*;
-    use std::{net::Ipv4Addr, str::FromStr};
+    use std::{net::Ipv4Addr, path::PathBuf, str::FromStr};
 
     mod fixture {
         use super::*;
@@ -171,6 +268,9 @@ mod tests {
 
         pub struct MockPortDefaults;
 
+        /// A file in the temp directory that is removed when dropped.
+        pub struct TempFile(pub PathBuf);
+
         //--------------------------------------------------------------------------------------------------
         // Methods
         //--------------------------------------------------------------------------------------------------
@@ -184,6 +284,25 @@ mod tests {
                 7711
             }
         }
+
+        impl TempFile {
+            pub fn new(name: &str, contents: &str) -> std::io::Result<Self> {
+                let path = std::env::temp_dir()
+                    .join(format!("zeroutils-config-{}-{name}", std::process::id()));
+                std::fs::write(&path, contents)?;
+                Ok(Self(path))
+            }
+        }
+
+        //--------------------------------------------------------------------------------------------------
+        // Trait Implementations
+        //--------------------------------------------------------------------------------------------------
+
+        impl Drop for TempFile {
+            fn drop(&mut self) {
+                let _ = std::fs::remove_file(&self.0);
+            }
+        }
     }
 
     #[test]
@@ -254,4 +373,180 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_from_file_partial() -> anyhow::Result<()> {
+        let toml = fixture::TempFile::new(
+            "partial.toml",
+            r#"
+            name = "alice"
+            user_port = 8800
+
+            [consensus]
+            heartbeat_interval = 1000
+            "#,
+        )?;
+
+        let config = NetworkConfig::<MockPortDefaults>::from_file(&toml.0)?;
+        assert_eq!(config.name, "alice");
+        assert_eq!(config.user_port, 8800);
+        assert_eq!(config.peer_port, 7711);
+        assert_eq!(config.host, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert!(config.seeds.is_empty());
+        assert_eq!(config.consensus.heartbeat_interval, 1000);
+        assert_eq!(
+            config.consensus.election_timeout_range,
+            DEFAULT_ELECTION_TIMEOUT_RANGE
+        );
+
+        let json = fixture::TempFile::new(
+            "partial.json",
+            r#"{ "name": "bob", "consensus": { "election_timeout_range": [100, 200] } }"#,
+        )?;
+
+        let config = NetworkConfig::<MockPortDefaults>::from_file(&json.0)?;
+        assert_eq!(config.name, "bob");
+        assert_eq!(config.user_port, 7700);
+        assert_eq!(
+            config.consensus.heartbeat_interval,
+            DEFAULT_HEARTBEAT_INTERVAL
+        );
+        assert_eq!(config.consensus.election_timeout_range, (100, 200));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_partial() -> anyhow::Result<()> {
+        let base = NetworkConfig::<MockPortDefaults>::from_toml_str(
+            r#"
+            name = "alice"
+            user_port = 8800
+
+            [consensus]
+            heartbeat_interval = 1000
+            "#,
+        )?;
+        let id = base.id.clone();
+
+        let config = base.merge(PartialNetworkConfig {
+            peer_port: Some(9911),
+            consensus: PartialConsensusConfig {
+                election_timeout_range: Some((100, 200)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        assert_eq!(config.id, id);
+        assert_eq!(config.name, "alice");
+        assert_eq!(config.user_port, 8800);
+        assert_eq!(config.peer_port, 9911);
+        assert_eq!(config.host, IpAddr::V4(Ipv4Addr::LOCALHOST));
+        assert_eq!(config.consensus.heartbeat_interval, 1000);
+        assert_eq!(config.consensus.election_timeout_range, (100, 200));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_layers() -> anyhow::Result<()> {
+        // Defaults, then a config file, then flags.
+        let file: PartialNetworkConfig = toml::from_str(
+            r#"
+            name = "alice"
+            host = "10.0.0.1"
+            user_port = 8800
+            "#,
+        )?;
+        let flags = PartialNetworkConfig {
+            name: Some("bob".to_string()),
+            ..Default::default()
+        };
+
+        let config = NetworkConfig::<MockPortDefaults>::default()
+            .merge(file)
+            .merge(flags);
+
+        assert_eq!(config.name, "bob");
+        assert_eq!(config.host, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(config.user_port, 8800);
+        assert_eq!(config.peer_port, 7711);
+        assert_eq!(
+            config.consensus.heartbeat_interval,
+            DEFAULT_HEARTBEAT_INTERVAL
+        );
+
+        // An empty override keeps everything.
+        let config = config.merge(PartialNetworkConfig::default());
+        assert_eq!(config.name, "bob");
+        assert_eq!(config.user_port, 8800);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_env() -> anyhow::Result<()> {
+        // Each test uses its own prefix as environment variables are shared across test threads.
+        let prefix = "ZEROUTILS_CONFIG_TEST_APPLY_ENV";
+        std::env::set_var(format!("{prefix}_USER_PORT"), "9900");
+        std::env::set_var(format!("{prefix}_HOST"), "10.0.0.1");
+        std::env::set_var(format!("{prefix}_CONSENSUS_HEARTBEAT_INTERVAL"), "250");
+
+        let mut config = NetworkConfig::<MockPortDefaults>::from_toml_str("user_port = 8800")?;
+        config.apply_env(prefix)?;
+
+        assert_eq!(config.user_port, 9900);
+        assert_eq!(config.peer_port, 7711);
+        assert_eq!(config.host, IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(config.consensus.heartbeat_interval, 250);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_apply_env_invalid_value() -> anyhow::Result<()> {
+        let prefix = "ZEROUTILS_CONFIG_TEST_APPLY_ENV_INVALID";
+        std::env::set_var(format!("{prefix}_PEER_PORT"), "not a port");
+
+        let mut config = NetworkConfig::<MockPortDefaults>::default();
+        let result = config.apply_env(prefix);
+
+        assert!(matches!(
+            result,
+            Err(ConfigError::InvalidEnvValue(ref key, ref value))
+                if key == "ZEROUTILS_CONFIG_TEST_APPLY_ENV_INVALID_PEER_PORT" && value == "not a port"
+        ));
+        assert_eq!(config.peer_port, 7711);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_file_errors() -> anyhow::Result<()> {
+        let toml = fixture::TempFile::new("invalid.toml", "user_port = \"not a port\"")?;
+        assert!(matches!(
+            NetworkConfig::<MockPortDefaults>::from_file(&toml.0),
+            Err(ConfigError::TomlError(_))
+        ));
+
+        let json = fixture::TempFile::new("invalid.json", "{ \"user_port\": ")?;
+        assert!(matches!(
+            NetworkConfig::<MockPortDefaults>::from_file(&json.0),
+            Err(ConfigError::JsonError(_))
+        ));
+
+        let missing = std::env::temp_dir().join("zeroutils-config-missing.toml");
+        assert!(matches!(
+            NetworkConfig::<MockPortDefaults>::from_file(missing),
+            Err(ConfigError::IoError(_))
+        ));
+
+        assert!(matches!(
+            NetworkConfig::<MockPortDefaults>::from_toml_str("host = 1"),
+            Err(ConfigError::TomlError(_))
+        ));
+
+        Ok(())
+    }
 }
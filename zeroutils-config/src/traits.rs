@@ -11,22 +11,54 @@ pub trait MainConfig {
     /// Validates the configuration.
     fn validate(&self) -> ConfigResult<()>;
 
-    /// Creates a new `ZerodbConfig` from a toml file.
+    /// Overrides fields with the values of environment variables named `{prefix}_{FIELD}`, e.g.
+    /// `ZEROCORE_NETWORK_USER_PORT` for the `user_port` field with the `ZEROCORE_NETWORK` prefix.
+    ///
+    /// Does nothing by default.
+    fn apply_env(&mut self, prefix: &str) -> ConfigResult<()> {
+        let _ = prefix;
+        Ok(())
+    }
+
+    /// Creates a new config from a toml or json file.
+    ///
+    /// Files with a `.json` extension are parsed as json, and all other files as toml. Missing fields
+    /// take their default values.
     fn from_file(path: impl AsRef<Path>) -> ConfigResult<Self>
     where
         Self: Sized + for<'de> serde::Deserialize<'de>,
     {
+        let path = path.as_ref();
         let config = fs::read_to_string(path)?;
-        let config = toml::from_str(&config)?;
-        Ok(config)
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json_str(config),
+            _ => Self::from_toml_str(config),
+        }
     }
 
-    /// Creates a new `ZerodbConfig` from a toml string.
+    /// Creates a new config from a toml string.
     fn from_string(config: impl AsRef<str>) -> ConfigResult<Self>
+    where
+        Self: Sized + for<'de> serde::Deserialize<'de>,
+    {
+        Self::from_toml_str(config)
+    }
+
+    /// Creates a new config from a toml string.
+    fn from_toml_str(config: impl AsRef<str>) -> ConfigResult<Self>
     where
         Self: Sized + for<'de> serde::Deserialize<'de>,
     {
         let config = toml::from_str(config.as_ref())?;
         Ok(config)
     }
+
+    /// Creates a new config from a json string.
+    fn from_json_str(config: impl AsRef<str>) -> ConfigResult<Self>
+    where
+        Self: Sized + for<'de> serde::Deserialize<'de>,
+    {
+        let config = serde_json::from_str(config.as_ref())?;
+        Ok(config)
+    }
 }
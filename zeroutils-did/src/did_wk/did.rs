@@ -1,12 +1,17 @@
-use std::{any::Any, fmt::Display, str::FromStr};
+use std::{
+    any::Any,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+};
 
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zeroutils_key::{
-    Ed25519PubKey, GetPublicKey, IntoOwned, P256PubKey, Secp256k1PubKey, WrappedKeyPair,
-    WrappedPubKey,
+    Ed25519PubKey, GetPublicKey, IntoOwned, Multikey, P256PubKey, P384PubKey, Secp256k1PubKey,
+    WrappedKeyPair, WrappedPubKey,
 };
 
-use crate::{Base, Did, DidError, DidResult, KeyDecode, KeyEncode};
+use crate::{decode_multicodec, Base, Did, DidDocument, DidError, DidResult, KeyDecode, KeyEncode};
 
 use super::{DidWebKeyBuilder, LocatorComponent};
 
@@ -21,9 +26,14 @@ use super::{DidWebKeyBuilder, LocatorComponent};
 /// `public_key` part and optionally includes a `locator_component` for finding the DID document. Without the
 /// locator component, it functions similarly as a [`did:key`][did-key] identifier.
 ///
+/// The base encoding only affects the string representation. Two `DidWebKey`s with the same public key and
+/// locator component compare equal regardless of their base. For the same reason, the `Display` form is not
+/// canonical; use [`canonical_bytes`][DidWebKey::canonical_bytes] where a stable key is needed, e.g. in a
+/// database.
+///
 /// [did-wk]: https://github.com/zerocore-ai/did-wk
 /// [did-key]: https://w3c-ccg.github.io/did-method-key/
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub struct DidWebKey<P = ()> {
     /// The public key.
     pub(crate) public_key: P,
@@ -73,10 +83,20 @@ pub enum WrappedDidWebKey<'a> {
     /// `NIST P-256` public key.
     P256(P256DidWebKey<'a>),
 
+    /// `NIST P-384` public key.
+    P384(P384DidWebKey<'a>),
+
     /// `secp256k1` public key.
     Secp256k1(Secp256k1DidWebKey<'a>),
 }
 
+/// Serializes a `did:wk` with the given base encoding instead of the one it was created or parsed with.
+///
+/// This is useful for persisting DIDs in contexts that restrict the alphabet, like DNS labels which
+/// require [`Base::Base32Lower`].
+#[derive(Debug, Clone, Copy)]
+pub struct WithBase<'a, T>(pub &'a T, pub Base);
+
 /// A `DID Web Key` ([`did:wk`][ref]) with an `ed25519` public key.
 ///
 /// The type represents a decentralized identifier (DID) using the [`did:wk` method][ref], which is designed
@@ -95,6 +115,15 @@ pub type Ed25519DidWebKey<'a> = DidWebKey<Ed25519PubKey<'a>>;
 /// [ref]: https://github.com/zerocore-ai/did-wk
 pub type P256DidWebKey<'a> = DidWebKey<P256PubKey<'a>>;
 
+/// A `DID Web Key` ([`did:wk`][ref]) with a `NIST P-384` public key.
+///
+/// The type represents a decentralized identifier (DID) using the [`did:wk` method][ref], which is designed
+/// for decentralized systems with web key authentication. It encapsulates the `public_key`, optionally includes
+/// a `locator_component` for resolving to a specific URL.
+///
+/// [ref]: https://github.com/zerocore-ai/did-wk
+pub type P384DidWebKey<'a> = DidWebKey<P384PubKey<'a>>;
+
 /// A `DID Web Key` ([`did:wk`][ref]) with a `secp256k1` public key.
 ///
 /// The type represents a decentralized identifier (DID) using the [`did:wk` method][ref], which is designed
@@ -134,6 +163,18 @@ impl DidWebKey {
 }
 
 impl<P> DidWebKey<P> {
+    /// Parses a [`DidWebKey`] from a did string representation.
+    ///
+    /// This is the same as [`FromStr::from_str`] and is the fallible alternative to the `From<&str>`
+    /// conversion, which panics on invalid input.
+    pub fn try_parse(did: &str) -> DidResult<Self>
+    where
+        P: KeyDecode,
+        DidError: From<P::Error>,
+    {
+        DidWebKey::from_str(did)
+    }
+
     /// Creates a new [`DidWebKey`] builder.
     pub fn builder() -> DidWebKeyBuilder {
         DidWebKeyBuilder::default()
@@ -169,6 +210,81 @@ impl<P> DidWebKey<P> {
 
         format!("did:wk:{}{}", key_encoded, locator_component_encoded)
     }
+
+    /// Serializes the `DidWebKey` as a did string using `base` instead of its own base encoding.
+    pub fn serialize_as<S>(&self, base: Base, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        P: KeyEncode,
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.encode(base))
+    }
+
+    /// Returns the canonical bytes of the DID.
+    ///
+    /// These are the multicodec prefixed public key bytes followed by `@` and the locator component, if any.
+    /// Unlike the `Display` form, they do not depend on the base encoding, so two DIDs that compare equal
+    /// have the same canonical bytes, which also sort consistently.
+    pub fn canonical_bytes(&self) -> Vec<u8>
+    where
+        P: KeyEncode,
+    {
+        let mut bytes = self.public_key.multikey_bytes();
+        if let Some(locator_component) = &self.locator_component {
+            bytes.push(b'@');
+            bytes.extend(locator_component.to_string().as_bytes());
+        }
+
+        bytes
+    }
+
+    /// Verifies that `doc` is the DID document of this DID.
+    ///
+    /// The document `id` must be this DID and every verification method must be controlled by this DID
+    /// and embed its public key. This is what makes a document fetched from the locator trustworthy.
+    /// DIDs in the document are compared like [`DidWebKey`]s, so they may use any base encoding.
+    pub fn verify_document(&self, doc: &DidDocument) -> DidResult<()>
+    where
+        P: KeyEncode + KeyDecode + PartialEq,
+        DidError: From<P::Error>,
+    {
+        let is_self =
+            |other: &str| DidWebKey::<P>::from_str(other).is_ok_and(|other| other == *self);
+
+        let did = self.to_string();
+        if !is_self(&doc.id) {
+            return Err(DidError::InvalidDocument(format!(
+                "document id `{}` does not match `{did}`",
+                doc.id
+            )));
+        }
+
+        if doc.verification_method.is_empty() {
+            return Err(DidError::InvalidDocument(
+                "document has no verification methods".to_string(),
+            ));
+        }
+
+        let expected = self.public_key.multikey_bytes();
+        for method in &doc.verification_method {
+            if !is_self(&method.controller) {
+                return Err(DidError::InvalidDocument(format!(
+                    "verification method `{}` is controlled by `{}`",
+                    method.id, method.controller
+                )));
+            }
+
+            let (public_key, _) = Multikey::decode(&method.public_key_multibase)?;
+            if Multikey::to_bytes(&public_key) != expected {
+                return Err(DidError::InvalidDocument(format!(
+                    "verification method `{}` does not embed the key of `{did}`",
+                    method.id
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -176,6 +292,49 @@ impl<P> DidWebKey<P> {
 //--------------------------------------------------------------------------------------------------
 
 impl<'a> WrappedDidWebKey<'a> {
+    /// Parses a [`WrappedDidWebKey`] from a did string representation.
+    ///
+    /// This is the same as [`FromStr::from_str`] and is the fallible alternative to the `From<&str>`
+    /// conversion, which panics on invalid input.
+    pub fn try_parse(did: &str) -> DidResult<Self> {
+        WrappedDidWebKey::from_str(did)
+    }
+
+    /// Parses a [`WrappedDidWebKey`] from a did string representation that must not have a locator
+    /// component.
+    ///
+    /// This is for callers that never allow locators. A DID with one fails with
+    /// [`DidError::UnexpectedLocator`] before the key is decoded.
+    pub fn from_str_no_locator(did: &str) -> DidResult<Self> {
+        if did.contains('@') {
+            return Err(DidError::UnexpectedLocator(did.to_string()));
+        }
+
+        Self::from_str(did)
+    }
+
+    /// Parses many did strings at once, e.g. the entries of an access control list.
+    ///
+    /// Unlike collecting into a `DidResult<Vec<_>>`, this does not stop at the first invalid DID. It returns
+    /// the parsed DIDs along with every input that failed to parse and its error, both in input order.
+    pub fn parse_many<I>(dids: I) -> (Vec<Self>, Vec<(String, DidError)>)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut parsed = Vec::new();
+        let mut failed = Vec::new();
+        for did in dids {
+            let did = did.as_ref();
+            match WrappedDidWebKey::from_str(did) {
+                Ok(wk) => parsed.push(wk),
+                Err(e) => failed.push((did.to_string(), e)),
+            }
+        }
+
+        (parsed, failed)
+    }
+
     /// Creates a new [`WrappedDidWebKey`] from a [`WrappedPubKey`] and base encoding.
     pub fn from_wrapped_pub_key(
         pub_key: &'a WrappedPubKey<'a>,
@@ -184,6 +343,7 @@ impl<'a> WrappedDidWebKey<'a> {
         match pub_key {
             WrappedPubKey::Ed25519(wk) => WrappedDidWebKey::Ed25519(DidWebKey::from_key(wk, base)),
             WrappedPubKey::P256(wk) => WrappedDidWebKey::P256(DidWebKey::from_key(wk, base)),
+            WrappedPubKey::P384(wk) => WrappedDidWebKey::P384(DidWebKey::from_key(wk, base)),
             WrappedPubKey::Secp256k1(wk) => {
                 WrappedDidWebKey::Secp256k1(DidWebKey::from_key(wk, base))
             }
@@ -198,6 +358,7 @@ impl<'a> WrappedDidWebKey<'a> {
         match key_pair {
             WrappedKeyPair::Ed25519(kp) => WrappedDidWebKey::Ed25519(DidWebKey::from_key(kp, base)),
             WrappedKeyPair::P256(kp) => WrappedDidWebKey::P256(DidWebKey::from_key(kp, base)),
+            WrappedKeyPair::P384(kp) => WrappedDidWebKey::P384(DidWebKey::from_key(kp, base)),
             WrappedKeyPair::Secp256k1(kp) => {
                 WrappedDidWebKey::Secp256k1(DidWebKey::from_key(kp, base))
             }
@@ -246,6 +407,14 @@ impl<'a> WrappedDidWebKey<'a> {
                 locator_component,
             };
             WrappedDidWebKey::P256(did_wk)
+        } else if any.downcast_ref::<P384PubKey>().is_some() {
+            let public_key = any.downcast::<P384PubKey>().unwrap();
+            let did_wk = DidWebKey {
+                public_key: *public_key,
+                base,
+                locator_component,
+            };
+            WrappedDidWebKey::P384(did_wk)
         } else if any.downcast_ref::<Secp256k1PubKey>().is_some() {
             let public_key = any.downcast::<Secp256k1PubKey>().unwrap();
             let did_wk = DidWebKey {
@@ -270,6 +439,7 @@ impl<'a> WrappedDidWebKey<'a> {
         let any_wk: Box<dyn Any> = match self {
             WrappedDidWebKey::Ed25519(wk) => Box::new(wk),
             WrappedDidWebKey::P256(wk) => Box::new(wk),
+            WrappedDidWebKey::P384(wk) => Box::new(wk),
             WrappedDidWebKey::Secp256k1(wk) => Box::new(wk),
         };
 
@@ -285,6 +455,7 @@ impl<'a> WrappedDidWebKey<'a> {
         match self {
             WrappedDidWebKey::Ed25519(wk) => WrappedPubKey::Ed25519(wk.public_key().clone()),
             WrappedDidWebKey::P256(wk) => WrappedPubKey::P256(wk.public_key().clone()),
+            WrappedDidWebKey::P384(wk) => WrappedPubKey::P384(wk.public_key().clone()),
             WrappedDidWebKey::Secp256k1(wk) => WrappedPubKey::Secp256k1(wk.public_key().clone()),
         }
     }
@@ -294,6 +465,7 @@ impl<'a> WrappedDidWebKey<'a> {
         match self {
             WrappedDidWebKey::Ed25519(wk) => wk.locator_component(),
             WrappedDidWebKey::P256(wk) => wk.locator_component(),
+            WrappedDidWebKey::P384(wk) => wk.locator_component(),
             WrappedDidWebKey::Secp256k1(wk) => wk.locator_component(),
         }
     }
@@ -305,15 +477,37 @@ impl<'a> WrappedDidWebKey<'a> {
         match self {
             WrappedDidWebKey::Ed25519(wk) => wk.encode(base),
             WrappedDidWebKey::P256(wk) => wk.encode(base),
+            WrappedDidWebKey::P384(wk) => wk.encode(base),
             WrappedDidWebKey::Secp256k1(wk) => wk.encode(base),
         }
     }
 
+    /// Returns the canonical bytes of the DID.
+    ///
+    /// See [`DidWebKey::canonical_bytes`].
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        match self {
+            WrappedDidWebKey::Ed25519(wk) => wk.canonical_bytes(),
+            WrappedDidWebKey::P256(wk) => wk.canonical_bytes(),
+            WrappedDidWebKey::P384(wk) => wk.canonical_bytes(),
+            WrappedDidWebKey::Secp256k1(wk) => wk.canonical_bytes(),
+        }
+    }
+
+    /// Serializes the `WrappedDidWebKey` as a did string using `base` instead of its own base encoding.
+    pub fn serialize_as<S>(&self, base: Base, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.encode(base))
+    }
+
     /// Gets the base encoding.
     pub fn base(&self) -> Base {
         match self {
             WrappedDidWebKey::Ed25519(wk) => wk.base(),
             WrappedDidWebKey::P256(wk) => wk.base(),
+            WrappedDidWebKey::P384(wk) => wk.base(),
             WrappedDidWebKey::Secp256k1(wk) => wk.base(),
         }
     }
@@ -323,11 +517,22 @@ impl<'a> WrappedDidWebKey<'a> {
 // Trait Implementations: WrappedDidWebKey
 //--------------------------------------------------------------------------------------------------
 
+impl Did for WrappedDidWebKey<'static> {
+    fn method(&self) -> &str {
+        "wk"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
 impl<'a> Display for WrappedDidWebKey<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             WrappedDidWebKey::Ed25519(wk) => write!(f, "{}", wk),
             WrappedDidWebKey::P256(wk) => write!(f, "{}", wk),
+            WrappedDidWebKey::P384(wk) => write!(f, "{}", wk),
             WrappedDidWebKey::Secp256k1(wk) => write!(f, "{}", wk),
         }
     }
@@ -349,31 +554,64 @@ impl<'a> FromStr for WrappedDidWebKey<'a> {
             Err(e) => return Err(e),
         }
 
+        match P384DidWebKey::from_str(did) {
+            Err(DidError::ExpectedKeyType(_)) => {}
+            Ok(wk) => return Ok(WrappedDidWebKey::P384(wk)),
+            Err(e) => return Err(e),
+        }
+
         match Secp256k1DidWebKey::from_str(did) {
             Err(DidError::ExpectedKeyType(_)) => {}
             Ok(wk) => return Ok(WrappedDidWebKey::Secp256k1(wk)),
             Err(e) => return Err(e),
         }
 
-        Err(DidError::UnsupportedKeyType(did.to_string()))
+        let multicodec = did
+            .strip_prefix("did:wk:")
+            .and_then(|s| s.split('@').next())
+            .and_then(decode_multicodec);
+
+        Err(DidError::UnsupportedKeyType {
+            did: did.to_string(),
+            multicodec,
+        })
     }
 }
 
+#[cfg(feature = "panicking-from")]
 impl<'a> From<&str> for WrappedDidWebKey<'a> {
+    /// # Panics
+    ///
+    /// Panics if `did` is not a valid `did:wk` string. Use [`WrappedDidWebKey::try_parse`] instead
+    /// for untrusted input.
+    #[track_caller]
     fn from(did: &str) -> Self {
-        WrappedDidWebKey::from_str(did).unwrap()
+        WrappedDidWebKey::try_parse(did)
+            .unwrap_or_else(|e| panic!("invalid `did:wk` string `{did}`: {e}"))
     }
 }
 
+#[cfg(feature = "panicking-from")]
 impl<'a> From<String> for WrappedDidWebKey<'a> {
+    /// # Panics
+    ///
+    /// Panics if `did` is not a valid `did:wk` string. Use [`WrappedDidWebKey::try_parse`] instead
+    /// for untrusted input.
+    #[track_caller]
     fn from(did: String) -> Self {
-        WrappedDidWebKey::from_str(&did).unwrap()
+        WrappedDidWebKey::from(did.as_str())
     }
 }
 
+#[cfg(feature = "panicking-from")]
 impl<'a> From<&String> for WrappedDidWebKey<'a> {
+    /// # Panics
+    ///
+    /// Panics if `did` is not a valid `did:wk` string. Use [`WrappedDidWebKey::try_parse`] instead
+    /// for untrusted input.
+    #[track_caller]
     fn from(did: &String) -> Self {
-        WrappedDidWebKey::from_str(did).unwrap()
+        WrappedDidWebKey::from(did.as_str())
     }
 }
 
@@ -389,6 +627,12 @@ impl<'a> From<P256DidWebKey<'a>> for WrappedDidWebKey<'a> {
     }
 }
 
+impl<'a> From<P384DidWebKey<'a>> for WrappedDidWebKey<'a> {
+    fn from(did: P384DidWebKey<'a>) -> Self {
+        WrappedDidWebKey::P384(did)
+    }
+}
+
 impl<'a> From<Secp256k1DidWebKey<'a>> for WrappedDidWebKey<'a> {
     fn from(did: Secp256k1DidWebKey<'a>) -> Self {
         WrappedDidWebKey::Secp256k1(did)
@@ -415,6 +659,15 @@ impl<'de, 'a> Deserialize<'de> for WrappedDidWebKey<'a> {
     }
 }
 
+impl Serialize for WithBase<'_, WrappedDidWebKey<'_>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_as(self.1, serializer)
+    }
+}
+
 impl IntoOwned for WrappedDidWebKey<'_> {
     type Owned = WrappedDidWebKey<'static>;
 
@@ -422,6 +675,7 @@ impl IntoOwned for WrappedDidWebKey<'_> {
         match self {
             WrappedDidWebKey::Ed25519(wk) => WrappedDidWebKey::Ed25519(wk.into_owned()),
             WrappedDidWebKey::P256(wk) => WrappedDidWebKey::P256(wk.into_owned()),
+            WrappedDidWebKey::P384(wk) => WrappedDidWebKey::P384(wk.into_owned()),
             WrappedDidWebKey::Secp256k1(wk) => WrappedDidWebKey::Secp256k1(wk.into_owned()),
         }
     }
@@ -433,9 +687,16 @@ impl IntoOwned for WrappedDidWebKey<'_> {
 
 impl<P> Did for DidWebKey<P>
 where
-    P: KeyEncode + KeyDecode,
+    P: KeyEncode + KeyDecode + std::fmt::Debug + Send + Sync + 'static,
     DidError: From<P::Error>,
 {
+    fn method(&self) -> &str {
+        "wk"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
 }
 
 impl<P> Display for DidWebKey<P>
@@ -476,33 +737,51 @@ where
     }
 }
 
+#[cfg(feature = "panicking-from")]
 impl<P> From<&str> for DidWebKey<P>
 where
     P: KeyDecode,
     DidError: From<P::Error>,
 {
+    /// # Panics
+    ///
+    /// Panics if `did` is not a valid `did:wk` string. Use [`DidWebKey::try_parse`] instead for
+    /// untrusted input.
+    #[track_caller]
     fn from(did: &str) -> Self {
-        DidWebKey::from_str(did).unwrap()
+        DidWebKey::try_parse(did).unwrap_or_else(|e| panic!("invalid `did:wk` string `{did}`: {e}"))
     }
 }
 
+#[cfg(feature = "panicking-from")]
 impl<P> From<String> for DidWebKey<P>
 where
     P: KeyDecode,
     DidError: From<P::Error>,
 {
+    /// # Panics
+    ///
+    /// Panics if `did` is not a valid `did:wk` string. Use [`DidWebKey::try_parse`] instead for
+    /// untrusted input.
+    #[track_caller]
     fn from(did: String) -> Self {
-        DidWebKey::from_str(&did).unwrap()
+        DidWebKey::from(did.as_str())
     }
 }
 
+#[cfg(feature = "panicking-from")]
 impl<P> From<&String> for DidWebKey<P>
 where
     P: KeyDecode,
     DidError: From<P::Error>,
 {
+    /// # Panics
+    ///
+    /// Panics if `did` is not a valid `did:wk` string. Use [`DidWebKey::try_parse`] instead for
+    /// untrusted input.
+    #[track_caller]
     fn from(did: &String) -> Self {
-        DidWebKey::from_str(did).unwrap()
+        DidWebKey::from(did.as_str())
     }
 }
 
@@ -519,6 +798,18 @@ where
     }
 }
 
+impl<P> Serialize for WithBase<'_, DidWebKey<P>>
+where
+    P: KeyEncode,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize_as(self.1, serializer)
+    }
+}
+
 impl<'de, P> Deserialize<'de> for DidWebKey<P>
 where
     P: KeyDecode,
@@ -548,21 +839,48 @@ where
     }
 }
 
+impl<P> PartialEq for DidWebKey<P>
+where
+    P: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.public_key == other.public_key && self.locator_component == other.locator_component
+    }
+}
+
+impl<P> Eq for DidWebKey<P> where P: Eq {}
+
+impl<P> Hash for DidWebKey<P>
+where
+    P: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.public_key.hash(state);
+        self.locator_component.hash(state);
+    }
+}
+
 impl<P> PartialOrd for DidWebKey<P>
 where
-    P: KeyEncode + PartialEq,
+    P: Ord,
 {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.to_string().cmp(&other.to_string()))
+        Some(self.cmp(other))
     }
 }
 
 impl<P> Ord for DidWebKey<P>
 where
-    P: KeyEncode + Eq,
+    P: Ord,
 {
+    /// Compares the public keys and then the locator components.
+    ///
+    /// NOTE: This does not format the DIDs, so the order is not the lexicographic order of their string
+    /// forms.
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.to_string().cmp(&other.to_string())
+        self.public_key
+            .cmp(&other.public_key)
+            .then_with(|| self.locator_component.cmp(&other.locator_component))
     }
 }
 
@@ -572,9 +890,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate, P256KeyPair, Secp256k1KeyPair};
+    use std::collections::BTreeSet;
 
-    use crate::did_wk::Path;
+    use zeroutils_key::{
+        Ed25519KeyPair, KeyPairGenerate, P256KeyPair, P384KeyPair, PublicKeyBytes,
+        Secp256k1KeyPair, Sign, Verify,
+    };
+
+    use crate::{did_wk::Path, VerificationMethod};
 
     use super::*;
 
@@ -646,6 +969,103 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_wrapped_did_web_key_p384_roundtrip() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let key_pair = P384KeyPair::generate(rng)?;
+
+        let did_web_key = WrappedDidWebKey::from_key(&key_pair, Base::Base58Btc)?;
+        assert!(matches!(did_web_key, WrappedDidWebKey::P384(_)));
+
+        let decoded = WrappedDidWebKey::from_str(&did_web_key.to_string())?;
+        assert_eq!(decoded, did_web_key);
+
+        // Signatures verify against the public key resolved from the DID.
+        let signature = key_pair.sign(b"hello")?;
+        decoded.public_key().verify(b"hello", &signature)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped_did_web_key_from_str_unsupported_key_type() -> anyhow::Result<()> {
+        let mut x25519_key = vec![0xEC, 0x01];
+        x25519_key.extend([7; 32]);
+        let did_string = format!("did:wk:{}", Base::Base58Btc.encode(&x25519_key));
+
+        let err = WrappedDidWebKey::from_str(&did_string).unwrap_err();
+        assert!(matches!(
+            err,
+            DidError::UnsupportedKeyType {
+                multicodec: Some(0xec),
+                ..
+            }
+        ));
+
+        let message = err.to_string();
+        assert!(message.contains("0xec"));
+        assert!(message.contains("ed25519 (0xed), p256 (0x1200), p384 (0x1201), secp256k1 (0xe7)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped_did_web_key_try_parse() -> anyhow::Result<()> {
+        let did_string = "did:wk:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq";
+        assert_eq!(
+            WrappedDidWebKey::try_parse(did_string)?,
+            WrappedDidWebKey::from_str(did_string)?
+        );
+
+        // Malformed input is reported as an error instead of panicking.
+        assert!(matches!(
+            WrappedDidWebKey::try_parse("did:xyz:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq"),
+            Err(DidError::InvalidMethod)
+        ));
+        assert!(Ed25519DidWebKey::try_parse("did:web:example.com").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped_did_web_key_from_str_no_locator() -> anyhow::Result<()> {
+        let did_string = "did:wk:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq";
+        let did_web_key = WrappedDidWebKey::from_str_no_locator(did_string)?;
+        assert_eq!(did_web_key, WrappedDidWebKey::from_str(did_string)?);
+        assert!(did_web_key.locator_component().is_none());
+
+        // With locator component
+        for did_string in [
+            "did:wk:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq@steve.zerocore.ai:8080/public",
+            "did:wk:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq@steve.zerocore.ai",
+        ] {
+            assert!(WrappedDidWebKey::from_str(did_string)?
+                .locator_component()
+                .is_some());
+            assert!(matches!(
+                WrappedDidWebKey::from_str_no_locator(did_string),
+                Err(DidError::UnexpectedLocator(did)) if did == did_string
+            ));
+        }
+
+        // Other errors are reported as usual
+        assert!(matches!(
+            WrappedDidWebKey::from_str_no_locator(
+                "did:xyz:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq"
+            ),
+            Err(DidError::InvalidMethod)
+        ));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "panicking-from")]
+    #[test]
+    #[should_panic(expected = "invalid `did:wk` string")]
+    fn test_wrapped_did_web_key_from_str_panics_on_invalid_input() {
+        let _ = WrappedDidWebKey::from("did:xyz:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq");
+    }
+
     #[test]
     fn test_did_web_key_display() -> anyhow::Result<()> {
         let did_string = "did:wk:z6Mkiyk3sxtq4QAR9etUibQAfj2FU1PU4jAw8Hd4ivHxYzAq";
@@ -728,6 +1148,77 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_did_web_key_serialize_as() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+
+        let public_key = Ed25519PubKey::from(Ed25519KeyPair::generate(rng)?);
+        let did_web_key = DidWebKey {
+            public_key,
+            base: Base::Base58Btc,
+            locator_component: Some(LocatorComponent::new(
+                "steve.zerocore.ai",
+                8080,
+                Path::from("/public"),
+            )),
+        };
+
+        let base58btc = serde_json::to_string(&did_web_key)?;
+        let base32 = serde_json::to_string(&WithBase(&did_web_key, Base::Base32Lower))?;
+        assert!(base58btc.starts_with("\"did:wk:z"));
+        assert!(base32.starts_with("\"did:wk:b"));
+        assert_ne!(base58btc, base32);
+
+        let from_base58btc: Ed25519DidWebKey = serde_json::from_str(&base58btc)?;
+        let from_base32: Ed25519DidWebKey = serde_json::from_str(&base32)?;
+        assert_eq!(from_base32.base(), Base::Base32Lower);
+        assert_eq!(from_base58btc, did_web_key);
+        assert_eq!(from_base32, did_web_key);
+
+        // Same for the wrapped type.
+        let wrapped = WrappedDidWebKey::from(did_web_key);
+        let base32 = serde_json::to_string(&WithBase(&wrapped, Base::Base32Lower))?;
+        assert_eq!(serde_json::from_str::<WrappedDidWebKey>(&base32)?, wrapped);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_did_web_key_ord() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+
+        let dids = (0..2000)
+            .map(|i| {
+                let public_key = Ed25519PubKey::from(Ed25519KeyPair::generate(rng)?);
+                let locator_component = (i % 2 == 0)
+                    .then(|| LocatorComponent::new("steve.zerocore.ai", 8080, Path::from("/")));
+                Ok(DidWebKey {
+                    public_key,
+                    base: Base::Base58Btc,
+                    locator_component,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let set = dids.iter().cloned().collect::<BTreeSet<_>>();
+        assert_eq!(set.len(), dids.len());
+        assert!(dids.iter().all(|did| set.contains(did)));
+
+        // Sorted by public key bytes.
+        let sorted = set.iter().collect::<Vec<_>>();
+        assert!(sorted
+            .windows(2)
+            .all(|w| w[0].public_key.public_key_bytes() < w[1].public_key.public_key_bytes()));
+
+        // The base encoding does not affect the order.
+        let mut did = dids[0].clone();
+        did.base = Base::Base32Lower;
+        assert_eq!(did.cmp(&dids[0]), std::cmp::Ordering::Equal);
+        assert!(set.contains(&did));
+
+        Ok(())
+    }
+
     #[test]
     fn test_did_web_key_wrap_into_inner() -> anyhow::Result<()> {
         let rng = &mut rand::thread_rng();
@@ -757,4 +1248,120 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_did_web_key_verify_document() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let key_pair = P256KeyPair::generate(rng)?;
+        let did = DidWebKey::from_key(&key_pair, Base::Base58Btc);
+        let did_str = did.to_string();
+
+        let method = |public_key_multibase: String| VerificationMethod {
+            id: format!("{did_str}#key-0"),
+            type_: "Multikey".to_string(),
+            controller: did_str.clone(),
+            public_key_multibase,
+        };
+
+        let doc = DidDocument {
+            id: did_str.clone(),
+            verification_method: vec![method(did.public_key().encode(Base::Base64))],
+        };
+        did.verify_document(&doc)?;
+
+        // The same DID in another base.
+        let other_base_str = DidWebKey::from_key(&key_pair, Base::Base64).to_string();
+        assert_ne!(other_base_str, did_str);
+        let other_base = DidDocument {
+            id: other_base_str.clone(),
+            verification_method: vec![VerificationMethod {
+                controller: other_base_str,
+                ..method(did.public_key().encode(Base::Base58Btc))
+            }],
+        };
+        did.verify_document(&other_base)?;
+
+        // Another key.
+        let other = P256KeyPair::generate(rng)?;
+        let mut tampered = doc.clone();
+        tampered.verification_method = vec![method(
+            DidWebKey::from_key(&other, Base::Base58Btc)
+                .public_key()
+                .encode(Base::Base58Btc),
+        )];
+        assert!(matches!(
+            did.verify_document(&tampered),
+            Err(DidError::InvalidDocument(_))
+        ));
+
+        // Another id.
+        let mut tampered = doc.clone();
+        tampered.id = DidWebKey::from_key(&other, Base::Base58Btc).to_string();
+        assert!(matches!(
+            did.verify_document(&tampered),
+            Err(DidError::InvalidDocument(_))
+        ));
+
+        // No verification methods.
+        let mut tampered = doc;
+        tampered.verification_method.clear();
+        assert!(did.verify_document(&tampered).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped_did_web_key_parse_many() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let ed25519 = WrappedDidWebKey::from_key(&Ed25519KeyPair::generate(rng)?, Base::Base58Btc)?;
+        let p256 = WrappedDidWebKey::from_key(&P256KeyPair::generate(rng)?, Base::Base32Lower)?;
+
+        let inputs = [
+            ed25519.to_string(),
+            "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp".to_string(),
+            p256.to_string(),
+            "did:wk:not-a-key".to_string(),
+        ];
+
+        let (parsed, failed) = WrappedDidWebKey::parse_many(&inputs);
+        assert_eq!(parsed, [ed25519, p256]);
+        assert_eq!(
+            failed.iter().map(|(did, _)| did).collect::<Vec<_>>(),
+            [&inputs[1], &inputs[3]]
+        );
+        assert!(matches!(failed[0].1, DidError::InvalidMethod));
+
+        let (parsed, failed) = WrappedDidWebKey::parse_many(Vec::<String>::new());
+        assert!(parsed.is_empty() && failed.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_did_web_key_canonical_bytes() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let key_pair = Ed25519KeyPair::generate(rng)?;
+
+        let base58 = DidWebKey::from_key(&key_pair, Base::Base58Btc);
+        let base64 = DidWebKey::from_key(&key_pair, Base::Base64);
+        assert_ne!(base58.to_string(), base64.to_string());
+        assert_eq!(base58.canonical_bytes(), base64.canonical_bytes());
+
+        let wrapped = WrappedDidWebKey::from_str(&base64.to_string())?;
+        assert_eq!(wrapped.canonical_bytes(), base58.canonical_bytes());
+
+        // The locator component is part of the canonical form.
+        let located = DidWebKey::<Ed25519PubKey>::from_str(&format!("{base58}@steve.zerocore.ai"))?;
+        assert_ne!(located.canonical_bytes(), base58.canonical_bytes());
+        assert!(located
+            .canonical_bytes()
+            .starts_with(&base58.canonical_bytes()));
+
+        // Another key has different canonical bytes.
+        let other_key = Ed25519KeyPair::generate(rng)?;
+        let other = DidWebKey::from_key(&other_key, Base::Base58Btc);
+        assert_ne!(other.canonical_bytes(), base58.canonical_bytes());
+
+        Ok(())
+    }
 }
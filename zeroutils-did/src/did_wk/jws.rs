@@ -0,0 +1,56 @@
+use zeroutils_key::{recover_jws_signer, IntoOwned};
+
+use crate::{Base, DidResult};
+
+use super::WrappedDidWebKey;
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Returns the `did:wk` of the signer of a compact JWS.
+///
+/// The signer is found with [`recover_jws_signer`], so the JWS must either embed the public key in its
+/// `jwk` header or be an `ES256K` JWS with a recoverable signature. The DID is encoded in `base58btc`.
+pub fn recover_did(jws: &str) -> DidResult<WrappedDidWebKey<'static>> {
+    let public_key = recover_jws_signer(jws)?;
+    Ok(WrappedDidWebKey::from_wrapped_pub_key(&public_key, Base::Base58Btc).into_owned())
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use zeroutils_key::{
+        sign_detached, sign_with_jwk, Ed25519KeyPair, KeyPairGenerate, P256KeyPair, WrappedKeyPair,
+    };
+
+    use crate::DidError;
+
+    use super::*;
+
+    #[test]
+    fn test_recover_did() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+
+        let key_pair = Ed25519KeyPair::generate(rng)?;
+        let did = WrappedDidWebKey::from_key(&key_pair, Base::Base58Btc)?;
+        let jws = sign_with_jwk(&WrappedKeyPair::from(key_pair), b"hello world")?;
+        assert_eq!(recover_did(&jws)?, did);
+        assert_eq!(recover_did(&jws)?.to_string(), did.to_string());
+
+        let key_pair = P256KeyPair::generate(rng)?;
+        let did = WrappedDidWebKey::from_key(&key_pair, Base::Base58Btc)?;
+        let jws = sign_with_jwk(&WrappedKeyPair::from(key_pair), b"hello world")?;
+        assert_eq!(recover_did(&jws)?, did);
+
+        // Without an embedded key, the signer is unknown.
+        let key_pair = Ed25519KeyPair::generate(rng)?;
+        let jws = sign_detached(&key_pair, b"hello world")?;
+        assert!(matches!(recover_did(&jws), Err(DidError::KeyError(_))));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use zeroutils_key::{IntoOwned, Multikey, Sign, WrappedKeyPair};
+
+use crate::{Base, DidError, DidResult};
+
+use super::WrappedDidWebKey;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A collection of key pairs indexed by their `did:wk`.
+///
+/// This is useful for services that manage several identities, for example to issue UCANs on behalf of
+/// multiple tenants.
+///
+/// Key pairs are looked up by the canonical bytes of the public key of a DID, so a key pair can be
+/// found with its DID in any base encoding and with or without a locator component.
+#[derive(Default)]
+pub struct Keychain {
+    keys: BTreeMap<Vec<u8>, (WrappedDidWebKey<'static>, WrappedKeyPair<'static>)>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl Keychain {
+    /// Creates an empty keychain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a key pair to the keychain and returns its DID.
+    ///
+    /// A key pair that is already in the keychain is replaced.
+    pub fn add(
+        &mut self,
+        key_pair: impl Into<WrappedKeyPair<'static>>,
+    ) -> WrappedDidWebKey<'static> {
+        let key_pair = key_pair.into();
+        let did = WrappedDidWebKey::from_wrapped_key_pair(&key_pair, Base::Base58Btc).into_owned();
+        self.keys.insert(key_id(&did), (did.clone(), key_pair));
+        did
+    }
+
+    /// Gets the key pair of the given DID.
+    pub fn get(&self, did: &WrappedDidWebKey) -> Option<&WrappedKeyPair<'static>> {
+        self.keys.get(&key_id(did)).map(|(_, key_pair)| key_pair)
+    }
+
+    /// Removes the key pair of the given DID from the keychain and returns it.
+    pub fn remove(&mut self, did: &WrappedDidWebKey) -> Option<WrappedKeyPair<'static>> {
+        self.keys.remove(&key_id(did)).map(|(_, key_pair)| key_pair)
+    }
+
+    /// Signs `msg` with the key pair of the given DID.
+    pub fn sign_as(&self, did: &WrappedDidWebKey, msg: &[u8]) -> DidResult<Vec<u8>> {
+        let key_pair = self
+            .get(did)
+            .ok_or_else(|| DidError::KeyNotFound(did.to_string()))?;
+
+        Ok(key_pair.sign(msg)?)
+    }
+
+    /// Returns an iterator over the DIDs in the keychain.
+    pub fn dids(&self) -> impl Iterator<Item = &WrappedDidWebKey<'static>> {
+        self.keys.values().map(|(did, _)| did)
+    }
+
+    /// Returns the number of key pairs in the keychain.
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the keychain has no key pairs.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Returns the multicodec prefixed public key bytes of `did`, which identify its key pair.
+fn key_id(did: &WrappedDidWebKey) -> Vec<u8> {
+    Multikey::to_bytes(&did.public_key())
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use zeroutils_key::{
+        Ed25519KeyPair, KeyPairGenerate, P256KeyPair, P384KeyPair, Secp256k1KeyPair, Verify,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_keychain_add_and_sign_as() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let mut keychain = Keychain::new();
+
+        let dids = [
+            keychain.add(Ed25519KeyPair::generate(rng)?),
+            keychain.add(P256KeyPair::generate(rng)?),
+            keychain.add(P384KeyPair::generate(rng)?),
+            keychain.add(Secp256k1KeyPair::generate(rng)?),
+        ];
+
+        assert_eq!(keychain.len(), 4);
+        assert!(dids.iter().all(|did| keychain.dids().any(|d| d == did)));
+
+        for did in &dids {
+            let signature = keychain.sign_as(did, b"hello")?;
+            did.public_key().verify(b"hello", &signature)?;
+        }
+
+        // Lookup does not depend on the base encoding of the DID.
+        let did = WrappedDidWebKey::from_str(&dids[0].encode(Base::Base32Lower))?;
+        assert!(keychain.get(&did).is_some());
+
+        let signature = keychain.sign_as(&did, b"hello")?;
+        dids[0].public_key().verify(b"hello", &signature)?;
+
+        // Lookup ignores the locator component of the DID.
+        let did = WrappedDidWebKey::from_str(&format!("{}@example.com", dids[1]))?;
+        assert!(did.locator_component().is_some());
+        assert!(keychain.get(&did).is_some());
+
+        let signature = keychain.sign_as(&did, b"hello")?;
+        dids[1].public_key().verify(b"hello", &signature)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_keychain_unknown_did() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let mut keychain = Keychain::new();
+
+        let did = keychain.add(Ed25519KeyPair::generate(rng)?);
+        let outsider =
+            WrappedDidWebKey::from_key(&Ed25519KeyPair::generate(rng)?, Base::Base58Btc)?;
+
+        assert!(keychain.get(&outsider).is_none());
+        assert!(matches!(
+            keychain.sign_as(&outsider, b"hello"),
+            Err(DidError::KeyNotFound(_))
+        ));
+
+        assert!(keychain.remove(&did).is_some());
+        assert!(keychain.is_empty());
+        assert!(keychain.sign_as(&did, b"hello").is_err());
+
+        Ok(())
+    }
+}
@@ -96,6 +96,24 @@ impl LocatorComponent {
     }
 }
 
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Parses the port of a locator component. An empty port is treated as absent, as allowed by
+/// [RFC 3986][ref].
+///
+/// [ref]: https://datatracker.ietf.org/doc/html/rfc3986#section-3.2.3
+fn parse_port(port: &str) -> Result<Option<u16>, DidError> {
+    if port.is_empty() {
+        return Ok(None);
+    }
+
+    port.parse()
+        .map(Some)
+        .map_err(|_| DidError::InvalidPort(port.to_owned()))
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
@@ -178,41 +196,36 @@ impl FromStr for LocatorComponent {
     type Err = DidError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let port_split: Vec<&str> = s.splitn(2, ':').collect();
-        let (host, port, path) = match port_split.len() {
-            1 => {
-                let host_path: Option<(&str, &str)> =
-                    port_split[0].find('/').map(|i| port_split[0].split_at(i));
-                let (host, path): (Host, Option<Path>) = match host_path {
-                    Some((host, path)) => {
-                        let host = host.parse()?;
-                        let path = path.parse().ok();
-
-                        (host, path)
-                    }
-                    None => (port_split[0].parse()?, None),
-                };
-                (host, None, path)
-            }
-            2 => {
-                let host = port_split[0];
-                let port_path = port_split[1].find('/').map(|i| port_split[1].split_at(i));
-                let (port, path): (Option<u16>, Option<Path>) = match port_path {
-                    Some((port, path)) => {
-                        let port = port.parse().ok();
-                        let path = path.parse().ok();
-
-                        (port, path)
-                    }
-                    None => (port_split[1].parse().ok(), None),
-                };
-
-                (host.parse()?, port, path)
-            }
+        // IP literals are enclosed in brackets and contain `:` themselves.
+        let host_end = if s.starts_with('[') {
+            s.find(']')
+                .map(|i| i + 1)
+                .ok_or_else(|| DidError::InvalidHost(s.to_owned()))?
+        } else {
+            s.find([':', '/']).unwrap_or(s.len())
+        };
+
+        let (host, rest) = s.split_at(host_end);
+        if host.is_empty() {
+            return Err(DidError::InvalidHost(s.to_owned()));
+        }
+
+        let (port, path) = match rest.strip_prefix(':') {
+            Some(port_path) => port_path.split_at(port_path.find('/').unwrap_or(port_path.len())),
+            None => ("", rest),
+        };
+
+        let path = match path {
+            "" => None,
+            path if path.starts_with('/') => Some(path.parse()?),
             _ => return Err(DidError::InvalidLocatorComponent(s.to_owned())),
         };
 
-        Ok(LocatorComponent::new(host, port, path))
+        Ok(LocatorComponent::new(
+            host.parse::<Host>()?,
+            parse_port(port)?,
+            path,
+        ))
     }
 }
 
@@ -311,11 +324,92 @@ mod tests {
         );
         assert_eq!(
             LocatorComponent::from_str(locator_no_path)?,
-            LocatorComponent::new("192.168.123.132", Some(443), None) // Gotta fix!
+            LocatorComponent::new("192.168.123.132", Some(443), None)
         );
         assert_eq!(
             LocatorComponent::from_str(locator_no_port_or_path)?,
-            LocatorComponent::new("steve.zerocore.ai", None, None) // Gotta fix!
+            LocatorComponent::new("steve.zerocore.ai", None, None)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_locator_from_str_path_edge_cases() -> anyhow::Result<()> {
+        // A bare `/` path right after the port.
+        let locator = LocatorComponent::from_str("steve.zerocore.ai:443/")?;
+        assert_eq!(
+            locator,
+            LocatorComponent::new("steve.zerocore.ai", 443, Path::from("/"))
+        );
+        assert_eq!(locator.to_string(), "steve.zerocore.ai:443/");
+
+        // No path is different from an empty path.
+        let locator = LocatorComponent::from_str("steve.zerocore.ai:443")?;
+        assert_eq!(locator.path(), None);
+        assert_eq!(locator.to_string(), "steve.zerocore.ai:443");
+
+        // Percent-encoded characters are kept as is.
+        let locator = LocatorComponent::from_str("steve.zerocore.ai/%2Fencoded")?;
+        assert_eq!(
+            locator,
+            LocatorComponent::new("steve.zerocore.ai", None, Path::from("/%2Fencoded"))
+        );
+        assert_eq!(locator.to_string(), "steve.zerocore.ai/%2Fencoded");
+
+        // IP literals contain `:`.
+        let locator = LocatorComponent::from_str("[2001:db8::1]:8080/public")?;
+        assert_eq!(
+            locator,
+            LocatorComponent::new("[2001:db8::1]", 8080, Path::from("/public"))
+        );
+
+        // Invalid paths are errors rather than being dropped.
+        assert!(matches!(
+            LocatorComponent::from_str("steve.zerocore.ai/a b"),
+            Err(DidError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            LocatorComponent::from_str("steve.zerocore.ai:443/%zz"),
+            Err(DidError::InvalidPath(_))
+        ));
+
+        // Missing host.
+        assert!(matches!(
+            LocatorComponent::from_str(":443/public"),
+            Err(DidError::InvalidHost(_))
+        ));
+        assert!(matches!(
+            LocatorComponent::from_str("[2001:db8::1"),
+            Err(DidError::InvalidHost(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_locator_from_str_invalid_port() -> anyhow::Result<()> {
+        assert!(matches!(
+            LocatorComponent::from_str("steve.zerocore.ai:99999"),
+            Err(DidError::InvalidPort(port)) if port == "99999"
+        ));
+        assert!(matches!(
+            LocatorComponent::from_str("steve.zerocore.ai:99999/public"),
+            Err(DidError::InvalidPort(port)) if port == "99999"
+        ));
+        assert!(matches!(
+            LocatorComponent::from_str("steve.zerocore.ai:abc"),
+            Err(DidError::InvalidPort(port)) if port == "abc"
+        ));
+
+        // An absent port is not an error.
+        assert_eq!(
+            LocatorComponent::from_str("steve.zerocore.ai")?.port(),
+            None
+        );
+        assert_eq!(
+            LocatorComponent::from_str("steve.zerocore.ai:/public")?.port(),
+            None
         );
 
         Ok(())
@@ -2,6 +2,8 @@
 
 mod builder;
 mod did;
+mod jws;
+mod keychain;
 mod locator;
 mod regex;
 
@@ -11,5 +13,7 @@ mod regex;
 
 pub use builder::*;
 pub use did::*;
+pub use jws::*;
+pub use keychain::*;
 pub use locator::*;
 pub use regex::*;
@@ -98,12 +98,12 @@ lazy_static! {
         ({H16}:){{6}}{LS32}
         | ::({H16}:){{5}}{LS32}
         | ({H16})?::({H16}:){{4}}{LS32}
-        | (({H16}:){{0, 1}}{H16})?::({H16}:){{3}}{LS32}
-        | (({H16}:){{0, 2}}{H16})?::({H16}:){{2}}{LS32}
-        | (({H16}:){{0, 3}}{H16})?::{H16}:{LS32}
-        | (({H16}:){{0, 4}}{H16})?::{LS32}
-        | (({H16}:){{0, 5}}{H16})?::{H16}
-        | (({H16}:){{0, 6}}{H16})?::
+        | (({H16}:){{0,1}}{H16})?::({H16}:){{3}}{LS32}
+        | (({H16}:){{0,2}}{H16})?::({H16}:){{2}}{LS32}
+        | (({H16}:){{0,3}}{H16})?::{H16}:{LS32}
+        | (({H16}:){{0,4}}{H16})?::{LS32}
+        | (({H16}:){{0,5}}{H16})?::{H16}
+        | (({H16}:){{0,6}}{H16})?::
         )"#,
         H16 = *H16,
         LS32 = *LS32
@@ -113,7 +113,7 @@ lazy_static! {
         H16 = *H16,
         IPV4ADDR = *IPV4ADDR
     );
-    static ref H16: String = format!(r"[0-9a-fA-F]{{1, 4}}");
+    static ref H16: String = format!(r"[0-9a-fA-F]{{1,4}}");
     static ref IPV4ADDR: String = format!(
         r#"(
         ([0-9]|[1-9][0-9]|1[0-9]{{2}}|2[0-4][0-9]|25[0-5])\.
@@ -407,9 +407,25 @@ mod tests {
             assert!(re.is_match(x));
         }
 
+        for x in [
+            "1:2:3:4:5:6:7::",
+            "1::8",
+            "1:2::7:8",
+            "1:2:3::6:7:8",
+            "::ffff:0:0",
+            "64:ff9b::192.0.2.33",
+        ] {
+            assert!(re.is_match(x));
+        }
+
+        // An address without `::` must have all eight groups and groups have at most four digits.
         for x in [
             "2001:db8:85a3:0:8a2e:370:7334",
             "2001:db8:85a3:0:8a2e:184.45.6.255",
+            "12345::",
+            "::12345",
+            "1:2:3:4:5:6:7:8:9",
+            "1::2::3",
             "0db8::2001:db8:85a3:0:8a2e:370:7334",
             "0db8::2001:db8:85a3:0:8a2e:184.45.6.255",
             "2001:0db8:0000:0000:8a2e:0370::0370:184.45.6.255",
@@ -1,12 +1,38 @@
+use serde::{Deserialize, Serialize};
+
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
 
 /// A `did:wk` DID document.
-pub struct DidDocument {}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    /// The DID the document describes.
+    pub id: String,
+
+    /// The verification methods of the DID.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub verification_method: Vec<VerificationMethod>,
+}
 
 /// A `did:wk` verification method.
-pub struct VerificationMethod {}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    /// The id of the verification method, usually `<did>#<key>`.
+    pub id: String,
+
+    /// The type of the verification method, e.g. `Multikey`.
+    #[serde(rename = "type")]
+    pub type_: String,
+
+    /// The DID that controls the verification method.
+    pub controller: String,
+
+    /// The public key encoded as a [`Multikey`][zeroutils_key::Multikey].
+    pub public_key_multibase: String,
+}
 
 //--------------------------------------------------------------------------------------------------
 // Methods
@@ -18,9 +18,27 @@ pub enum DidError {
     #[error("Expected the `did:wk` method.")]
     InvalidMethod,
 
+    /// Invalid DID.
+    #[error("Expected a `did:<method>:<id>` DID: {0}")]
+    InvalidDid(String),
+
+    /// No method registered for the DID.
+    #[error("Unsupported DID method: {0}")]
+    UnsupportedMethod(String),
+
     /// Unsupported key type.
-    #[error("Unsupported key type: {0}")]
-    UnsupportedKeyType(String),
+    #[error(
+        "Unsupported key type in `{did}`: multicodec {code}, expected one of {supported}",
+        code = .multicodec.map_or("unknown".to_string(), |code| format!("{code:#x}")),
+        supported = crate::SUPPORTED_KEY_TYPES.join(", ")
+    )]
+    UnsupportedKeyType {
+        /// The DID that failed to parse.
+        did: String,
+
+        /// The multicodec code of the key, if it could be decoded.
+        multicodec: Option<u64>,
+    },
 
     /// Invalid host.
     #[error("Expected a valid host domain, ipv4 or ipv6 address: {0}")]
@@ -38,10 +56,22 @@ pub enum DidError {
     #[error("Invalid locator component: {0}")]
     InvalidLocatorComponent(String),
 
+    /// A locator component where none is allowed.
+    #[error("Unexpected locator component in DID: {0}")]
+    UnexpectedLocator(String),
+
     /// Expected an certain key type.
     #[error("Expected a {0} key type.")]
     ExpectedKeyType(String),
 
+    /// The DID document does not belong to the DID.
+    #[error("Invalid DID document: {0}")]
+    InvalidDocument(String),
+
+    /// No key pair for the DID in the keychain.
+    #[error("No key pair found for DID: {0}")]
+    KeyNotFound(String),
+
     /// Key error.
     #[error("Key error: {0}")]
     KeyError(#[from] zeroutils_key::KeyError),
@@ -4,9 +4,9 @@
 #![warn(missing_docs)]
 #![allow(clippy::module_inception)]
 
-mod base;
 mod doc;
 mod error;
+mod registry;
 mod traits;
 
 //--------------------------------------------------------------------------------------------------
@@ -16,7 +16,8 @@ mod traits;
 pub mod did_key;
 pub mod did_wk;
 
-pub use base::*;
 pub use doc::*;
 pub use error::*;
+pub use registry::*;
 pub use traits::*;
+pub use zeroutils_key::Base;
@@ -0,0 +1,220 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+use crate::{did_wk::WrappedDidWebKey, Did, DidError, DidResult};
+
+//--------------------------------------------------------------------------------------------------
+// Traits
+//--------------------------------------------------------------------------------------------------
+
+/// A DID method that can parse DIDs of the form `did:<name>:<id>`.
+pub trait DidMethod: Send + Sync {
+    /// Returns the name of the method, e.g. `wk` for `did:wk`.
+    fn name(&self) -> &str;
+
+    /// Parses a DID of this method.
+    fn parse(&self, did: &str) -> DidResult<Box<dyn Did>>;
+}
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// The [`DidMethod`] of [`did:wk`][crate::did_wk] DIDs.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DidWebKeyMethod;
+
+/// A registry of DID methods.
+///
+/// The registry dispatches on the `did:<method>:` prefix of a DID to the registered [`DidMethod`], so new
+/// methods can be supported without changing the callers.
+pub struct DidRegistry {
+    methods: BTreeMap<String, Box<dyn DidMethod>>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl DidRegistry {
+    /// Creates a registry with the methods supported by this crate.
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(DidWebKeyMethod);
+        registry
+    }
+
+    /// Creates a registry with no methods.
+    pub fn empty() -> Self {
+        Self {
+            methods: BTreeMap::new(),
+        }
+    }
+
+    /// Registers a DID method and returns the method it replaced, if any.
+    pub fn register(&mut self, method: impl DidMethod + 'static) -> Option<Box<dyn DidMethod>> {
+        self.methods
+            .insert(method.name().to_string(), Box::new(method))
+    }
+
+    /// Returns `true` if a method with the given name is registered.
+    pub fn contains(&self, name: &str) -> bool {
+        self.methods.contains_key(name)
+    }
+
+    /// Parses a DID with the method registered for its `did:<method>:` prefix.
+    pub fn parse_did(&self, did: &str) -> DidResult<Box<dyn Did>> {
+        let name = did
+            .strip_prefix("did:")
+            .and_then(|s| s.split_once(':'))
+            .map(|(name, _)| name)
+            .ok_or_else(|| DidError::InvalidDid(did.to_string()))?;
+
+        let method = self
+            .methods
+            .get(name)
+            .ok_or_else(|| DidError::UnsupportedMethod(name.to_string()))?;
+
+        method.parse(did)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Parses a DID with any of the methods supported by this crate.
+///
+/// Use a [`DidRegistry`] to parse DIDs of other methods.
+pub fn parse_did(did: &str) -> DidResult<Box<dyn Did>> {
+    DidRegistry::new().parse_did(did)
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl DidMethod for DidWebKeyMethod {
+    fn name(&self) -> &str {
+        "wk"
+    }
+
+    fn parse(&self, did: &str) -> DidResult<Box<dyn Did>> {
+        Ok(Box::new(WrappedDidWebKey::from_str(did)?))
+    }
+}
+
+impl Default for DidRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate};
+
+    use crate::Base;
+
+    use super::*;
+
+    use fixtures::{ExampleDid, ExampleMethod};
+
+    #[test]
+    fn test_did_registry_parse_did() -> anyhow::Result<()> {
+        let mut registry = DidRegistry::new();
+        assert!(registry.register(ExampleMethod).is_none());
+
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let wk = WrappedDidWebKey::from_key(&key_pair, Base::Base58Btc)?.to_string();
+
+        let did = registry.parse_did(&wk)?;
+        assert_eq!(did.method(), "wk");
+        assert_eq!(did.to_string(), wk);
+
+        // The parsed DID can be downcast to the type of its method.
+        let wk_did = did
+            .downcast_ref::<WrappedDidWebKey<'static>>()
+            .ok_or_else(|| anyhow::anyhow!("expected a did:wk"))?;
+        assert_eq!(wk_did.encode(Base::Base58Btc), wk);
+
+        let did = registry.parse_did("did:example:123")?;
+        assert_eq!(did.method(), "example");
+        assert_eq!(did.to_string(), "did:example:123");
+        assert!(did.downcast_ref::<WrappedDidWebKey<'static>>().is_none());
+        assert_eq!(
+            did.downcast_ref::<ExampleDid>().map(|d| d.0.as_str()),
+            Some("123")
+        );
+
+        assert!(matches!(
+            registry.parse_did("did:web:example.com"),
+            Err(DidError::UnsupportedMethod(m)) if m == "web"
+        ));
+        assert!(matches!(
+            registry.parse_did("example:123"),
+            Err(DidError::InvalidDid(_))
+        ));
+
+        // The free function only knows the built-in methods.
+        assert_eq!(parse_did(&wk)?.to_string(), wk);
+        assert!(parse_did("did:example:123").is_err());
+
+        Ok(())
+    }
+
+    mod fixtures {
+        use std::{
+            any::Any,
+            fmt::{self, Display},
+        };
+
+        use super::*;
+
+        //--------------------------------------------------------------------------------------------------
+        // Types
+        //--------------------------------------------------------------------------------------------------
+
+        pub(super) struct ExampleMethod;
+
+        #[derive(Debug)]
+        pub(super) struct ExampleDid(pub(super) String);
+
+        //--------------------------------------------------------------------------------------------------
+        // Trait Implementations
+        //--------------------------------------------------------------------------------------------------
+
+        impl DidMethod for ExampleMethod {
+            fn name(&self) -> &str {
+                "example"
+            }
+
+            fn parse(&self, did: &str) -> DidResult<Box<dyn Did>> {
+                let id = did
+                    .strip_prefix("did:example:")
+                    .ok_or_else(|| DidError::InvalidDid(did.to_string()))?;
+
+                Ok(Box::new(ExampleDid(id.to_string())))
+            }
+        }
+
+        impl Did for ExampleDid {
+            fn method(&self) -> &str {
+                "example"
+            }
+
+            fn as_any(&self) -> &dyn Any {
+                self
+            }
+        }
+
+        impl Display for ExampleDid {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "did:example:{}", self.0)
+            }
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use std::{fmt::Display, str::FromStr};
+use std::{
+    any::Any,
+    fmt::{Debug, Display},
+};
 
 use anyhow::Result;
 
 use zeroutils_key::{
-    Ed25519PubKey, P256PubKey, PublicKeyBytes, PublicKeyGenerate, Secp256k1PubKey,
+    Ed25519PubKey, GetPublicKey, KeyError, Multikey, P256PubKey, P384PubKey, Secp256k1PubKey,
+    WrappedPubKey,
 };
 
 use super::{Base, DidError};
@@ -13,7 +17,16 @@ use super::{Base, DidError};
 //--------------------------------------------------------------------------------------------------
 
 /// A trait for working with decentralized identifier (DID) types.
-pub trait Did: Display + FromStr {
+///
+/// The trait is object safe so that DIDs of different methods can be handled as `Box<dyn Did>`. See
+/// [`DidRegistry`][crate::DidRegistry].
+pub trait Did: Display + Debug + Send + Sync {
+    /// Returns the name of the DID method, e.g. `wk` for `did:wk`.
+    fn method(&self) -> &str;
+
+    /// Returns the DID as [`Any`], so that a `dyn Did` can be downcast to its concrete type.
+    fn as_any(&self) -> &dyn Any;
+
     // fn verification_method(&self) -> String;
     // fn fetch_did_document(&self) -> impl Future<Output = DidDocument>;
 }
@@ -28,6 +41,13 @@ pub trait KeyEncode {
     /// [multicodec]: https://github.com/multiformats/multicodec
     /// [multibase]: https://github.com/multiformats/multibase
     fn encode(&self, base: Base) -> String;
+
+    /// Returns the public key bytes prefixed with their [Multicodec][multicodec] code.
+    ///
+    /// Unlike [`encode`][KeyEncode::encode], this does not depend on a base encoding.
+    ///
+    /// [multicodec]: https://github.com/multiformats/multicodec
+    fn multikey_bytes(&self) -> Vec<u8>;
 }
 
 /// A trait for decoding public keys from a DID [Web] Key format.
@@ -45,18 +65,52 @@ pub trait KeyDecode {
         Self: Sized;
 }
 
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl dyn Did {
+    /// Returns the DID as a `T` if it is one.
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: Did + 'static,
+    {
+        self.as_any().downcast_ref()
+    }
+}
+
 //--------------------------------------------------------------------------------------------------
 // Constants
 //--------------------------------------------------------------------------------------------------
 
-/// `ed25519-pub` varuint public key code and varuint representation.
-const ED25519_PUB_KEY_CODE: (u8, [u8; 2]) = (0xed, [0xED, 0x01]);
+/// The key types supported by `did:wk` along with their multicodec codes.
+pub const SUPPORTED_KEY_TYPES: [&str; 4] = [
+    "ed25519 (0xed)",
+    "p256 (0x1200)",
+    "p384 (0x1201)",
+    "secp256k1 (0xe7)",
+];
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
 
-/// `p256-pub` varuint public key code and varuint representation.
-const P256_PUB_KEY_CODE: (u16, [u8; 2]) = (0x1200, [0x80, 0x1A]);
+/// Decodes the multicodec code prefixed to a multibase encoded key.
+///
+/// Returns `None` if the key is not valid multibase or the prefix is not a valid varint.
+pub(crate) fn decode_multicodec(encoded: impl AsRef<str>) -> Option<u64> {
+    let (_, multicodec_enc) = Base::decode(encoded).ok()?;
+
+    let mut code = 0;
+    for (i, byte) in multicodec_enc.iter().take(9).enumerate() {
+        code |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some(code);
+        }
+    }
 
-/// `secp256k1-pub` varuint public key code and varuint representation.
-const SECP256K1_PUB_KEY_CODE: (u8, [u8; 2]) = (0xe7, [0xE7, 0x01]);
+    None
+}
 
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
@@ -64,37 +118,41 @@ const SECP256K1_PUB_KEY_CODE: (u8, [u8; 2]) = (0xe7, [0xE7, 0x01]);
 
 impl KeyEncode for Ed25519PubKey<'_> {
     fn encode(&self, base: Base) -> String {
-        let multicodec_enc = {
-            let mut tmp = ED25519_PUB_KEY_CODE.1.to_vec();
-            tmp.extend(self.public_key_bytes());
-            tmp
-        };
+        Multikey::encode(&self.public_key().into(), base)
+    }
 
-        base.encode(&multicodec_enc)
+    fn multikey_bytes(&self) -> Vec<u8> {
+        Multikey::to_bytes(&self.public_key().into())
     }
 }
 
 impl KeyEncode for P256PubKey<'_> {
     fn encode(&self, base: Base) -> String {
-        let multicodec_enc = {
-            let mut tmp = P256_PUB_KEY_CODE.1.to_vec();
-            tmp.extend(self.public_key_bytes());
-            tmp
-        };
+        Multikey::encode(&self.public_key().into(), base)
+    }
+
+    fn multikey_bytes(&self) -> Vec<u8> {
+        Multikey::to_bytes(&self.public_key().into())
+    }
+}
+
+impl KeyEncode for P384PubKey<'_> {
+    fn encode(&self, base: Base) -> String {
+        Multikey::encode(&self.public_key().into(), base)
+    }
 
-        base.encode(&multicodec_enc)
+    fn multikey_bytes(&self) -> Vec<u8> {
+        Multikey::to_bytes(&self.public_key().into())
     }
 }
 
 impl KeyEncode for Secp256k1PubKey<'_> {
     fn encode(&self, base: Base) -> String {
-        let multicodec_enc = {
-            let mut tmp = SECP256K1_PUB_KEY_CODE.1.to_vec();
-            tmp.extend(self.public_key_bytes());
-            tmp
-        };
+        Multikey::encode(&self.public_key().into(), base)
+    }
 
-        base.encode(&multicodec_enc)
+    fn multikey_bytes(&self) -> Vec<u8> {
+        Multikey::to_bytes(&self.public_key().into())
     }
 }
 
@@ -102,14 +160,13 @@ impl KeyDecode for Ed25519PubKey<'_> {
     type Error = DidError;
 
     fn decode(encoded: impl AsRef<str>) -> Result<(Self, Base), Self::Error> {
-        let (base, multicodec_enc) = Base::decode(encoded)?;
-
-        let pk_bytes = match &multicodec_enc[0..2] {
-            [0xED, 0x01] => &multicodec_enc[2..],
-            _ => return Err(DidError::ExpectedKeyType("ed25519".to_string())),
-        };
-
-        Ok((Ed25519PubKey::from_public_key(pk_bytes)?, base))
+        match Multikey::decode(encoded) {
+            Ok((WrappedPubKey::Ed25519(public_key), base)) => Ok((public_key, base)),
+            Ok(_) | Err(KeyError::UnsupportedMulticodec(_)) => {
+                Err(DidError::ExpectedKeyType("ed25519".to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 }
 
@@ -117,14 +174,27 @@ impl KeyDecode for P256PubKey<'_> {
     type Error = DidError;
 
     fn decode(encoded: impl AsRef<str>) -> Result<(Self, Base), Self::Error> {
-        let (base, multicodec_enc) = Base::decode(encoded)?;
+        match Multikey::decode(encoded) {
+            Ok((WrappedPubKey::P256(public_key), base)) => Ok((public_key, base)),
+            Ok(_) | Err(KeyError::UnsupportedMulticodec(_)) => {
+                Err(DidError::ExpectedKeyType("p256".to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
 
-        let pk_bytes = match &multicodec_enc[0..2] {
-            [0x80, 0x1A] => &multicodec_enc[2..],
-            _ => return Err(DidError::ExpectedKeyType("p256".to_string())),
-        };
+impl KeyDecode for P384PubKey<'_> {
+    type Error = DidError;
 
-        Ok((P256PubKey::from_public_key(pk_bytes)?, base))
+    fn decode(encoded: impl AsRef<str>) -> Result<(Self, Base), Self::Error> {
+        match Multikey::decode(encoded) {
+            Ok((WrappedPubKey::P384(public_key), base)) => Ok((public_key, base)),
+            Ok(_) | Err(KeyError::UnsupportedMulticodec(_)) => {
+                Err(DidError::ExpectedKeyType("p384".to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 }
 
@@ -132,14 +202,13 @@ impl KeyDecode for Secp256k1PubKey<'_> {
     type Error = DidError;
 
     fn decode(encoded: impl AsRef<str>) -> Result<(Self, Base), Self::Error> {
-        let (base, multicodec_enc) = Base::decode(encoded)?;
-
-        let pk_bytes = match &multicodec_enc[0..2] {
-            [0xE7, 0x01] => &multicodec_enc[2..],
-            _ => return Err(DidError::ExpectedKeyType("secp256k1".to_string())),
-        };
-
-        Ok((Secp256k1PubKey::from_public_key(pk_bytes)?, base))
+        match Multikey::decode(encoded) {
+            Ok((WrappedPubKey::Secp256k1(public_key), base)) => Ok((public_key, base)),
+            Ok(_) | Err(KeyError::UnsupportedMulticodec(_)) => {
+                Err(DidError::ExpectedKeyType("secp256k1".to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
     }
 }
 
@@ -151,7 +220,9 @@ impl KeyDecode for Secp256k1PubKey<'_> {
 mod tests {
     use anyhow::Ok;
 
-    use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate, P256KeyPair, Secp256k1KeyPair};
+    use zeroutils_key::{
+        Ed25519KeyPair, KeyPairGenerate, P256KeyPair, P384KeyPair, Secp256k1KeyPair,
+    };
 
     use super::*;
 
@@ -183,6 +254,26 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_p384_encode_and_decode() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let pub_key = P384PubKey::from(P384KeyPair::generate(&mut rng)?);
+
+        let encoded = pub_key.encode(Base::Base58Btc);
+        let (decoded, base) = P384PubKey::decode(encoded)?;
+
+        assert_eq!(pub_key, decoded);
+        assert_eq!(base, Base::Base58Btc);
+
+        // The multicodec prefix of `p384-pub` is `0x1201`.
+        assert_eq!(
+            decode_multicodec(pub_key.encode(Base::Base58Btc)),
+            Some(0x1201)
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_secp256k1_encode_and_decode() -> anyhow::Result<()> {
         let mut rng = rand::thread_rng();
@@ -1,5 +1,54 @@
+use crate::{Client, PoolConfig, PooledClient, ReconnectPolicy, Transport};
+
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
 
-pub struct ClientBuilder {}
+/// A builder for [`Client`].
+pub struct ClientBuilder<T> {
+    transport: T,
+    reconnect: Option<ReconnectPolicy>,
+    pool: PoolConfig,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<T> ClientBuilder<T>
+where
+    T: Transport,
+{
+    /// Creates a new builder for a client that connects with `transport`.
+    pub fn new(transport: T) -> Self {
+        Self {
+            transport,
+            reconnect: None,
+            pool: PoolConfig::default(),
+        }
+    }
+
+    /// Sets the policy for reconnecting after the connection drops.
+    ///
+    /// Without a policy, a dropped connection fails the request and the next request connects again.
+    pub fn reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = Some(policy);
+        self
+    }
+
+    /// Sets the configuration of the connection pool used by [`ClientBuilder::build_pooled`].
+    pub fn pool(mut self, config: PoolConfig) -> Self {
+        self.pool = config;
+        self
+    }
+
+    /// Builds the client.
+    pub fn build(self) -> Client<T> {
+        Client::new(self.transport, self.reconnect)
+    }
+
+    /// Builds a client that shares a pool of connections between concurrent requests.
+    pub fn build_pooled(self) -> PooledClient<T> {
+        PooledClient::new(self.transport, self.pool, self.reconnect)
+    }
+}
@@ -1,5 +1,408 @@
+use async_stream::try_stream;
+use futures::Stream;
+use tracing::Instrument;
+
+use crate::{
+    decode_request_id, next_request_id, retry_delay, Chunk, ClientBuilder, Connection, Frame,
+    IpcError, IpcResult, ReconnectPolicy, RequestId, Response, Transport,
+};
+
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
 
-pub struct Client {}
+/// An IPC client.
+///
+/// The client connects lazily on the first request. If a [`ReconnectPolicy`] is set, requests that
+/// fail because the connection dropped are retried on a new connection.
+pub struct Client<T>
+where
+    T: Transport,
+{
+    transport: T,
+    connection: Option<T::Connection>,
+    reconnect: Option<ReconnectPolicy>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<T> Client<T>
+where
+    T: Transport,
+{
+    pub(crate) fn new(transport: T, reconnect: Option<ReconnectPolicy>) -> Self {
+        Self {
+            transport,
+            connection: None,
+            reconnect,
+        }
+    }
+
+    /// Creates a new builder for a client that connects with `transport`.
+    pub fn builder(transport: T) -> ClientBuilder<T> {
+        ClientBuilder::new(transport)
+    }
+
+    /// Sends a request for `action` with the given body and returns the response.
+    ///
+    /// Each request gets a new [`RequestId`] that the response carries. The request runs in an
+    /// `ipc_request` tracing span with the id and the action.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::ConnectionLost`] if the connection drops and cannot be re-established
+    /// within the retries of the reconnect policy.
+    pub async fn request(&mut self, action: &str, body: &[u8]) -> IpcResult<Response> {
+        let id = next_request_id();
+        let span = tracing::debug_span!("ipc_request", id, action);
+        self.request_with_id(id, action, body)
+            .instrument(span)
+            .await
+    }
+
+    async fn request_with_id(
+        &mut self,
+        id: RequestId,
+        action: &str,
+        body: &[u8],
+    ) -> IpcResult<Response> {
+        let mut attempt = 0;
+        loop {
+            let error = match self.try_request(id, action, body).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+
+            tracing::debug!(attempt, %error, "ipc request failed");
+
+            // The connection dropped or is out of step with its responses, so the next attempt or
+            // request starts with a new one.
+            self.connection = None;
+
+            let delay = retry_delay(self.reconnect.as_ref(), attempt, error)?;
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Sends a request for `action` and returns a stream of the chunks the server pushes in response.
+    ///
+    /// The chunks are yielded in the order they are received and the stream ends at the end frame.
+    /// Unlike [`Client::request`], a streamed request is not retried when the connection drops, as
+    /// the server may have already pushed some of the chunks.
+    ///
+    /// # Errors
+    ///
+    /// Yields [`IpcError::UnexpectedEndOfStream`] if the connection closes before the end frame, and
+    /// [`IpcError::RequestIdMismatch`] if the response is for another request.
+    pub fn stream_request<'a>(
+        &'a mut self,
+        action: &'a str,
+        body: &'a [u8],
+    ) -> impl Stream<Item = IpcResult<Chunk>> + 'a {
+        try_stream! {
+            // The connection is only put back once the response is fully read, so it is not reused
+            // in an unknown state after an error.
+            let mut connection = match self.connection.take() {
+                Some(connection) => connection,
+                None => self.transport.connect().await?,
+            };
+
+            let id = next_request_id();
+            tracing::debug!(id, action, "sending ipc stream request");
+            connection.send_stream_request(id, action, body).await?;
+
+            // The first chunk is the id of the request.
+            let mut decoder = connection.frame_decoder();
+            let mut id_received = false;
+            loop {
+                match decoder.next_frame()? {
+                    Some(Frame::Chunk(chunk)) if !id_received => {
+                        check_request_id(id, decode_request_id(&chunk)?)?;
+                        id_received = true;
+                    }
+                    Some(Frame::Chunk(chunk)) => {
+                        yield chunk;
+                    }
+                    Some(Frame::End) => break,
+                    None => {
+                        let bytes = connection.read().await?;
+                        if bytes.is_empty() {
+                            Err::<(), _>(IpcError::UnexpectedEndOfStream)?;
+                        }
+
+                        decoder.extend(&bytes);
+                    }
+                }
+            }
+
+            self.connection = Some(connection);
+        }
+    }
+
+    async fn try_request(
+        &mut self,
+        id: RequestId,
+        action: &str,
+        body: &[u8],
+    ) -> IpcResult<Response> {
+        let connection = match &mut self.connection {
+            Some(connection) => connection,
+            None => self.connection.insert(self.transport.connect().await?),
+        };
+
+        let response = connection.request(id, action, body).await?;
+        check_request_id(id, response.id())?;
+        Ok(response)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Checks that a response carries the id of the request it was received for.
+pub(crate) fn check_request_id(id: RequestId, response_id: RequestId) -> IpcResult<()> {
+    if id != response_id {
+        return Err(IpcError::RequestIdMismatch(id, response_id));
+    }
+
+    Ok(())
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{Error, ErrorKind},
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures::{StreamExt, TryStreamExt};
+
+    use super::*;
+
+    mod fixtures {
+        use super::*;
+
+        //--------------------------------------------------------------------------------------------------
+        // Types
+        //--------------------------------------------------------------------------------------------------
+
+        /// A transport whose connections drop on the first `drops` requests.
+        #[derive(Default)]
+        pub struct MockTransport {
+            pub connects: Arc<AtomicU32>,
+            pub drops: Arc<AtomicU32>,
+        }
+
+        pub struct MockConnection {
+            drops: Arc<AtomicU32>,
+            pending: Vec<u8>,
+        }
+
+        //--------------------------------------------------------------------------------------------------
+        // Trait Implementations
+        //--------------------------------------------------------------------------------------------------
+
+        impl Transport for MockTransport {
+            type Connection = MockConnection;
+
+            async fn connect(&self) -> IpcResult<MockConnection> {
+                self.connects.fetch_add(1, Ordering::SeqCst);
+                Ok(MockConnection {
+                    drops: self.drops.clone(),
+                    pending: Vec::new(),
+                })
+            }
+        }
+
+        impl Connection for MockConnection {
+            /// Echoes the action and body. The `misroute` action responds with another request id.
+            async fn request(
+                &mut self,
+                id: RequestId,
+                action: &str,
+                body: &[u8],
+            ) -> IpcResult<Response> {
+                let dropped = self
+                    .drops
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                    .is_ok();
+
+                if dropped {
+                    return Err(Error::new(ErrorKind::ConnectionReset, "connection dropped").into());
+                }
+
+                let id = if action == "misroute" { id + 1 } else { id };
+                Ok(Response::new(id, [action.as_bytes(), body].concat()))
+            }
+
+            /// Streams `body` back one byte per chunk. The `truncate` action leaves out the end frame.
+            async fn send_stream_request(
+                &mut self,
+                id: RequestId,
+                action: &str,
+                body: &[u8],
+            ) -> IpcResult<()> {
+                self.pending = Frame::Chunk(id.to_be_bytes().to_vec()).encode()?;
+                for byte in body {
+                    self.pending.extend(Frame::Chunk(vec![*byte]).encode()?);
+                }
+
+                if action != "truncate" {
+                    self.pending.extend(Frame::End.encode()?);
+                }
+
+                Ok(())
+            }
+
+            /// Returns the pending bytes three at a time, so frames arrive split.
+            async fn read(&mut self) -> IpcResult<Vec<u8>> {
+                let len = self.pending.len().min(3);
+                Ok(self.pending.drain(..len).collect())
+            }
+        }
+    }
+
+    fn policy(max_retries: u32) -> ReconnectPolicy {
+        ReconnectPolicy {
+            max_retries,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_reconnects_after_drop() -> anyhow::Result<()> {
+        let transport = fixtures::MockTransport::default();
+        transport.drops.store(1, Ordering::SeqCst);
+        let connects = transport.connects.clone();
+
+        let mut client = Client::builder(transport).reconnect(policy(3)).build();
+
+        assert_eq!(
+            client.request("echo", b"-hello").await?.body(),
+            b"echo-hello"
+        );
+        assert_eq!(connects.load(Ordering::SeqCst), 2);
+
+        // The new connection is reused.
+        assert_eq!(
+            client.request("echo", b"-again").await?.body(),
+            b"echo-again"
+        );
+        assert_eq!(connects.load(Ordering::SeqCst), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_connection_lost() -> anyhow::Result<()> {
+        let transport = fixtures::MockTransport::default();
+        transport.drops.store(10, Ordering::SeqCst);
+        let connects = transport.connects.clone();
+
+        let mut client = Client::builder(transport).reconnect(policy(2)).build();
+
+        assert!(matches!(
+            client.request("echo", b"").await,
+            Err(IpcError::ConnectionLost(2))
+        ));
+        assert_eq!(connects.load(Ordering::SeqCst), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_stream_request() -> anyhow::Result<()> {
+        let transport = fixtures::MockTransport::default();
+        let connects = transport.connects.clone();
+
+        let mut client = Client::builder(transport).build();
+
+        let chunks = client
+            .stream_request("echo", b"hello")
+            .try_collect::<Vec<_>>()
+            .await?;
+        assert_eq!(chunks, [b"h", b"e", b"l", b"l", b"o"]);
+
+        // The connection is reused after the stream ends.
+        assert_eq!(client.request("echo", b"").await?.body(), b"echo");
+        assert_eq!(connects.load(Ordering::SeqCst), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_stream_request_without_end_frame() -> anyhow::Result<()> {
+        let mut client = Client::builder(fixtures::MockTransport::default()).build();
+
+        let results = client
+            .stream_request("truncate", b"hi")
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().ok(), Some(&b"h".to_vec()));
+        assert_eq!(results[1].as_ref().ok(), Some(&b"i".to_vec()));
+        assert!(matches!(results[2], Err(IpcError::UnexpectedEndOfStream)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_without_reconnect() -> anyhow::Result<()> {
+        let transport = fixtures::MockTransport::default();
+        transport.drops.store(1, Ordering::SeqCst);
+
+        let mut client = Client::builder(transport).build();
+
+        assert!(matches!(
+            client.request("echo", b"").await,
+            Err(IpcError::IoError(_))
+        ));
+        assert_eq!(client.request("echo", b"").await?.body(), b"echo");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_client_response_id_matches_request() -> anyhow::Result<()> {
+        let mut client = Client::builder(fixtures::MockTransport::default()).build();
+
+        let first = client.request("echo", b"").await?;
+        let second = client.request("echo", b"").await?;
+        assert_ne!(first.id(), second.id());
+
+        // The connection echoes the request id it was sent.
+        let id = next_request_id();
+        let response = client
+            .connection
+            .as_mut()
+            .unwrap()
+            .request(id, "echo", b"")
+            .await?;
+        assert_eq!(response.id(), id);
+
+        // A response for another request is rejected.
+        assert!(matches!(
+            client.request("misroute", b"").await,
+            Err(IpcError::RequestIdMismatch(id, response_id)) if response_id == id + 1
+        ));
+
+        // The connection that answered out of step is not reused.
+        assert!(client.connection.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,226 @@
+use crate::{IpcError, IpcResult};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The tag of a frame that carries a chunk of a streamed response.
+pub const CHUNK_FRAME_TAG: u8 = 0x00;
+
+/// The tag of the frame that marks the end of a streamed response.
+pub const END_FRAME_TAG: u8 = 0x01;
+
+/// The size of a frame header: a one byte tag followed by a big-endian `u32` payload length.
+pub const FRAME_HEADER_SIZE: usize = 5;
+
+/// The default maximum payload length a [`FrameDecoder`] accepts.
+pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A chunk of a streamed response.
+pub type Chunk = Vec<u8>;
+
+/// A frame of a streamed response.
+///
+/// A streamed response is a sequence of [`Frame::Chunk`] frames terminated by a single
+/// [`Frame::End`] frame. Each frame is encoded as:
+///
+/// ```txt
+/// ┌─────────┬──────────────────┬─────────────────┐
+/// │ tag: u8 │ length: u32 (BE) │ payload: length │
+/// └─────────┴──────────────────┴─────────────────┘
+/// ```
+///
+/// The end frame has an empty payload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frame {
+    /// A chunk of the response.
+    Chunk(Chunk),
+
+    /// The end of the response.
+    End,
+}
+
+/// Decodes frames from bytes that may arrive split at arbitrary positions.
+///
+/// Frames with a payload longer than the maximum frame length are rejected as soon as their header
+/// arrives, so a peer cannot make the decoder buffer an arbitrary amount of bytes.
+#[derive(Debug)]
+pub struct FrameDecoder {
+    buffer: Vec<u8>,
+    max_frame_len: usize,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl Frame {
+    /// Encodes the frame.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::FrameTooLarge`] if the payload length does not fit in a `u32`.
+    pub fn encode(&self) -> IpcResult<Vec<u8>> {
+        let (tag, payload) = match self {
+            Frame::Chunk(chunk) => (CHUNK_FRAME_TAG, &chunk[..]),
+            Frame::End => (END_FRAME_TAG, &[][..]),
+        };
+
+        let len = u32::try_from(payload.len())
+            .map_err(|_| IpcError::FrameTooLarge(payload.len(), u32::MAX as usize))?;
+
+        let mut bytes = Vec::with_capacity(FRAME_HEADER_SIZE + payload.len());
+        bytes.push(tag);
+        bytes.extend(len.to_be_bytes());
+        bytes.extend(payload);
+        Ok(bytes)
+    }
+}
+
+impl FrameDecoder {
+    /// Creates a new frame decoder that accepts payloads of up to [`DEFAULT_MAX_FRAME_LEN`] bytes.
+    pub fn new() -> Self {
+        Self::with_max_frame_len(DEFAULT_MAX_FRAME_LEN)
+    }
+
+    /// Creates a new frame decoder that accepts payloads of up to `max_frame_len` bytes.
+    pub fn with_max_frame_len(max_frame_len: usize) -> Self {
+        Self {
+            buffer: Vec::new(),
+            max_frame_len,
+        }
+    }
+
+    /// Appends received bytes to the decoder.
+    pub fn extend(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Decodes the next complete frame, or returns `None` if more bytes are needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::InvalidFrame`] if the frame has an unknown tag or the end frame has a
+    /// payload, and [`IpcError::FrameTooLarge`] if the payload is longer than the maximum frame
+    /// length.
+    pub fn next_frame(&mut self) -> IpcResult<Option<Frame>> {
+        let Some(header) = self.buffer.get(..FRAME_HEADER_SIZE) else {
+            return Ok(None);
+        };
+
+        let tag = header[0];
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        if len > self.max_frame_len {
+            return Err(IpcError::FrameTooLarge(len, self.max_frame_len));
+        }
+
+        if self.buffer.len() < FRAME_HEADER_SIZE + len {
+            return Ok(None);
+        }
+
+        let frame = match (tag, len) {
+            (CHUNK_FRAME_TAG, _) => {
+                Frame::Chunk(self.buffer[FRAME_HEADER_SIZE..FRAME_HEADER_SIZE + len].to_vec())
+            }
+            (END_FRAME_TAG, 0) => Frame::End,
+            _ => {
+                return Err(IpcError::InvalidFrame(format!(
+                    "tag {tag:#04x} with a {len} byte payload"
+                )))
+            }
+        };
+
+        self.buffer.drain(..FRAME_HEADER_SIZE + len);
+        Ok(Some(frame))
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_decoder_split_input() -> anyhow::Result<()> {
+        let frames = [
+            Frame::Chunk(b"hello".to_vec()),
+            Frame::Chunk(vec![]),
+            Frame::Chunk(b"world".to_vec()),
+            Frame::End,
+        ];
+
+        let encoded = frames
+            .iter()
+            .map(Frame::encode)
+            .collect::<IpcResult<Vec<_>>>()?
+            .concat();
+
+        // Feed the bytes one at a time to exercise partial headers and payloads.
+        let mut decoder = FrameDecoder::new();
+        let mut decoded = Vec::new();
+        for byte in encoded {
+            decoder.extend(&[byte]);
+            while let Some(frame) = decoder.next_frame()? {
+                decoded.push(frame);
+            }
+        }
+
+        assert_eq!(decoded, frames);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_frame_decoder_invalid_frame() {
+        let mut decoder = FrameDecoder::new();
+        decoder.extend(&[0x07, 0, 0, 0, 0]);
+        assert!(matches!(
+            decoder.next_frame(),
+            Err(IpcError::InvalidFrame(_))
+        ));
+
+        let mut decoder = FrameDecoder::new();
+        decoder.extend(&[END_FRAME_TAG, 0, 0, 0, 1, 0xFF]);
+        assert!(decoder.next_frame().is_err());
+    }
+
+    #[test]
+    fn test_frame_decoder_max_frame_len() -> anyhow::Result<()> {
+        let mut decoder = FrameDecoder::with_max_frame_len(4);
+        decoder.extend(&Frame::Chunk(b"four".to_vec()).encode()?);
+        assert_eq!(decoder.next_frame()?, Some(Frame::Chunk(b"four".to_vec())));
+
+        // Rejected from the header alone, before the payload arrives.
+        decoder.extend(&[CHUNK_FRAME_TAG, 0, 0, 0, 5]);
+        assert!(matches!(
+            decoder.next_frame(),
+            Err(IpcError::FrameTooLarge(5, 4))
+        ));
+
+        let mut decoder = FrameDecoder::new();
+        decoder.extend(&[CHUNK_FRAME_TAG, 0xFF, 0xFF, 0xFF, 0xFF]);
+        assert!(matches!(
+            decoder.next_frame(),
+            Err(IpcError::FrameTooLarge(len, DEFAULT_MAX_FRAME_LEN)) if len == u32::MAX as usize
+        ));
+
+        Ok(())
+    }
+}
@@ -1,6 +1,13 @@
 mod builder;
 mod client;
+mod frame;
+mod pool;
+mod reconnect;
 mod response;
+mod stream;
+#[cfg(feature = "tls")]
+mod tls;
+mod transport;
 
 //--------------------------------------------------------------------------------------------------
 // Exports
@@ -8,4 +15,11 @@ mod response;
 
 pub use builder::*;
 pub use client::*;
+pub use frame::*;
+pub use pool::*;
+pub use reconnect::*;
 pub use response::*;
+pub use stream::*;
+#[cfg(feature = "tls")]
+pub use tls::*;
+pub use transport::*;
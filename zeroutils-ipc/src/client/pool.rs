@@ -0,0 +1,328 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Semaphore;
+
+use tracing::Instrument;
+
+use crate::{
+    check_request_id, next_request_id, retry_delay, Connection, IpcError, IpcResult,
+    ReconnectPolicy, RequestId, Response, Transport,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The default maximum number of connections in a pool.
+pub const DEFAULT_POOL_MAX_SIZE: usize = 8;
+
+/// The default time an unused connection is kept in a pool.
+pub const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// Configures the connection pool of a [`PooledClient`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// The maximum number of connections open at the same time.
+    pub max_size: usize,
+
+    /// How long an unused connection is kept before it is closed.
+    pub idle_timeout: Duration,
+}
+
+/// An IPC client that shares a pool of connections between concurrent requests.
+///
+/// Each request borrows an idle connection or opens a new one, and returns it to the pool when it
+/// completes. At most [`PoolConfig::max_size`] connections are open at the same time, so further
+/// requests wait for a connection to be returned.
+pub struct PooledClient<T>
+where
+    T: Transport,
+{
+    transport: T,
+    config: PoolConfig,
+    reconnect: Option<ReconnectPolicy>,
+    idle: Mutex<Vec<(T::Connection, Instant)>>,
+    permits: Semaphore,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<T> PooledClient<T>
+where
+    T: Transport,
+{
+    pub(crate) fn new(
+        transport: T,
+        config: PoolConfig,
+        reconnect: Option<ReconnectPolicy>,
+    ) -> Self {
+        // A pool without connections could never serve a request.
+        let config = PoolConfig {
+            max_size: config.max_size.max(1),
+            ..config
+        };
+
+        Self {
+            transport,
+            permits: Semaphore::new(config.max_size),
+            config,
+            reconnect,
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sends a request for `action` with the given body and returns the response.
+    ///
+    /// See [`Client::request`][crate::Client::request] for how requests are identified and traced.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`IpcError::ConnectionLost`] if the connection drops and cannot be re-established
+    /// within the retries of the reconnect policy.
+    pub async fn request(&self, action: &str, body: &[u8]) -> IpcResult<Response> {
+        let id = next_request_id();
+        let span = tracing::debug_span!("ipc_request", id, action);
+        self.request_with_id(id, action, body)
+            .instrument(span)
+            .await
+    }
+
+    /// Returns the number of idle connections in the pool.
+    pub fn idle_connections(&self) -> usize {
+        self.idle_guard().len()
+    }
+
+    async fn request_with_id(
+        &self,
+        id: RequestId,
+        action: &str,
+        body: &[u8],
+    ) -> IpcResult<Response> {
+        let _permit = self.permits.acquire().await.map_err(IpcError::custom)?;
+
+        let mut attempt = 0;
+        loop {
+            let error = match self.try_request(id, action, body).await {
+                Ok(response) => return Ok(response),
+                Err(error) => error,
+            };
+
+            tracing::debug!(attempt, %error, "ipc request failed");
+
+            let delay = retry_delay(self.reconnect.as_ref(), attempt, error)?;
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn try_request(&self, id: RequestId, action: &str, body: &[u8]) -> IpcResult<Response> {
+        let mut connection = match self.checkout() {
+            Some(connection) => connection,
+            None => self.transport.connect().await?,
+        };
+
+        let response = connection.request(id, action, body).await?;
+        check_request_id(id, response.id())?;
+
+        // Only a connection that answered with the response to this request is returned to the pool.
+        // One that failed or is out of step with its responses would fail the next request too.
+        self.idle_guard().push((connection, Instant::now()));
+        Ok(response)
+    }
+
+    /// Takes the most recently used idle connection and closes the ones that timed out.
+    fn checkout(&self) -> Option<T::Connection> {
+        let mut idle = self.idle_guard();
+        idle.retain(|(_, since)| since.elapsed() < self.config.idle_timeout);
+        idle.pop().map(|(connection, _)| connection)
+    }
+
+    fn idle_guard(&self) -> std::sync::MutexGuard<'_, Vec<(T::Connection, Instant)>> {
+        // The idle list is always left in a valid state, so a poisoned lock can be recovered.
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: DEFAULT_POOL_MAX_SIZE,
+            idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use futures::future;
+
+    use crate::Client;
+
+    use super::*;
+
+    mod fixtures {
+        use super::*;
+
+        //--------------------------------------------------------------------------------------------------
+        // Types
+        //--------------------------------------------------------------------------------------------------
+
+        /// A transport that counts its connections and how many are in use at the same time.
+        ///
+        /// The next `mismatched` responses have the id of another request.
+        #[derive(Default, Clone)]
+        pub struct CountingTransport {
+            pub connects: Rc<Cell<usize>>,
+            pub in_use: Rc<Cell<usize>>,
+            pub max_in_use: Rc<Cell<usize>>,
+            pub mismatched: Rc<Cell<usize>>,
+        }
+
+        pub struct CountingConnection(CountingTransport);
+
+        //--------------------------------------------------------------------------------------------------
+        // Trait Implementations
+        //--------------------------------------------------------------------------------------------------
+
+        impl Transport for CountingTransport {
+            type Connection = CountingConnection;
+
+            async fn connect(&self) -> IpcResult<CountingConnection> {
+                self.connects.set(self.connects.get() + 1);
+                Ok(CountingConnection(self.clone()))
+            }
+        }
+
+        impl Connection for CountingConnection {
+            async fn request(
+                &mut self,
+                id: RequestId,
+                _: &str,
+                body: &[u8],
+            ) -> IpcResult<Response> {
+                let transport = &self.0;
+                transport.in_use.set(transport.in_use.get() + 1);
+                transport
+                    .max_in_use
+                    .set(transport.max_in_use.get().max(transport.in_use.get()));
+
+                // Let the other requests run while this one is in flight.
+                tokio::task::yield_now().await;
+
+                transport.in_use.set(transport.in_use.get() - 1);
+
+                if transport.mismatched.get() > 0 {
+                    transport.mismatched.set(transport.mismatched.get() - 1);
+                    return Ok(Response::new(id.wrapping_add(1), body.to_vec()));
+                }
+
+                Ok(Response::new(id, body.to_vec()))
+            }
+
+            async fn send_stream_request(
+                &mut self,
+                _: RequestId,
+                _: &str,
+                _: &[u8],
+            ) -> IpcResult<()> {
+                Ok(())
+            }
+
+            async fn read(&mut self) -> IpcResult<Vec<u8>> {
+                Ok(Vec::new())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pooled_client_limits_connections() -> anyhow::Result<()> {
+        let transport = fixtures::CountingTransport::default();
+        let client = Client::builder(transport.clone())
+            .pool(PoolConfig {
+                max_size: 3,
+                ..Default::default()
+            })
+            .build_pooled();
+
+        let bodies = (0..20u8).map(|i| vec![i]).collect::<Vec<_>>();
+        let responses =
+            future::join_all(bodies.iter().map(|body| client.request("echo", body))).await;
+
+        for (response, body) in responses.into_iter().zip(&bodies) {
+            assert_eq!(response?.body(), body);
+        }
+
+        assert_eq!(transport.connects.get(), 3);
+        assert_eq!(transport.max_in_use.get(), 3);
+        assert_eq!(client.idle_connections(), 3);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_client_idle_timeout() -> anyhow::Result<()> {
+        let transport = fixtures::CountingTransport::default();
+        let client = Client::builder(transport.clone()).build_pooled();
+
+        client.request("echo", b"a").await?;
+        client.request("echo", b"b").await?;
+        assert_eq!(transport.connects.get(), 1);
+
+        // Connections that are idle for too long are not reused.
+        let transport = fixtures::CountingTransport::default();
+        let client = Client::builder(transport.clone())
+            .pool(PoolConfig {
+                idle_timeout: Duration::ZERO,
+                ..Default::default()
+            })
+            .build_pooled();
+
+        client.request("echo", b"a").await?;
+        client.request("echo", b"b").await?;
+        assert_eq!(transport.connects.get(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_client_drops_connection_on_request_id_mismatch() -> anyhow::Result<()> {
+        let transport = fixtures::CountingTransport::default();
+        let client = Client::builder(transport.clone()).build_pooled();
+
+        client.request("echo", b"a").await?;
+        assert_eq!(client.idle_connections(), 1);
+
+        transport.mismatched.set(1);
+        assert!(matches!(
+            client.request("echo", b"b").await,
+            Err(IpcError::RequestIdMismatch(..))
+        ));
+        assert_eq!(client.idle_connections(), 0);
+
+        // The next request opens a new connection.
+        assert_eq!(client.request("echo", b"c").await?.body(), b"c");
+        assert_eq!(transport.connects.get(), 2);
+        assert_eq!(client.idle_connections(), 1);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use crate::{IpcError, IpcResult};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The default maximum number of reconnection attempts.
+pub const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// The default delay before the first reconnection attempt.
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The default maximum delay between reconnection attempts.
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// Determines how a [`Client`][crate::Client] reconnects after its connection drops.
+///
+/// The delay before each attempt starts at `initial_backoff` and doubles after every attempt up to
+/// `max_backoff`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// The maximum number of reconnection attempts for a single request.
+    pub max_retries: u32,
+
+    /// The delay before the first reconnection attempt.
+    pub initial_backoff: Duration,
+
+    /// The maximum delay between reconnection attempts.
+    pub max_backoff: Duration,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl ReconnectPolicy {
+    /// Returns the delay before the given reconnection attempt, starting from `0`.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Returns the delay before retrying a request that failed with `error` on the given attempt.
+///
+/// Only dropped connections, reported as [`IpcError::IoError`], are retried. Other errors, errors
+/// without a policy and errors after the last retry are returned as `Err`.
+pub(crate) fn retry_delay(
+    policy: Option<&ReconnectPolicy>,
+    attempt: u32,
+    error: IpcError,
+) -> IpcResult<Duration> {
+    match (policy, error) {
+        (Some(policy), IpcError::IoError(_)) if attempt >= policy.max_retries => {
+            Err(IpcError::ConnectionLost(attempt))
+        }
+        (Some(policy), IpcError::IoError(_)) => Ok(policy.backoff(attempt)),
+        (_, error) => Err(error),
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reconnect_policy_backoff() {
+        let policy = ReconnectPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        };
+
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+    }
+}
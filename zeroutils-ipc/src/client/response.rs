@@ -1,8 +1,67 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{IpcError, IpcResult};
+
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
 
-pub struct Response {}
+/// The identifier of a request, used to correlate it with its response.
+///
+/// Identifiers are unique within the process.
+pub type RequestId = u64;
+
+/// The response to a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    id: RequestId,
+    body: Vec<u8>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl Response {
+    /// Creates a new response to the request with the given id.
+    pub fn new(id: RequestId, body: Vec<u8>) -> Self {
+        Self { id, body }
+    }
+
+    /// Returns the id of the request this is a response to.
+    pub fn id(&self) -> RequestId {
+        self.id
+    }
+
+    /// Returns the body of the response.
+    pub fn body(&self) -> &[u8] {
+        &self.body
+    }
+
+    /// Returns the body of the response.
+    pub fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Returns a new request id.
+pub(crate) fn next_request_id() -> RequestId {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Decodes a request id sent as the first chunk of a response.
+pub(crate) fn decode_request_id(chunk: &[u8]) -> IpcResult<RequestId> {
+    let bytes = chunk
+        .try_into()
+        .map_err(|_| IpcError::InvalidFrame(format!("request id of {} bytes", chunk.len())))?;
+
+    Ok(RequestId::from_be_bytes(bytes))
+}
 
 // let client: Client = ipc::Client::builder()
 //     .path("some/path")
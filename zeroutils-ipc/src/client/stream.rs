@@ -0,0 +1,167 @@
+use std::{
+    io::{self, ErrorKind},
+    net::SocketAddr,
+};
+
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use crate::{
+    decode_request_id, Connection, Frame, FrameDecoder, IpcError, IpcResult, RequestId, Response,
+    Transport, DEFAULT_MAX_FRAME_LEN,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The size of the buffer used to read from a stream.
+const READ_BUFFER_SIZE: usize = 8 * 1024;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A [`Connection`] over a byte stream, e.g. a TCP or TLS stream.
+///
+/// A request is sent as three [`Frame::Chunk`] frames: the big-endian request id, the action and the
+/// body. The server responds with the request id chunk followed by the body as chunk frames,
+/// terminated by a [`Frame::End`] frame.
+pub struct StreamConnection<S> {
+    stream: S,
+    max_frame_len: usize,
+}
+
+/// A [`Transport`] that connects over plain TCP.
+#[derive(Debug, Clone)]
+pub struct TcpTransport {
+    addr: SocketAddr,
+    max_frame_len: usize,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<S> StreamConnection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    /// Creates a new connection over `stream`.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    /// Sets the maximum payload length of the frames the connection accepts.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    /// Returns the underlying stream.
+    pub fn into_inner(self) -> S {
+        self.stream
+    }
+}
+
+impl TcpTransport {
+    /// Creates a new transport that connects to `addr`.
+    pub fn new(addr: SocketAddr) -> Self {
+        Self {
+            addr,
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        }
+    }
+
+    /// Sets the maximum payload length of the frames its connections accept.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl<S> Connection for StreamConnection<S>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    async fn request(&mut self, id: RequestId, action: &str, body: &[u8]) -> IpcResult<Response> {
+        self.send_stream_request(id, action, body).await?;
+
+        let mut decoder = self.frame_decoder();
+        let mut response_id = None;
+        let mut response = Vec::new();
+        loop {
+            match decoder.next_frame()? {
+                Some(Frame::Chunk(chunk)) if response_id.is_none() => {
+                    response_id = Some(decode_request_id(&chunk)?);
+                }
+                Some(Frame::Chunk(chunk)) => response.extend(chunk),
+                Some(Frame::End) => {
+                    let id = response_id.ok_or(IpcError::InvalidFrame(
+                        "response without request id".to_string(),
+                    ))?;
+
+                    return Ok(Response::new(id, response));
+                }
+                None => {
+                    let bytes = self.read().await?;
+                    if bytes.is_empty() {
+                        // Reported as an io error so the client treats it as a dropped connection.
+                        return Err(io::Error::from(ErrorKind::UnexpectedEof).into());
+                    }
+
+                    decoder.extend(&bytes);
+                }
+            }
+        }
+    }
+
+    async fn send_stream_request(
+        &mut self,
+        id: RequestId,
+        action: &str,
+        body: &[u8],
+    ) -> IpcResult<()> {
+        self.stream
+            .write_all(&Frame::Chunk(id.to_be_bytes().to_vec()).encode()?)
+            .await?;
+        self.stream
+            .write_all(&Frame::Chunk(action.as_bytes().to_vec()).encode()?)
+            .await?;
+        self.stream
+            .write_all(&Frame::Chunk(body.to_vec()).encode()?)
+            .await?;
+        self.stream.flush().await?;
+
+        Ok(())
+    }
+
+    async fn read(&mut self) -> IpcResult<Vec<u8>> {
+        let mut buf = vec![0; READ_BUFFER_SIZE];
+        let n = self.stream.read(&mut buf).await?;
+        buf.truncate(n);
+        Ok(buf)
+    }
+
+    fn frame_decoder(&self) -> FrameDecoder {
+        FrameDecoder::with_max_frame_len(self.max_frame_len)
+    }
+}
+
+impl Transport for TcpTransport {
+    type Connection = StreamConnection<TcpStream>;
+
+    async fn connect(&self) -> IpcResult<Self::Connection> {
+        let stream = TcpStream::connect(self.addr).await?;
+        Ok(StreamConnection::new(stream).with_max_frame_len(self.max_frame_len))
+    }
+}
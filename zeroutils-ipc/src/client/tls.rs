@@ -0,0 +1,382 @@
+use std::{io::BufReader, net::SocketAddr, sync::Arc};
+
+use tokio::net::TcpStream;
+use tokio_rustls::{
+    client::TlsStream,
+    rustls::{
+        crypto::ring,
+        pki_types::{CertificateDer, PrivateKeyDer, ServerName},
+        ClientConfig, Error as RustlsError, RootCertStore,
+    },
+    TlsConnector,
+};
+
+use crate::{IpcError, IpcResult, StreamConnection, Transport, DEFAULT_MAX_FRAME_LEN};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// The TLS settings of a [`TlsTransport`].
+///
+/// The server certificate is verified against the CA bundle and the server name.
+pub struct TlsConfig {
+    server_name: ServerName<'static>,
+    roots: RootCertStore,
+    client_auth: Option<(Vec<CertificateDer<'static>>, PrivateKeyDer<'static>)>,
+}
+
+/// A [`Transport`] that connects over TCP secured with TLS.
+#[derive(Clone)]
+pub struct TlsTransport {
+    addr: SocketAddr,
+    server_name: ServerName<'static>,
+    connector: TlsConnector,
+    max_frame_len: usize,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl TlsConfig {
+    /// Creates a new TLS config that expects the server certificate to be valid for `server_name`.
+    pub fn new(server_name: impl Into<String>) -> IpcResult<Self> {
+        let server_name = server_name.into();
+        let server_name = ServerName::try_from(server_name.clone())
+            .map_err(|_| IpcError::InvalidServerName(server_name))?;
+
+        Ok(Self {
+            server_name,
+            roots: RootCertStore::empty(),
+            client_auth: None,
+        })
+    }
+
+    /// Adds the certificates of a PEM encoded CA bundle to the trusted roots.
+    pub fn ca_pem(mut self, pem: &[u8]) -> IpcResult<Self> {
+        for cert in rustls_pemfile::certs(&mut BufReader::new(pem)) {
+            self.roots.add(cert?)?;
+        }
+
+        Ok(self)
+    }
+
+    /// Sets the PEM encoded certificate chain and private key to authenticate the client with.
+    pub fn client_cert_pem(mut self, cert_chain: &[u8], key: &[u8]) -> IpcResult<Self> {
+        let cert_chain = rustls_pemfile::certs(&mut BufReader::new(cert_chain))
+            .collect::<Result<Vec<_>, _>>()?;
+        let key = rustls_pemfile::private_key(&mut BufReader::new(key))?
+            .ok_or(IpcError::MissingPrivateKey)?;
+
+        self.client_auth = Some((cert_chain, key));
+        Ok(self)
+    }
+}
+
+impl TlsTransport {
+    /// Creates a new transport that connects to `addr` with the given TLS config.
+    pub fn new(addr: SocketAddr, config: TlsConfig) -> IpcResult<Self> {
+        let builder = ClientConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_root_certificates(config.roots);
+        let client_config = match config.client_auth {
+            Some((cert_chain, key)) => builder.with_client_auth_cert(cert_chain, key)?,
+            None => builder.with_no_client_auth(),
+        };
+
+        Ok(Self {
+            addr,
+            server_name: config.server_name,
+            connector: TlsConnector::from(Arc::new(client_config)),
+            max_frame_len: DEFAULT_MAX_FRAME_LEN,
+        })
+    }
+
+    /// Sets the maximum payload length of the frames its connections accept.
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl Transport for TlsTransport {
+    type Connection = StreamConnection<TlsStream<TcpStream>>;
+
+    async fn connect(&self) -> IpcResult<Self::Connection> {
+        let stream = TcpStream::connect(self.addr).await?;
+        let stream = self
+            .connector
+            .connect(self.server_name.clone(), stream)
+            .await
+            .map_err(handshake_error)?;
+
+        Ok(StreamConnection::new(stream).with_max_frame_len(self.max_frame_len))
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Maps an error of the TLS handshake to [`IpcError::TlsError`] if it was caused by TLS, like an
+/// untrusted certificate, so that it is not retried like a plain IO error.
+fn handshake_error(error: std::io::Error) -> IpcError {
+    match error
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<RustlsError>())
+    {
+        Some(tls_error) => IpcError::TlsError(tls_error.clone()),
+        None => IpcError::IoError(error),
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{Client, ReconnectPolicy};
+
+    use super::*;
+
+    mod fixtures {
+        use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa, KeyPair};
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+        use tokio_rustls::{
+            rustls::{server::WebPkiClientVerifier, ServerConfig},
+            TlsAcceptor,
+        };
+
+        use crate::{Frame, FrameDecoder};
+
+        use super::*;
+
+        //--------------------------------------------------------------------------------------------------
+        // Functions
+        //--------------------------------------------------------------------------------------------------
+
+        /// Generates a self-signed CA certificate and its key.
+        pub fn ca() -> anyhow::Result<(Certificate, KeyPair)> {
+            let ca_key = KeyPair::generate()?;
+            let mut ca_params = CertificateParams::new(Vec::<String>::new())?;
+            ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            let ca_cert = ca_params.self_signed(&ca_key)?;
+
+            Ok((ca_cert, ca_key))
+        }
+
+        /// Generates a certificate for `name` signed by the CA, and its key.
+        pub fn leaf(
+            name: &str,
+            ca_cert: &Certificate,
+            ca_key: &KeyPair,
+        ) -> anyhow::Result<(Certificate, KeyPair)> {
+            let key = KeyPair::generate()?;
+            let cert =
+                CertificateParams::new(vec![name.to_string()])?.signed_by(&key, ca_cert, ca_key)?;
+
+            Ok((cert, key))
+        }
+
+        /// Generates a CA and a server certificate for `localhost` signed by it.
+        ///
+        /// Returns the PEM encoded CA certificate and the server config.
+        pub fn ca_and_server_config() -> anyhow::Result<(String, ServerConfig)> {
+            let (ca_cert, ca_key) = ca()?;
+            let (server_cert, server_key) = leaf("localhost", &ca_cert, &ca_key)?;
+
+            let server_config =
+                ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+                    .with_safe_default_protocol_versions()?
+                    .with_no_client_auth()
+                    .with_single_cert(
+                        vec![server_cert.der().clone()],
+                        PrivateKeyDer::Pkcs8(server_key.serialize_der().into()),
+                    )?;
+
+            Ok((ca_cert.pem(), server_config))
+        }
+
+        /// Generates a CA, and a server certificate for `localhost` and a client certificate both
+        /// signed by it.
+        ///
+        /// Returns the PEM encoded CA certificate, the PEM encoded client certificate and key, and a
+        /// server config that requires clients to authenticate with a certificate signed by the CA.
+        pub fn ca_client_and_server_config(
+        ) -> anyhow::Result<(String, String, String, ServerConfig)> {
+            let (ca_cert, ca_key) = ca()?;
+            let (server_cert, server_key) = leaf("localhost", &ca_cert, &ca_key)?;
+            let (client_cert, client_key) = leaf("client", &ca_cert, &ca_key)?;
+
+            let provider = Arc::new(ring::default_provider());
+            let mut client_roots = RootCertStore::empty();
+            client_roots.add(ca_cert.der().clone())?;
+            let verifier = WebPkiClientVerifier::builder_with_provider(
+                Arc::new(client_roots),
+                provider.clone(),
+            )
+            .build()?;
+
+            let server_config = ServerConfig::builder_with_provider(provider)
+                .with_safe_default_protocol_versions()?
+                .with_client_cert_verifier(verifier)
+                .with_single_cert(
+                    vec![server_cert.der().clone()],
+                    PrivateKeyDer::Pkcs8(server_key.serialize_der().into()),
+                )?;
+
+            Ok((
+                ca_cert.pem(),
+                client_cert.pem(),
+                client_key.serialize_pem(),
+                server_config,
+            ))
+        }
+
+        /// Spawns a TLS server that responds to every request with its body.
+        pub async fn spawn_echo_server(config: ServerConfig) -> anyhow::Result<SocketAddr> {
+            let listener = TcpListener::bind("127.0.0.1:0").await?;
+            let addr = listener.local_addr()?;
+            let acceptor = TlsAcceptor::from(Arc::new(config));
+
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let acceptor = acceptor.clone();
+                    tokio::spawn(async move {
+                        let Ok(mut stream) = acceptor.accept(stream).await else {
+                            return;
+                        };
+
+                        let mut decoder = FrameDecoder::new();
+                        let mut chunks = Vec::new();
+                        let mut buf = vec![0; 1024];
+                        loop {
+                            while let Ok(Some(Frame::Chunk(chunk))) = decoder.next_frame() {
+                                chunks.push(chunk);
+                            }
+
+                            // Requests are an id chunk, an action chunk and a body chunk.
+                            if chunks.len() >= 3 {
+                                let mut request = chunks.drain(..3);
+                                let id = request.next().unwrap_or_default();
+                                let body = request.nth(1).unwrap_or_default();
+                                drop(request);
+
+                                let Ok(response) =
+                                    [Frame::Chunk(id), Frame::Chunk(body), Frame::End]
+                                        .iter()
+                                        .map(Frame::encode)
+                                        .collect::<IpcResult<Vec<_>>>()
+                                else {
+                                    return;
+                                };
+
+                                if stream.write_all(&response.concat()).await.is_err() {
+                                    return;
+                                }
+
+                                continue;
+                            }
+
+                            match stream.read(&mut buf).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => decoder.extend(&buf[..n]),
+                            }
+                        }
+                    });
+                }
+            });
+
+            Ok(addr)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tls_client_echo() -> anyhow::Result<()> {
+        let (ca_pem, server_config) = fixtures::ca_and_server_config()?;
+        let addr = fixtures::spawn_echo_server(server_config).await?;
+
+        let config = TlsConfig::new("localhost")?.ca_pem(ca_pem.as_bytes())?;
+        let mut client = Client::builder(TlsTransport::new(addr, config)?).build();
+
+        assert_eq!(client.request("echo", b"hello").await?.body(), b"hello");
+        assert_eq!(client.request("echo", b"world").await?.body(), b"world");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tls_client_rejects_untrusted_server() -> anyhow::Result<()> {
+        let (_, server_config) = fixtures::ca_and_server_config()?;
+        let addr = fixtures::spawn_echo_server(server_config).await?;
+
+        // Trust a different CA than the one that signed the server certificate.
+        let (other_ca_pem, _) = fixtures::ca_and_server_config()?;
+        let config = TlsConfig::new("localhost")?.ca_pem(other_ca_pem.as_bytes())?;
+        let mut client = Client::builder(TlsTransport::new(addr, config)?).build();
+        assert!(client.request("echo", b"hello").await.is_err());
+
+        // The server certificate is not valid for another name.
+        let (ca_pem, server_config) = fixtures::ca_and_server_config()?;
+        let addr = fixtures::spawn_echo_server(server_config).await?;
+        let config = TlsConfig::new("example.com")?.ca_pem(ca_pem.as_bytes())?;
+        let mut client = Client::builder(TlsTransport::new(addr, config)?).build();
+        assert!(client.request("echo", b"hello").await.is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tls_client_does_not_retry_certificate_errors() -> anyhow::Result<()> {
+        let (_, server_config) = fixtures::ca_and_server_config()?;
+        let addr = fixtures::spawn_echo_server(server_config).await?;
+
+        let (other_ca_pem, _) = fixtures::ca_and_server_config()?;
+        let config = TlsConfig::new("localhost")?.ca_pem(other_ca_pem.as_bytes())?;
+        let mut client = Client::builder(TlsTransport::new(addr, config)?)
+            .reconnect(ReconnectPolicy {
+                max_retries: 3,
+                initial_backoff: Duration::from_millis(1),
+                max_backoff: Duration::from_millis(1),
+            })
+            .build();
+
+        assert!(matches!(
+            client.request("echo", b"hello").await,
+            Err(IpcError::TlsError(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_tls_client_mutual_auth() -> anyhow::Result<()> {
+        let (ca_pem, client_cert_pem, client_key_pem, server_config) =
+            fixtures::ca_client_and_server_config()?;
+        let addr = fixtures::spawn_echo_server(server_config).await?;
+
+        let config = TlsConfig::new("localhost")?
+            .ca_pem(ca_pem.as_bytes())?
+            .client_cert_pem(client_cert_pem.as_bytes(), client_key_pem.as_bytes())?;
+        let mut client = Client::builder(TlsTransport::new(addr, config)?).build();
+        assert_eq!(client.request("echo", b"hello").await?.body(), b"hello");
+
+        // The server rejects clients without a certificate.
+        let config = TlsConfig::new("localhost")?.ca_pem(ca_pem.as_bytes())?;
+        let mut client = Client::builder(TlsTransport::new(addr, config)?).build();
+        assert!(client.request("echo", b"hello").await.is_err());
+
+        Ok(())
+    }
+}
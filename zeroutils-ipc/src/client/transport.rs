@@ -0,0 +1,57 @@
+use std::future::Future;
+
+use crate::{FrameDecoder, IpcResult, RequestId, Response};
+
+//--------------------------------------------------------------------------------------------------
+// Traits
+//--------------------------------------------------------------------------------------------------
+
+/// Establishes connections to an IPC server.
+pub trait Transport {
+    /// The connection type created by the transport.
+    type Connection: Connection;
+
+    /// Connects to the server.
+    fn connect(&self) -> impl Future<Output = IpcResult<Self::Connection>>;
+}
+
+/// A connection to an IPC server.
+pub trait Connection {
+    /// Sends a request for `action` with the given body and returns the response.
+    ///
+    /// The response must carry the `id` of the request.
+    ///
+    /// # Errors
+    ///
+    /// A dropped connection should be reported as an [`IpcError::IoError`][crate::IpcError::IoError]
+    /// so that the client can reconnect.
+    fn request(
+        &mut self,
+        id: RequestId,
+        action: &str,
+        body: &[u8],
+    ) -> impl Future<Output = IpcResult<Response>>;
+
+    /// Sends a request for `action` whose response is streamed as [`Frame`][crate::Frame]s.
+    ///
+    /// The first chunk of the response must be the big-endian `id` of the request.
+    fn send_stream_request(
+        &mut self,
+        id: RequestId,
+        action: &str,
+        body: &[u8],
+    ) -> impl Future<Output = IpcResult<()>>;
+
+    /// Reads the next bytes of a streamed response.
+    ///
+    /// The bytes do not have to align with frame boundaries. Empty bytes mean the connection closed.
+    fn read(&mut self) -> impl Future<Output = IpcResult<Vec<u8>>>;
+
+    /// Creates the decoder for the frames of a streamed response.
+    ///
+    /// The default decoder accepts payloads of up to
+    /// [`DEFAULT_MAX_FRAME_LEN`][crate::DEFAULT_MAX_FRAME_LEN] bytes.
+    fn frame_decoder(&self) -> FrameDecoder {
+        FrameDecoder::new()
+    }
+}
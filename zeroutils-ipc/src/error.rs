@@ -12,6 +12,48 @@ pub type IpcResult<T> = Result<T, IpcError>;
 /// The main error type.
 #[derive(Debug, Error)]
 pub enum IpcError {
+    /// Io error.
+    #[error("Io error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    /// The connection could not be re-established after the given number of retries.
+    #[error("Connection lost after {0} retries")]
+    ConnectionLost(u32),
+
+    /// A frame of a streamed response is invalid.
+    #[error("Invalid frame: {0}")]
+    InvalidFrame(String),
+
+    /// A frame payload is longer than allowed, with its length and the maximum length.
+    #[error("Frame payload of {0} bytes exceeds the maximum of {1} bytes")]
+    FrameTooLarge(usize, usize),
+
+    /// The connection closed before the end frame of a streamed response.
+    #[error("Connection closed before the end of the streamed response")]
+    UnexpectedEndOfStream,
+
+    /// Tls error, including certificate and handshake failures.
+    ///
+    /// These are not retried by a [`ReconnectPolicy`](crate::ReconnectPolicy) since reconnecting does
+    /// not fix them.
+    #[cfg(feature = "tls")]
+    #[error("Tls error: {0}")]
+    TlsError(#[from] tokio_rustls::rustls::Error),
+
+    /// The server name is not a valid DNS name or IP address.
+    #[cfg(feature = "tls")]
+    #[error("Invalid server name: {0}")]
+    InvalidServerName(String),
+
+    /// A PEM file does not contain a private key.
+    #[cfg(feature = "tls")]
+    #[error("Missing private key")]
+    MissingPrivateKey,
+
+    /// A response does not belong to the request it was received for.
+    #[error("Response id {1} does not match request id {0}")]
+    RequestIdMismatch(u64, u64),
+
     /// Custom error.
     #[error("Custom error: {0}")]
     Custom(#[from] AnyError),
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use super::DidResult;
+use crate::KeyResult;
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -106,7 +106,7 @@ impl Base {
     /// This follows the [Multibase][multibase] standard.
     ///
     /// [multibase]: https://github.com/multiformats/multibase
-    pub fn decode(encoded: impl AsRef<str>) -> DidResult<(Base, Vec<u8>)> {
+    pub fn decode(encoded: impl AsRef<str>) -> KeyResult<(Base, Vec<u8>)> {
         let (base, data) = multibase::decode(encoded)?;
         Ok((base.into(), data))
     }
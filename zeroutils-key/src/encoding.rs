@@ -0,0 +1,173 @@
+use crate::{
+    JwsAlgName, JwsAlgorithm, KeyError, KeyResult, P256Key, P384Key, Secp256k1Key, Sign, Verify,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// The encoding of an ECDSA signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SignatureEncoding {
+    /// The fixed-size `r || s` encoding used by JWS.
+    #[default]
+    Fixed,
+
+    /// The ASN.1 DER encoding used by X.509 and most TLS and OpenSSL based verifiers.
+    Der,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Traits
+//--------------------------------------------------------------------------------------------------
+
+/// A trait for ECDSA keys that can sign data with a chosen [`SignatureEncoding`].
+pub trait SignWithEncoding: Sign + JwsAlgName {
+    /// Signs data and returns the signature in the given encoding.
+    fn sign_with_encoding(&self, data: &[u8], encoding: SignatureEncoding) -> KeyResult<Vec<u8>> {
+        let signature = self.sign(data)?;
+        match encoding {
+            SignatureEncoding::Fixed => Ok(signature),
+            SignatureEncoding::Der => fixed_to_der(self.alg(), &signature),
+        }
+    }
+}
+
+/// A trait for ECDSA keys that can verify signatures in a chosen [`SignatureEncoding`].
+pub trait VerifyWithEncoding: Verify + JwsAlgName {
+    /// Verifies a signature in the given encoding against data.
+    fn verify_with_encoding(
+        &self,
+        data: &[u8],
+        signature: &[u8],
+        encoding: SignatureEncoding,
+    ) -> KeyResult<()> {
+        match encoding {
+            SignatureEncoding::Fixed => self.verify(data, signature),
+            SignatureEncoding::Der => self.verify(data, &der_to_fixed(self.alg(), signature)?),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Converts a DER encoded ECDSA signature to the fixed-size `r || s` encoding.
+///
+/// # Errors
+///
+/// Returns [`KeyError::UnsupportedSignatureEncoding`] for algorithms without a DER encoding, like
+/// `EdDSA`.
+pub fn der_to_fixed(alg: JwsAlgorithm, der: &[u8]) -> KeyResult<Vec<u8>> {
+    match alg {
+        JwsAlgorithm::ES256 => Ok(p256::ecdsa::Signature::from_der(der)?.to_vec()),
+        JwsAlgorithm::ES384 => Ok(p384::ecdsa::Signature::from_der(der)?.to_vec()),
+        JwsAlgorithm::ES256K => Ok(libsecp256k1::Signature::parse_der(der)?
+            .serialize()
+            .to_vec()),
+        alg => Err(KeyError::UnsupportedSignatureEncoding(alg)),
+    }
+}
+
+/// Converts a fixed-size `r || s` ECDSA signature to the DER encoding.
+///
+/// # Errors
+///
+/// Returns [`KeyError::UnsupportedSignatureEncoding`] for algorithms without a DER encoding, like
+/// `EdDSA`.
+pub fn fixed_to_der(alg: JwsAlgorithm, fixed: &[u8]) -> KeyResult<Vec<u8>> {
+    match alg {
+        JwsAlgorithm::ES256 => Ok(p256::ecdsa::Signature::try_from(fixed)?
+            .to_der()
+            .as_bytes()
+            .to_vec()),
+        JwsAlgorithm::ES384 => Ok(p384::ecdsa::Signature::try_from(fixed)?
+            .to_der()
+            .as_bytes()
+            .to_vec()),
+        JwsAlgorithm::ES256K => Ok(libsecp256k1::Signature::parse_standard_slice(fixed)?
+            .serialize_der()
+            .as_ref()
+            .to_vec()),
+        alg => Err(KeyError::UnsupportedSignatureEncoding(alg)),
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl<S> VerifyWithEncoding for P256Key<'_, S> {}
+
+impl<S> VerifyWithEncoding for P384Key<'_, S> {}
+
+impl<S> VerifyWithEncoding for Secp256k1Key<'_, S> {}
+
+impl<T> SignWithEncoding for T where T: VerifyWithEncoding + Sign {}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{Ed25519KeyPair, KeyPairGenerate, P256KeyPair, P384KeyPair, Secp256k1KeyPair};
+
+    use super::*;
+
+    fn check_conversions<K>(key_pair: &K) -> anyhow::Result<()>
+    where
+        K: SignWithEncoding + VerifyWithEncoding,
+    {
+        let data = b"hello world";
+
+        // Fixed to DER
+        let fixed = key_pair.sign_with_encoding(data, SignatureEncoding::Fixed)?;
+        let der = fixed_to_der(key_pair.alg(), &fixed)?;
+        assert_eq!(der[0], 0x30);
+        key_pair.verify_with_encoding(data, &der, SignatureEncoding::Der)?;
+        assert_eq!(der_to_fixed(key_pair.alg(), &der)?, fixed);
+
+        // DER to fixed
+        let der = key_pair.sign_with_encoding(data, SignatureEncoding::Der)?;
+        let fixed = der_to_fixed(key_pair.alg(), &der)?;
+        key_pair.verify_with_encoding(data, &fixed, SignatureEncoding::Fixed)?;
+        key_pair.verify(data, &fixed)?;
+
+        // Wrong encoding
+        assert!(key_pair
+            .verify_with_encoding(data, &der, SignatureEncoding::Fixed)
+            .is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_signature_encoding_p256() -> anyhow::Result<()> {
+        check_conversions(&P256KeyPair::generate(&mut rand::thread_rng())?)
+    }
+
+    #[test]
+    fn test_signature_encoding_p384() -> anyhow::Result<()> {
+        check_conversions(&P384KeyPair::generate(&mut rand::thread_rng())?)
+    }
+
+    #[test]
+    fn test_signature_encoding_secp256k1() -> anyhow::Result<()> {
+        check_conversions(&Secp256k1KeyPair::generate(&mut rand::thread_rng())?)
+    }
+
+    #[test]
+    fn test_signature_encoding_eddsa_unsupported() -> anyhow::Result<()> {
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let signature = key_pair.sign(b"hello world")?;
+
+        assert!(matches!(
+            fixed_to_der(JwsAlgorithm::EdDSA, &signature),
+            Err(KeyError::UnsupportedSignatureEncoding(JwsAlgorithm::EdDSA))
+        ));
+
+        Ok(())
+    }
+}
@@ -22,10 +22,94 @@ pub enum KeyError {
     #[error("secp256k1 error: {0}")]
     Secp256k1Error(#[from] libsecp256k1::Error),
 
+    /// PKCS#8 error.
+    #[error("PKCS#8 error: {0}")]
+    Pkcs8Error(#[from] pkcs8::Error),
+
+    /// SubjectPublicKeyInfo error.
+    #[error("SubjectPublicKeyInfo error: {0}")]
+    SpkiError(#[from] pkcs8::spki::Error),
+
+    /// Elliptic curve error.
+    #[error("Elliptic curve error: {0}")]
+    EllipticCurveError(#[from] p256::elliptic_curve::Error),
+
+    /// Mnemonic error.
+    #[error("Mnemonic error: {0}")]
+    MnemonicError(#[from] bip39::Error),
+
+    /// Base64 decode error.
+    #[error("Base64 decode error: {0}")]
+    Base64DecodeError(#[from] base64::DecodeError),
+
+    /// JSON error.
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    /// Invalid JWS header.
+    #[error("Invalid JWS header: {0}")]
+    InvalidJwsHeader(String),
+
+    /// Invalid compact JWS.
+    #[error("Invalid JWS: {0}")]
+    InvalidJws(String),
+
+    /// The signer of a JWS can not be determined from the JWS alone.
+    #[error("The signer of a {0} JWS without a `jwk` header can not be recovered")]
+    UnrecoverableJwsSigner(crate::JwsAlgorithm),
+
+    /// Invalid JSON Web Key.
+    #[error("Invalid JWK: {0}")]
+    InvalidJwk(String),
+
+    /// The signature and the key are of different key types.
+    #[error("Signature algorithm {0} does not match key algorithm {1}")]
+    SignatureAlgorithmMismatch(crate::JwsAlgorithm, crate::JwsAlgorithm),
+
+    /// Invalid signature string.
+    #[error("Invalid signature string: {0}")]
+    InvalidSignatureString(String),
+
     /// Unsupported JWS algorithm name.
     #[error("Unsupported JWS algorithm name: {0}")]
     UnsupportedJwsAlgName(String),
 
+    /// The algorithm does not support the requested signature encoding.
+    #[error("Unsupported signature encoding for algorithm: {0}")]
+    UnsupportedSignatureEncoding(crate::JwsAlgorithm),
+
+    /// The signature is not in low-S form.
+    #[error("Signature is not in low-S form")]
+    HighSSignature,
+
+    /// Base encoding or decoding error.
+    #[error("Base encoding or decoding error: {0}")]
+    BaseError(#[from] multibase::Error),
+
+    /// The multicodec prefix of a multikey is not a supported public key type.
+    #[error("Unsupported multikey multicodec: {0:#x?}")]
+    UnsupportedMulticodec(Option<u64>),
+
+    /// Invalid derivation path.
+    #[error("Invalid derivation path: {0}")]
+    InvalidDerivationPath(String),
+
+    /// Non-hardened derivation is not supported by the curve.
+    #[error("Non-hardened derivation is not supported for index: {0}")]
+    NonHardenedDerivation(u32),
+
+    /// Invalid key length.
+    #[error("Invalid key length: {0}")]
+    InvalidKeyLength(usize),
+
+    /// Invalid signature length, with the expected and the actual length.
+    #[error("Invalid signature length: expected {0} bytes, got {1}")]
+    InvalidSignatureLength(usize, usize),
+
+    /// Decryption failed.
+    #[error("Decryption failed")]
+    DecryptionFailed,
+
     /// Casting failed.
     #[error("Casting failed for type: {0:?}")]
     CastingFailed(TypeId),
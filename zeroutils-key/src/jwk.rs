@@ -0,0 +1,161 @@
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Ed25519PubKey, KeyError, KeyResult, P256PubKey, P384PubKey, PublicKeyGenerate, Secp256k1PubKey,
+    WrappedPubKey,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A public [JSON Web Key][ref].
+///
+/// `ed25519` keys use the `OKP` key type and the elliptic curve keys use the `EC` key type with their
+/// uncompressed `x` and `y` coordinates.
+///
+/// [ref]: https://datatracker.ietf.org/doc/html/rfc7517
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    /// The key type, `OKP` or `EC`.
+    pub kty: String,
+
+    /// The curve, e.g. `Ed25519` or `P-256`.
+    pub crv: String,
+
+    /// The base64url encoded public key or `x` coordinate.
+    pub x: String,
+
+    /// The base64url encoded `y` coordinate of `EC` keys.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl Jwk {
+    fn ec(crv: &str, uncompressed: &[u8]) -> Self {
+        let (x, y) = uncompressed[1..].split_at((uncompressed.len() - 1) / 2);
+        Self {
+            kty: "EC".to_string(),
+            crv: crv.to_string(),
+            x: BASE64_URL_SAFE_NO_PAD.encode(x),
+            y: Some(BASE64_URL_SAFE_NO_PAD.encode(y)),
+        }
+    }
+}
+
+impl WrappedPubKey<'_> {
+    /// Returns the public key as a [`Jwk`].
+    pub fn to_jwk(&self) -> Jwk {
+        match self {
+            WrappedPubKey::Ed25519(key) => Jwk {
+                kty: "OKP".to_string(),
+                crv: "Ed25519".to_string(),
+                x: BASE64_URL_SAFE_NO_PAD.encode(key.public.as_bytes()),
+                y: None,
+            },
+            WrappedPubKey::P256(key) => {
+                Jwk::ec("P-256", key.public.to_encoded_point(false).as_bytes())
+            }
+            WrappedPubKey::P384(key) => {
+                Jwk::ec("P-384", key.public.to_encoded_point(false).as_bytes())
+            }
+            WrappedPubKey::Secp256k1(key) => Jwk::ec(
+                "secp256k1",
+                &libsecp256k1::PublicKey::serialize(&key.public),
+            ),
+        }
+    }
+
+    /// Creates a public key from a [`Jwk`].
+    pub fn from_jwk(jwk: &Jwk) -> KeyResult<WrappedPubKey<'static>> {
+        let x = BASE64_URL_SAFE_NO_PAD.decode(&jwk.x)?;
+        match (jwk.kty.as_str(), jwk.crv.as_str(), &jwk.y) {
+            ("OKP", "Ed25519", None) => Ok(Ed25519PubKey::from_public_key(&x)?.into()),
+            ("EC", crv, Some(y)) => {
+                let y = BASE64_URL_SAFE_NO_PAD.decode(y)?;
+                if x.len() != y.len() {
+                    return Err(KeyError::InvalidJwk(format!("{jwk:?}")));
+                }
+
+                let uncompressed = [&[0x04][..], &x, &y].concat();
+                match crv {
+                    "P-256" => Ok(P256PubKey::from_public_key(&uncompressed)?.into()),
+                    "P-384" => Ok(P384PubKey::from_public_key(&uncompressed)?.into()),
+                    "secp256k1" => Ok(Secp256k1PubKey::from_public_key(&uncompressed)?.into()),
+                    _ => Err(KeyError::InvalidJwk(format!("{jwk:?}"))),
+                }
+            }
+            _ => Err(KeyError::InvalidJwk(format!("{jwk:?}"))),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Ed25519KeyPair, GetPublicKey, IntoOwned, KeyPairGenerate, P256KeyPair, P384KeyPair,
+        Secp256k1KeyPair,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_jwk_roundtrip() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let public_keys = [
+            WrappedPubKey::from(Ed25519KeyPair::generate(rng)?.public_key().into_owned()),
+            WrappedPubKey::from(P256KeyPair::generate(rng)?.public_key().into_owned()),
+            WrappedPubKey::from(P384KeyPair::generate(rng)?.public_key().into_owned()),
+            WrappedPubKey::from(Secp256k1KeyPair::generate(rng)?.public_key().into_owned()),
+        ];
+
+        for (public_key, (kty, crv)) in public_keys.iter().zip([
+            ("OKP", "Ed25519"),
+            ("EC", "P-256"),
+            ("EC", "P-384"),
+            ("EC", "secp256k1"),
+        ]) {
+            let jwk = public_key.to_jwk();
+            assert_eq!((jwk.kty.as_str(), jwk.crv.as_str()), (kty, crv));
+            assert_eq!(jwk.y.is_some(), kty == "EC");
+            assert_eq!(&WrappedPubKey::from_jwk(&jwk)?, public_key);
+
+            let serialized = serde_json::to_string(&jwk)?;
+            assert_eq!(serde_json::from_str::<Jwk>(&serialized)?, jwk);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jwk_invalid() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let public_key = WrappedPubKey::from(P256KeyPair::generate(rng)?.public_key().into_owned());
+
+        let mut jwk = public_key.to_jwk();
+        jwk.crv = "P-521".to_string();
+        assert!(matches!(
+            WrappedPubKey::from_jwk(&jwk),
+            Err(KeyError::InvalidJwk(_))
+        ));
+
+        let mut jwk = public_key.to_jwk();
+        jwk.y = None;
+        assert!(WrappedPubKey::from_jwk(&jwk).is_err());
+
+        let mut jwk = public_key.to_jwk();
+        jwk.x = "not base64!".to_string();
+        assert!(WrappedPubKey::from_jwk(&jwk).is_err());
+
+        Ok(())
+    }
+}
@@ -1,8 +1,16 @@
+//! [JSON Web Signature (JWS)][ref] signing and verification.
+//!
+//! [ref]: https://datatracker.ietf.org/doc/html/rfc7515
+
 use std::{fmt::Display, str::FromStr};
 
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
 use serde::{Deserialize, Serialize};
 
-use crate::KeyError;
+use crate::{
+    recover_pubkey_sha256, Jwk, JwsAlgName, KeyError, KeyResult, RecoverableSignature, Sign,
+    Verify, WrappedKeyPair, WrappedPubKey,
+};
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -19,11 +27,215 @@ pub enum JwsAlgorithm {
     #[serde(rename = "ES256")]
     ES256,
 
+    /// ECDSA using P-384 and SHA-384.
+    #[serde(rename = "ES384")]
+    ES384,
+
     /// ECDSA using secp256k1 and SHA-256.
     #[serde(rename = "ES256K")]
     ES256K,
 }
 
+/// The protected header of a detached JWS with an unencoded payload.
+#[derive(Debug, Serialize, Deserialize)]
+struct DetachedJwsHeader {
+    alg: JwsAlgorithm,
+    b64: bool,
+    crit: Vec<String>,
+}
+
+/// The protected header of a JWS that may embed the public key of the signer.
+#[derive(Debug, Serialize, Deserialize)]
+struct SignerJwsHeader {
+    alg: JwsAlgorithm,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    jwk: Option<Jwk>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crit: Option<Vec<String>>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Signs arbitrary bytes and returns a [RFC 7797][ref] detached JWS of the form
+/// `header..signature`.
+///
+/// The payload is signed unencoded (`"b64": false`) and is left out of the JWS, so it has to be
+/// passed to [`verify_detached`] along with the JWS.
+///
+/// [ref]: https://datatracker.ietf.org/doc/html/rfc7797
+pub fn sign_detached<K>(keypair: &K, payload: &[u8]) -> KeyResult<String>
+where
+    K: Sign + JwsAlgName,
+{
+    let header = DetachedJwsHeader {
+        alg: keypair.alg(),
+        b64: false,
+        crit: vec!["b64".to_string()],
+    };
+
+    let header = BASE64_URL_SAFE_NO_PAD.encode(serde_json::to_vec(&header)?);
+    let signature = keypair.sign(&detached_signing_input(&header, payload))?;
+
+    Ok(format!(
+        "{header}..{}",
+        BASE64_URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+/// Verifies the `header` and `signature` segments of a detached JWS created with [`sign_detached`]
+/// against the original `payload`.
+///
+/// # Errors
+///
+/// Returns [`KeyError::InvalidJwsHeader`] if the header does not describe an unencoded payload signed
+/// with the algorithm of `pubkey`.
+pub fn verify_detached<K>(
+    pubkey: &K,
+    header: &str,
+    payload: &[u8],
+    signature: &str,
+) -> KeyResult<()>
+where
+    K: Verify + JwsAlgName,
+{
+    let decoded: DetachedJwsHeader =
+        serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(header)?)?;
+
+    if decoded.alg != pubkey.alg() || decoded.b64 || !decoded.crit.iter().any(|c| c == "b64") {
+        return Err(KeyError::InvalidJwsHeader(header.to_string()));
+    }
+
+    let signature = BASE64_URL_SAFE_NO_PAD.decode(signature)?;
+    pubkey.verify(&detached_signing_input(header, payload), &signature)
+}
+
+/// Signs `payload` and returns a compact JWS of the form `header.payload.signature`.
+///
+/// The header only contains the `alg` of `keypair`.
+pub fn sign<K>(payload: &[u8], keypair: &K) -> KeyResult<String>
+where
+    K: Sign + JwsAlgName,
+{
+    let header = SignerJwsHeader {
+        alg: keypair.alg(),
+        jwk: None,
+        crit: None,
+    };
+
+    sign_compact(&header, payload, keypair)
+}
+
+/// Verifies a compact JWS of the form `header.payload.signature` with `pubkey` and returns the decoded
+/// payload.
+///
+/// # Errors
+///
+/// Returns [`KeyError::SignatureAlgorithmMismatch`] if the `alg` of the JWS is not the algorithm of
+/// `pubkey`, and [`KeyError::InvalidJwsHeader`] if the header has critical extensions, which are not
+/// supported.
+pub fn verify<K>(jws: &str, pubkey: &K) -> KeyResult<Vec<u8>>
+where
+    K: Verify + JwsAlgName,
+{
+    let (header, payload, signature) = split_compact(jws)?;
+    let decoded: SignerJwsHeader = serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(header)?)?;
+
+    if decoded.alg != pubkey.alg() {
+        return Err(KeyError::SignatureAlgorithmMismatch(
+            decoded.alg,
+            pubkey.alg(),
+        ));
+    }
+
+    if decoded.crit.is_some() {
+        return Err(KeyError::InvalidJwsHeader(header.to_string()));
+    }
+
+    let signature = BASE64_URL_SAFE_NO_PAD.decode(signature)?;
+    pubkey.verify(format!("{header}.{payload}").as_bytes(), &signature)?;
+
+    Ok(BASE64_URL_SAFE_NO_PAD.decode(payload)?)
+}
+
+/// Signs `payload` and returns a compact JWS of the form `header.payload.signature` that embeds the
+/// public key of `keypair` in the `jwk` header parameter.
+///
+/// The signer can be found again with [`recover_jws_signer`].
+pub fn sign_with_jwk(keypair: &WrappedKeyPair, payload: &[u8]) -> KeyResult<String> {
+    let header = SignerJwsHeader {
+        alg: keypair.alg(),
+        jwk: Some(keypair.public_key().to_jwk()),
+        crit: None,
+    };
+
+    sign_compact(&header, payload, keypair)
+}
+
+/// Finds the public key that signed a compact JWS of the form `header.payload.signature`.
+///
+/// The signer is taken from the `jwk` header parameter, in which case the signature is verified against
+/// it, or recovered from `ES256K` signatures that carry a recovery id in a 65th byte, as produced by
+/// [`Secp256k1KeyPair::sign_recoverable_sha256`](crate::Secp256k1KeyPair::sign_recoverable_sha256).
+///
+/// # Errors
+///
+/// Returns [`KeyError::UnrecoverableJwsSigner`] if the JWS has neither.
+pub fn recover_jws_signer(jws: &str) -> KeyResult<WrappedPubKey<'static>> {
+    let (header, payload, signature) = split_compact(jws)?;
+    let decoded: SignerJwsHeader = serde_json::from_slice(&BASE64_URL_SAFE_NO_PAD.decode(header)?)?;
+    let signing_input = format!("{header}.{payload}");
+    let signature = BASE64_URL_SAFE_NO_PAD.decode(signature)?;
+
+    match (decoded.jwk, decoded.alg) {
+        (Some(jwk), alg) => {
+            let public_key = WrappedPubKey::from_jwk(&jwk)?;
+            if public_key.alg() != alg {
+                return Err(KeyError::SignatureAlgorithmMismatch(alg, public_key.alg()));
+            }
+
+            public_key.verify(signing_input.as_bytes(), &signature)?;
+            Ok(public_key)
+        }
+        (None, JwsAlgorithm::ES256K) if signature.len() == RecoverableSignature::LENGTH => {
+            let signature = RecoverableSignature::from_bytes(&signature)?;
+            Ok(recover_pubkey_sha256(signing_input.as_bytes(), &signature)?.into())
+        }
+        (None, alg) => Err(KeyError::UnrecoverableJwsSigner(alg)),
+    }
+}
+
+fn sign_compact<K>(header: &SignerJwsHeader, payload: &[u8], keypair: &K) -> KeyResult<String>
+where
+    K: Sign,
+{
+    let header = BASE64_URL_SAFE_NO_PAD.encode(serde_json::to_vec(header)?);
+    let payload = BASE64_URL_SAFE_NO_PAD.encode(payload);
+    let signature = keypair.sign(format!("{header}.{payload}").as_bytes())?;
+
+    Ok(format!(
+        "{header}.{payload}.{}",
+        BASE64_URL_SAFE_NO_PAD.encode(signature)
+    ))
+}
+
+fn split_compact(jws: &str) -> KeyResult<(&str, &str, &str)> {
+    let [header, payload, signature] = jws.split('.').collect::<Vec<_>>()[..] else {
+        return Err(KeyError::InvalidJws(jws.to_string()));
+    };
+
+    Ok((header, payload, signature))
+}
+
+fn detached_signing_input(header: &str, payload: &[u8]) -> Vec<u8> {
+    let mut input = Vec::with_capacity(header.len() + 1 + payload.len());
+    input.extend_from_slice(header.as_bytes());
+    input.push(b'.');
+    input.extend_from_slice(payload);
+    input
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
@@ -33,6 +245,7 @@ impl Display for JwsAlgorithm {
         match self {
             JwsAlgorithm::EdDSA => write!(f, "EdDSA"),
             JwsAlgorithm::ES256 => write!(f, "ES256"),
+            JwsAlgorithm::ES384 => write!(f, "ES384"),
             JwsAlgorithm::ES256K => write!(f, "ES256K"),
         }
     }
@@ -45,8 +258,172 @@ impl FromStr for JwsAlgorithm {
         match s {
             "EdDSA" => Ok(JwsAlgorithm::EdDSA),
             "ES256" => Ok(JwsAlgorithm::ES256),
+            "ES384" => Ok(JwsAlgorithm::ES384),
             "ES256K" => Ok(JwsAlgorithm::ES256K),
             s => Err(KeyError::UnsupportedJwsAlgName(s.to_string())),
         }
     }
 }
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Ed25519KeyPair, GetPublicKey, IntoOwned, KeyPairGenerate, P256KeyPair, P384KeyPair,
+        Secp256k1KeyPair,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_jws_detached_sign_and_verify() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let key_pair = Ed25519KeyPair::generate(&mut rng)?;
+        let payload = b"arbitrary bytes \x00\xff";
+
+        let jws = sign_detached(&key_pair, payload)?;
+        let [header, body, signature] = jws.split('.').collect::<Vec<_>>()[..] else {
+            panic!("expected three segments: {jws}");
+        };
+
+        assert!(body.is_empty());
+        verify_detached(&key_pair.public_key(), header, payload, signature)?;
+
+        // Changed bytes
+        assert!(
+            verify_detached(&key_pair.public_key(), header, b"other bytes", signature).is_err()
+        );
+
+        // Different key with a different algorithm
+        let other = P256KeyPair::generate(&mut rng)?;
+        assert!(matches!(
+            verify_detached(&other.public_key(), header, payload, signature),
+            Err(KeyError::InvalidJwsHeader(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jws_sign_and_verify() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let payload = br#"{"hello":"world"}"#;
+
+        let ed25519 = Ed25519KeyPair::generate(rng)?;
+        let jws = sign(payload, &ed25519)?;
+        assert_eq!(jws.split('.').count(), 3);
+        assert_eq!(verify(&jws, &ed25519.public_key())?, payload);
+
+        let p256 = P256KeyPair::generate(rng)?;
+        let jws = sign(payload, &p256)?;
+        assert_eq!(verify(&jws, &p256.public_key())?, payload);
+
+        // Wrong key type
+        assert!(matches!(
+            verify(&jws, &ed25519.public_key()),
+            Err(KeyError::SignatureAlgorithmMismatch(
+                JwsAlgorithm::ES256,
+                JwsAlgorithm::EdDSA
+            ))
+        ));
+
+        // Wrong key of the same type
+        let other = P256KeyPair::generate(rng)?;
+        assert!(verify(&jws, &other.public_key()).is_err());
+
+        // Detached JWS are not supported.
+        let detached = sign_detached(&p256, payload)?;
+        assert!(verify(&detached, &p256.public_key()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jws_verify_tampered() -> anyhow::Result<()> {
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let jws = sign(b"hello world", &key_pair)?;
+        let (header, _, signature) = split_compact(&jws)?;
+
+        let payload = BASE64_URL_SAFE_NO_PAD.encode("hello worle");
+        let tampered = format!("{header}.{payload}.{signature}");
+        assert!(verify(&tampered, &key_pair.public_key()).is_err());
+
+        let header = BASE64_URL_SAFE_NO_PAD.encode(r#"{"alg":"EdDSA","kid":"x"}"#);
+        let payload = BASE64_URL_SAFE_NO_PAD.encode("hello world");
+        let tampered = format!("{header}.{payload}.{signature}");
+        assert!(verify(&tampered, &key_pair.public_key()).is_err());
+
+        assert!(matches!(
+            verify("a.b", &key_pair.public_key()),
+            Err(KeyError::InvalidJws(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jws_recover_signer_from_jwk() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let key_pairs = [
+            WrappedKeyPair::from(Ed25519KeyPair::generate(rng)?),
+            WrappedKeyPair::from(P256KeyPair::generate(rng)?),
+            WrappedKeyPair::from(P384KeyPair::generate(rng)?),
+            WrappedKeyPair::from(Secp256k1KeyPair::generate(rng)?),
+        ];
+
+        for key_pair in &key_pairs {
+            let jws = sign_with_jwk(key_pair, b"hello world")?;
+            assert_eq!(recover_jws_signer(&jws)?, key_pair.public_key());
+
+            // The signature must match the embedded key.
+            let (signing_input, _) = jws.rsplit_once('.').unwrap();
+            let other = sign_with_jwk(&key_pairs[0], b"other")?;
+            let (_, other_signature) = other.rsplit_once('.').unwrap();
+            assert!(recover_jws_signer(&format!("{signing_input}.{other_signature}")).is_err());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_jws_recover_signer_es256k() -> anyhow::Result<()> {
+        let key_pair = Secp256k1KeyPair::generate(&mut rand::thread_rng())?;
+
+        let header = BASE64_URL_SAFE_NO_PAD.encode(r#"{"alg":"ES256K"}"#);
+        let payload = BASE64_URL_SAFE_NO_PAD.encode("hello world");
+        let signing_input = format!("{header}.{payload}");
+        let signature = key_pair.sign_recoverable_sha256(signing_input.as_bytes());
+
+        // The first 64 bytes are a regular `ES256K` signature.
+        key_pair.verify(signing_input.as_bytes(), &signature.to_bytes()[..64])?;
+
+        let jws = format!(
+            "{signing_input}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(signature.to_bytes())
+        );
+        assert_eq!(
+            recover_jws_signer(&jws)?,
+            WrappedPubKey::from(key_pair.public_key().into_owned())
+        );
+
+        // Without the recovery id, the signer can not be recovered.
+        let jws = format!(
+            "{signing_input}.{}",
+            BASE64_URL_SAFE_NO_PAD.encode(&signature.to_bytes()[..64])
+        );
+        assert!(matches!(
+            recover_jws_signer(&jws),
+            Err(KeyError::UnrecoverableJwsSigner(JwsAlgorithm::ES256K))
+        ));
+
+        assert!(matches!(
+            recover_jws_signer("not a jws"),
+            Err(KeyError::InvalidJws(_))
+        ));
+
+        Ok(())
+    }
+}
@@ -1,10 +1,15 @@
-use std::{any::Any, borrow::Cow};
+use std::{
+    any::Any,
+    borrow::Cow,
+    fmt::{self, Debug},
+};
 
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    Ed25519KeyPair, Ed25519PubKey, GetPublicKey, IntoOwned, KeyError, KeyPairBytes, KeyResult,
-    P256KeyPair, P256PubKey, PublicKeyBytes, Secp256k1KeyPair, Secp256k1PubKey, Sign, Verify,
+    Ed25519KeyPair, Ed25519PubKey, GetPublicKey, IntoOwned, JwsAlgName, JwsAlgorithm, KeyError,
+    KeyPairBytes, KeyResult, P256KeyPair, P256PubKey, P384KeyPair, P384PubKey, PublicKeyBytes,
+    Secp256k1KeyPair, Secp256k1PubKey, Sign, Verify,
 };
 
 //--------------------------------------------------------------------------------------------------
@@ -12,7 +17,10 @@ use crate::{
 //--------------------------------------------------------------------------------------------------
 
 /// A key pair with a public and private key.
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// The private key is never included in the `Debug` output. Private keys of the supported key types
+/// are wiped from memory when dropped.
+#[derive(Clone, PartialEq, Eq)]
 pub struct AsymmetricKey<'a, P, S>
 where
     P: Clone,
@@ -35,6 +43,10 @@ pub enum KeyType {
     #[serde(rename = "p256")]
     P256,
 
+    /// A `NIST P-384` key
+    #[serde(rename = "p384")]
+    P384,
+
     /// A `secp256k1` key
     #[serde(rename = "secp256k1")]
     Secp256k1,
@@ -50,7 +62,9 @@ pub enum KeyType {
 /// Key types supported:
 /// - `ed25519`
 /// - `NIST P-256`
+/// - `NIST P-384`
 /// - `secp256k1`
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WrappedPubKey<'a> {
     /// `ed25519` public key.
     Ed25519(Ed25519PubKey<'a>),
@@ -58,6 +72,9 @@ pub enum WrappedPubKey<'a> {
     /// `NIST P-256` public key.
     P256(P256PubKey<'a>),
 
+    /// `NIST P-384` public key.
+    P384(P384PubKey<'a>),
+
     /// `secp256k1` public key.
     Secp256k1(Secp256k1PubKey<'a>),
 }
@@ -73,6 +90,7 @@ pub enum WrappedPubKey<'a> {
 /// Key types supported:
 /// - `ed25519`
 /// - `NIST P-256`
+/// - `NIST P-384`
 /// - `secp256k1`
 #[allow(clippy::large_enum_variant)]
 pub enum WrappedKeyPair<'a> {
@@ -82,10 +100,35 @@ pub enum WrappedKeyPair<'a> {
     /// `NIST P-256` public key.
     P256(P256KeyPair<'a>),
 
+    /// `NIST P-384` public key.
+    P384(P384KeyPair<'a>),
+
     /// `secp256k1` public key.
     Secp256k1(Secp256k1KeyPair<'a>),
 }
 
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl WrappedPubKey<'_> {
+    /// Returns the JWS algorithm of the wrapped public key.
+    ///
+    /// This is the same as [`JwsAlgName::alg`] but does not need the trait in scope.
+    pub fn jws_alg(&self) -> JwsAlgorithm {
+        self.alg()
+    }
+}
+
+impl WrappedKeyPair<'_> {
+    /// Returns the JWS algorithm of the wrapped key pair.
+    ///
+    /// This is the same as [`JwsAlgName::alg`] but does not need the trait in scope.
+    pub fn jws_alg(&self) -> JwsAlgorithm {
+        self.alg()
+    }
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations: AsymmetricKey
 //--------------------------------------------------------------------------------------------------
@@ -105,6 +148,17 @@ where
     }
 }
 
+impl<P> Debug for PubKey<'_, P>
+where
+    P: Clone + Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsymmetricKey")
+            .field("public", &self.public)
+            .finish()
+    }
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations: WrappedPubKey
 //--------------------------------------------------------------------------------------------------
@@ -114,16 +168,29 @@ impl Verify for WrappedPubKey<'_> {
         match self {
             WrappedPubKey::Ed25519(wk) => wk.verify(data, signature),
             WrappedPubKey::P256(wk) => wk.verify(data, signature),
+            WrappedPubKey::P384(wk) => wk.verify(data, signature),
             WrappedPubKey::Secp256k1(wk) => wk.verify(data, signature),
         }
     }
 }
 
+impl JwsAlgName for WrappedPubKey<'_> {
+    fn alg(&self) -> JwsAlgorithm {
+        match self {
+            WrappedPubKey::Ed25519(wk) => wk.alg(),
+            WrappedPubKey::P256(wk) => wk.alg(),
+            WrappedPubKey::P384(wk) => wk.alg(),
+            WrappedPubKey::Secp256k1(wk) => wk.alg(),
+        }
+    }
+}
+
 impl PublicKeyBytes for WrappedPubKey<'_> {
     fn public_key_bytes(&self) -> Vec<u8> {
         match self {
             WrappedPubKey::Ed25519(wk) => wk.public_key_bytes(),
             WrappedPubKey::P256(wk) => wk.public_key_bytes(),
+            WrappedPubKey::P384(wk) => wk.public_key_bytes(),
             WrappedPubKey::Secp256k1(wk) => wk.public_key_bytes(),
         }
     }
@@ -138,6 +205,7 @@ impl Verify for WrappedKeyPair<'_> {
         match self {
             WrappedKeyPair::Ed25519(wk) => wk.verify(data, signature),
             WrappedKeyPair::P256(wk) => wk.verify(data, signature),
+            WrappedKeyPair::P384(wk) => wk.verify(data, signature),
             WrappedKeyPair::Secp256k1(wk) => wk.verify(data, signature),
         }
     }
@@ -148,16 +216,29 @@ impl Sign for WrappedKeyPair<'_> {
         match self {
             WrappedKeyPair::Ed25519(wk) => wk.sign(data),
             WrappedKeyPair::P256(wk) => wk.sign(data),
+            WrappedKeyPair::P384(wk) => wk.sign(data),
             WrappedKeyPair::Secp256k1(wk) => wk.sign(data),
         }
     }
 }
 
+impl JwsAlgName for WrappedKeyPair<'_> {
+    fn alg(&self) -> JwsAlgorithm {
+        match self {
+            WrappedKeyPair::Ed25519(wk) => wk.alg(),
+            WrappedKeyPair::P256(wk) => wk.alg(),
+            WrappedKeyPair::P384(wk) => wk.alg(),
+            WrappedKeyPair::Secp256k1(wk) => wk.alg(),
+        }
+    }
+}
+
 impl PublicKeyBytes for WrappedKeyPair<'_> {
     fn public_key_bytes(&self) -> Vec<u8> {
         match self {
             WrappedKeyPair::Ed25519(wk) => wk.public_key_bytes(),
             WrappedKeyPair::P256(wk) => wk.public_key_bytes(),
+            WrappedKeyPair::P384(wk) => wk.public_key_bytes(),
             WrappedKeyPair::Secp256k1(wk) => wk.public_key_bytes(),
         }
     }
@@ -168,6 +249,7 @@ impl KeyPairBytes for WrappedKeyPair<'_> {
         match self {
             WrappedKeyPair::Ed25519(wk) => wk.private_key_bytes(),
             WrappedKeyPair::P256(wk) => wk.private_key_bytes(),
+            WrappedKeyPair::P384(wk) => wk.private_key_bytes(),
             WrappedKeyPair::Secp256k1(wk) => wk.private_key_bytes(),
         }
     }
@@ -187,6 +269,7 @@ impl<'a> WrappedPubKey<'a> {
         let any = match self {
             WrappedPubKey::Ed25519(wk) => Box::new(wk) as Box<dyn Any>,
             WrappedPubKey::P256(wk) => Box::new(wk) as Box<dyn Any>,
+            WrappedPubKey::P384(wk) => Box::new(wk) as Box<dyn Any>,
             WrappedPubKey::Secp256k1(wk) => Box::new(wk) as Box<dyn Any>,
         };
 
@@ -202,6 +285,7 @@ impl<'a> WrappedPubKey<'a> {
         match self {
             WrappedPubKey::Ed25519(wk) => WrappedPubKey::Ed25519(wk.into_owned()),
             WrappedPubKey::P256(wk) => WrappedPubKey::P256(wk.into_owned()),
+            WrappedPubKey::P384(wk) => WrappedPubKey::P384(wk.into_owned()),
             WrappedPubKey::Secp256k1(wk) => WrappedPubKey::Secp256k1(wk.into_owned()),
         }
     }
@@ -217,6 +301,7 @@ impl<'a> WrappedKeyPair<'a> {
         let any = match self {
             WrappedKeyPair::Ed25519(wk) => Box::new(wk) as Box<dyn Any>,
             WrappedKeyPair::P256(wk) => Box::new(wk) as Box<dyn Any>,
+            WrappedKeyPair::P384(wk) => Box::new(wk) as Box<dyn Any>,
             WrappedKeyPair::Secp256k1(wk) => Box::new(wk) as Box<dyn Any>,
         };
 
@@ -232,7 +317,96 @@ impl<'a> WrappedKeyPair<'a> {
         match self {
             WrappedKeyPair::Ed25519(wk) => WrappedPubKey::Ed25519(wk.public_key()),
             WrappedKeyPair::P256(wk) => WrappedPubKey::P256(wk.public_key()),
+            WrappedKeyPair::P384(wk) => WrappedPubKey::P384(wk.public_key()),
             WrappedKeyPair::Secp256k1(wk) => WrappedPubKey::Secp256k1(wk.public_key()),
         }
     }
 }
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::SigningKey;
+    use zeroize::ZeroizeOnDrop;
+
+    use crate::{KeyPairGenerate, Secp256k1SecretKey, X25519SecretKey};
+
+    use super::*;
+
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+
+    fn assert_redacted(debug: String, private_key_bytes: Vec<u8>) {
+        assert!(debug.contains("[REDACTED]"));
+        assert!(!debug.contains(&format!("{:?}", private_key_bytes)));
+        assert!(!debug.contains(format!("{:?}", &private_key_bytes[..4]).trim_matches(['[', ']'])));
+    }
+
+    #[test]
+    fn test_secret_keys_zeroize_on_drop() {
+        assert_zeroize_on_drop::<SigningKey>();
+        assert_zeroize_on_drop::<p256::ecdsa::SigningKey>();
+        assert_zeroize_on_drop::<p384::ecdsa::SigningKey>();
+        assert_zeroize_on_drop::<Secp256k1SecretKey>();
+    }
+
+    #[test]
+    fn test_key_pair_debug_is_redacted() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+
+        let key_pair = Ed25519KeyPair::generate(&mut rng)?;
+        assert_redacted(format!("{:?}", key_pair), key_pair.private_key_bytes());
+
+        let key_pair = P256KeyPair::generate(&mut rng)?;
+        assert_redacted(format!("{:?}", key_pair), key_pair.private_key_bytes());
+
+        let key_pair = P384KeyPair::generate(&mut rng)?;
+        assert_redacted(format!("{:?}", key_pair), key_pair.private_key_bytes());
+
+        let key_pair = Secp256k1KeyPair::generate(&mut rng)?;
+        assert_redacted(format!("{:?}", key_pair), key_pair.private_key_bytes());
+
+        let key_pair = X25519SecretKey::generate(&mut rng)?;
+        assert_redacted(format!("{:?}", key_pair), key_pair.private_key_bytes());
+
+        // Public keys have nothing to redact.
+        let public_key = Ed25519KeyPair::generate(&mut rng)?
+            .public_key()
+            .into_owned();
+        assert!(!format!("{:?}", public_key).contains("[REDACTED]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped_keys_jws_alg() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let key_pairs = [
+            (
+                WrappedKeyPair::from(Ed25519KeyPair::generate(&mut rng)?),
+                JwsAlgorithm::EdDSA,
+            ),
+            (
+                WrappedKeyPair::from(P256KeyPair::generate(&mut rng)?),
+                JwsAlgorithm::ES256,
+            ),
+            (
+                WrappedKeyPair::from(P384KeyPair::generate(&mut rng)?),
+                JwsAlgorithm::ES384,
+            ),
+            (
+                WrappedKeyPair::from(Secp256k1KeyPair::generate(&mut rng)?),
+                JwsAlgorithm::ES256K,
+            ),
+        ];
+
+        for (key_pair, alg) in key_pairs {
+            assert_eq!(key_pair.jws_alg(), alg);
+            assert_eq!(key_pair.public_key().jws_alg(), alg);
+        }
+
+        Ok(())
+    }
+}
@@ -5,24 +5,43 @@
 #![warn(missing_docs)]
 #![allow(clippy::module_inception)]
 
+mod base;
 mod ed25519;
+mod encoding;
 mod error;
-mod jws;
+mod jwk;
 mod key;
+mod mnemonic;
+mod multikey;
 mod p256;
+mod p384;
+mod sealed;
 mod secp256k1;
+mod signature;
+mod slip10;
 mod traits;
 mod x25519;
 
+pub mod jws;
+
 //--------------------------------------------------------------------------------------------------
 // Exports
 //--------------------------------------------------------------------------------------------------
 
+pub use base::*;
 pub use ed25519::*;
+pub use encoding::*;
 pub use error::*;
-pub use jws::*;
+pub use jwk::*;
+pub use jws::{recover_jws_signer, sign_detached, sign_with_jwk, verify_detached, JwsAlgorithm};
 pub use key::*;
+pub use mnemonic::*;
+pub use multikey::*;
 pub use p256::*;
+pub use p384::*;
+pub use sealed::*;
 pub use secp256k1::*;
+pub use signature::*;
+pub use slip10::*;
 pub use traits::*;
 pub use x25519::*;
@@ -0,0 +1,138 @@
+use std::{fmt::Display, str::FromStr};
+
+use rand_core::{CryptoRngCore, OsRng};
+
+use crate::{KeyError, KeyResult};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A [BIP39][ref] mnemonic phrase in English that encodes the entropy a key can be derived from.
+///
+/// Mnemonics are meant for backing up keys in a form that is easy for users to write down.
+///
+/// [ref]: https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic(bip39::Mnemonic);
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl Mnemonic {
+    /// Generates a new random mnemonic with `word_count` words, which must be either 12 or 24.
+    pub fn generate(word_count: usize) -> KeyResult<Self> {
+        Self::generate_with(&mut OsRng, word_count)
+    }
+
+    /// Generates a new mnemonic with `word_count` words, which must be either 12 or 24, using the
+    /// given random number generator.
+    pub fn generate_with(rng: &mut impl CryptoRngCore, word_count: usize) -> KeyResult<Self> {
+        let mut entropy = [0; 32];
+        let entropy = match word_count {
+            12 => &mut entropy[..16],
+            24 => &mut entropy[..],
+            _ => return Err(bip39::Error::BadWordCount(word_count).into()),
+        };
+
+        rng.fill_bytes(entropy);
+        Self::from_entropy(entropy)
+    }
+
+    /// Creates a mnemonic from 16 or 32 bytes of entropy.
+    pub fn from_entropy(entropy: &[u8]) -> KeyResult<Self> {
+        Ok(Self(bip39::Mnemonic::from_entropy(entropy)?))
+    }
+
+    /// Returns the number of words in the mnemonic.
+    pub fn word_count(&self) -> usize {
+        self.0.word_count()
+    }
+
+    /// Derives the 64-byte BIP39 seed from the mnemonic and an optional `passphrase`.
+    ///
+    /// An empty passphrase is the same as no passphrase.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        self.0.to_seed(passphrase)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl FromStr for Mnemonic {
+    type Err = KeyError;
+
+    /// Parses a mnemonic phrase, validating its words and checksum.
+    fn from_str(phrase: &str) -> KeyResult<Self> {
+        Ok(Self(bip39::Mnemonic::parse_normalized(phrase)?))
+    }
+}
+
+impl Display for Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Mnemonic").field(&"[REDACTED]").finish()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mnemonic_generate() -> anyhow::Result<()> {
+        let mnemonic = Mnemonic::generate(12)?;
+        assert_eq!(mnemonic.word_count(), 12);
+
+        let mnemonic = Mnemonic::generate(24)?;
+        assert_eq!(mnemonic.word_count(), 24);
+        assert_eq!(Mnemonic::from_str(&mnemonic.to_string())?, mnemonic);
+
+        assert!(Mnemonic::generate(13).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mnemonic_bip39_test_vectors() -> anyhow::Result<()> {
+        // Test vectors from https://github.com/trezor/python-mnemonic/blob/master/vectors.json
+        let mnemonic = Mnemonic::from_entropy(&[0; 16])?;
+        assert_eq!(
+            mnemonic.to_string(),
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+        );
+        assert_eq!(
+            hex::encode(mnemonic.to_seed("TREZOR")),
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04"
+        );
+
+        let mnemonic = Mnemonic::from_entropy(&[0; 32])?;
+        assert_eq!(mnemonic.word_count(), 24);
+        assert!(mnemonic.to_string().ends_with("abandon art"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mnemonic_rejects_invalid_checksum() {
+        let phrase = ["abandon"; 12].join(" ");
+        assert!(matches!(
+            Mnemonic::from_str(&phrase),
+            Err(KeyError::MnemonicError(bip39::Error::InvalidChecksum))
+        ));
+
+        assert!(Mnemonic::from_str("not a valid mnemonic phrase").is_err());
+    }
+}
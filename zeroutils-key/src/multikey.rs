@@ -0,0 +1,222 @@
+use crate::{
+    Base, Ed25519PubKey, KeyError, KeyResult, P256PubKey, P384PubKey, PublicKeyBytes,
+    PublicKeyGenerate, Secp256k1PubKey, WrappedPubKey,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// `ed25519-pub` multicodec code and its varint representation.
+pub const ED25519_PUB_MULTICODEC: (u64, [u8; 2]) = (0xed, [0xED, 0x01]);
+
+/// `p256-pub` multicodec code and its varint representation.
+pub const P256_PUB_MULTICODEC: (u64, [u8; 2]) = (0x1200, [0x80, 0x24]);
+
+/// The `p256-pub` prefix that older `did:wk` identifiers were encoded with. It is not a valid varint
+/// of `0x1200` but is still accepted when decoding.
+const P256_PUB_LEGACY_PREFIX: [u8; 2] = [0x80, 0x1A];
+
+/// `p384-pub` multicodec code and its varint representation.
+pub const P384_PUB_MULTICODEC: (u64, [u8; 2]) = (0x1201, [0x81, 0x24]);
+
+/// `secp256k1-pub` multicodec code and its varint representation.
+pub const SECP256K1_PUB_MULTICODEC: (u64, [u8; 2]) = (0xe7, [0xE7, 0x01]);
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// Encodes and decodes public keys in the [Multikey][multikey] format.
+///
+/// A multikey is the public key bytes prefixed with the [Multicodec][multicodec] code of the key type
+/// and then encoded with [Multibase][multibase]. This is the format used by `did:key` and `did:wk`.
+///
+/// [multikey]: https://www.w3.org/TR/controller-document/#multikey
+/// [multicodec]: https://github.com/multiformats/multicodec
+/// [multibase]: https://github.com/multiformats/multibase
+pub struct Multikey;
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl Multikey {
+    /// Encodes the public key as a multikey with the given base encoding.
+    pub fn encode(public_key: &WrappedPubKey, base: Base) -> String {
+        base.encode(&Self::to_bytes(public_key))
+    }
+
+    /// Returns the public key bytes prefixed with the multicodec code of the key type.
+    ///
+    /// This is a multikey before the base encoding is applied.
+    pub fn to_bytes(public_key: &WrappedPubKey) -> Vec<u8> {
+        let (_, prefix) = match public_key {
+            WrappedPubKey::Ed25519(_) => ED25519_PUB_MULTICODEC,
+            WrappedPubKey::P256(_) => P256_PUB_MULTICODEC,
+            WrappedPubKey::P384(_) => P384_PUB_MULTICODEC,
+            WrappedPubKey::Secp256k1(_) => SECP256K1_PUB_MULTICODEC,
+        };
+
+        let mut bytes = prefix.to_vec();
+        bytes.extend(public_key.public_key_bytes());
+        bytes
+    }
+
+    /// Decodes a multikey into a public key and the base encoding it was encoded with.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyError::UnsupportedMulticodec`] if the multicodec prefix is not one of the supported
+    /// public key types.
+    pub fn decode(encoded: impl AsRef<str>) -> KeyResult<(WrappedPubKey<'static>, Base)> {
+        let (base, bytes) = Base::decode(encoded)?;
+        let prefix = bytes.get(..2).unwrap_or_default();
+        let key_bytes = bytes.get(2..).unwrap_or_default();
+
+        let public_key = if prefix == ED25519_PUB_MULTICODEC.1 {
+            Ed25519PubKey::from_public_key(key_bytes)?.into()
+        } else if prefix == P256_PUB_MULTICODEC.1 || prefix == P256_PUB_LEGACY_PREFIX {
+            P256PubKey::from_public_key(key_bytes)?.into()
+        } else if prefix == P384_PUB_MULTICODEC.1 {
+            P384PubKey::from_public_key(key_bytes)?.into()
+        } else if prefix == SECP256K1_PUB_MULTICODEC.1 {
+            Secp256k1PubKey::from_public_key(key_bytes)?.into()
+        } else {
+            return Err(KeyError::UnsupportedMulticodec(decode_varint(&bytes)));
+        };
+
+        Ok((public_key, base))
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Decodes the unsigned varint at the start of `bytes`.
+fn decode_varint(bytes: &[u8]) -> Option<u64> {
+    let mut value = 0;
+    for (i, byte) in bytes.iter().take(9).enumerate() {
+        value |= u64::from(byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+
+    None
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Ed25519KeyPair, GetPublicKey, IntoOwned, KeyPairGenerate, P256KeyPair, P384KeyPair,
+        Secp256k1KeyPair,
+    };
+
+    use super::*;
+
+    fn check_roundtrip(
+        public_key: WrappedPubKey,
+        base: Base,
+        (code, prefix): (u64, [u8; 2]),
+    ) -> anyhow::Result<()> {
+        let encoded = Multikey::encode(&public_key, base);
+
+        let (decoded_base, bytes) = Base::decode(&encoded)?;
+        assert_eq!(decoded_base, base);
+        assert_eq!(bytes[..2], prefix);
+        assert_eq!(decode_varint(&bytes), Some(code));
+
+        let (decoded, decoded_base) = Multikey::decode(&encoded)?;
+        assert_eq!(decoded, public_key);
+        assert_eq!(decoded_base, base);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multikey_ed25519() -> anyhow::Result<()> {
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let public_key = WrappedPubKey::from(key_pair.public_key().into_owned());
+
+        check_roundtrip(public_key.clone(), Base::Base58Btc, ED25519_PUB_MULTICODEC)?;
+        check_roundtrip(public_key, Base::Base32Lower, ED25519_PUB_MULTICODEC)?;
+
+        // Well-known `did:key` prefix for ed25519 keys.
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let public_key = WrappedPubKey::from(key_pair.public_key().into_owned());
+        assert!(Multikey::encode(&public_key, Base::Base58Btc).starts_with("z6Mk"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multikey_p256() -> anyhow::Result<()> {
+        let key_pair = P256KeyPair::generate(&mut rand::thread_rng())?;
+        let public_key = WrappedPubKey::from(key_pair.public_key().into_owned());
+
+        check_roundtrip(public_key.clone(), Base::Base58Btc, P256_PUB_MULTICODEC)?;
+        check_roundtrip(public_key.clone(), Base::Base64Url, P256_PUB_MULTICODEC)?;
+        assert!(Multikey::encode(&public_key, Base::Base58Btc).starts_with("zDn"));
+
+        // Keys encoded with the legacy prefix still decode.
+        let mut bytes = P256_PUB_LEGACY_PREFIX.to_vec();
+        bytes.extend(public_key.public_key_bytes());
+        let (decoded, _) = Multikey::decode(Base::Base58Btc.encode(&bytes))?;
+        assert_eq!(decoded, public_key);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multikey_p384() -> anyhow::Result<()> {
+        let key_pair = P384KeyPair::generate(&mut rand::thread_rng())?;
+        let public_key = WrappedPubKey::from(key_pair.public_key().into_owned());
+
+        check_roundtrip(public_key.clone(), Base::Base58Btc, P384_PUB_MULTICODEC)?;
+        check_roundtrip(public_key.clone(), Base::Base16Lower, P384_PUB_MULTICODEC)?;
+        assert!(Multikey::encode(&public_key, Base::Base58Btc).starts_with("z82"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multikey_secp256k1() -> anyhow::Result<()> {
+        let key_pair = Secp256k1KeyPair::generate(&mut rand::thread_rng())?;
+        let public_key = WrappedPubKey::from(key_pair.public_key().into_owned());
+
+        check_roundtrip(
+            public_key.clone(),
+            Base::Base58Btc,
+            SECP256K1_PUB_MULTICODEC,
+        )?;
+        check_roundtrip(public_key.clone(), Base::Base32Z, SECP256K1_PUB_MULTICODEC)?;
+        assert!(Multikey::encode(&public_key, Base::Base58Btc).starts_with("zQ3s"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multikey_decode_unsupported() -> anyhow::Result<()> {
+        // `x25519-pub`
+        let mut bytes = vec![0xEC, 0x01];
+        bytes.extend([7; 32]);
+
+        assert!(matches!(
+            Multikey::decode(Base::Base58Btc.encode(&bytes)),
+            Err(KeyError::UnsupportedMulticodec(Some(0xec)))
+        ));
+        assert!(matches!(
+            Multikey::decode(Base::Base58Btc.encode(&[0xED])),
+            Err(KeyError::UnsupportedMulticodec(None))
+        ));
+        assert!(Multikey::decode("not multibase").is_err());
+
+        Ok(())
+    }
+}
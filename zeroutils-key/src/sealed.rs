@@ -0,0 +1,210 @@
+//! Anonymous public-key encryption, also known as sealed boxes.
+
+use std::borrow::Cow;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    XChaCha20Poly1305, XNonce,
+};
+use rand_core::{CryptoRngCore, OsRng};
+use sha2::{Digest, Sha256};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::{
+    KeyError, KeyResult, SharedSecret, X25519KeyPair, X25519PubKey, X25519SecretKey,
+    X25519_KEY_LENGTH,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The number of bytes a sealed box adds to the plaintext: the ephemeral public key and the tag.
+pub const SEAL_OVERHEAD: usize = X25519_KEY_LENGTH + 16;
+
+const SEAL_INFO: &[u8] = b"zeroutils-key/sealed-box";
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Encrypts `plaintext` to `recipient` without revealing the sender.
+///
+/// This follows the libsodium sealed-box construction: a fresh ephemeral `x25519` key pair is
+/// generated for every message, the shared secret with the recipient is used to encrypt the message
+/// with XChaCha20-Poly1305 and the ephemeral public key is prepended to the ciphertext. The key and
+/// nonce are derived from the shared secret and both public keys.
+///
+/// NOTE: The key derivation uses HKDF-SHA256, so the output is not byte-compatible with libsodium.
+pub fn seal(recipient: &X25519PubKey, plaintext: &[u8]) -> Vec<u8> {
+    seal_with(&mut OsRng, recipient, plaintext)
+}
+
+/// Encrypts `plaintext` to `recipient` like [`seal`], generating the ephemeral key pair with the given
+/// random number generator.
+pub fn seal_with(
+    rng: &mut impl CryptoRngCore,
+    recipient: &X25519PubKey,
+    plaintext: &[u8],
+) -> Vec<u8> {
+    let ephemeral_secret = StaticSecret::random_from_rng(rng);
+    let ephemeral = X25519KeyPair {
+        public: Cow::Owned(PublicKey::from(&ephemeral_secret)),
+        private: ephemeral_secret,
+    };
+
+    let shared = ephemeral.diffie_hellman(recipient);
+    let (cipher, nonce) = derive_cipher(&shared, ephemeral.public.as_bytes(), recipient);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .expect("encryption with XChaCha20-Poly1305 does not fail for in-memory buffers");
+
+    let mut sealed = Vec::with_capacity(SEAL_OVERHEAD + plaintext.len());
+    sealed.extend_from_slice(ephemeral.public.as_bytes());
+    sealed.extend_from_slice(&ciphertext);
+    sealed
+}
+
+/// Decrypts a sealed box created with [`seal`] using the recipient's secret key.
+///
+/// # Errors
+///
+/// Returns [`KeyError::DecryptionFailed`] if the sealed box is malformed, has been tampered with or
+/// was not sealed for this recipient.
+pub fn unseal(recipient_secret: &X25519SecretKey, sealed: &[u8]) -> KeyResult<Vec<u8>> {
+    if sealed.len() < SEAL_OVERHEAD {
+        return Err(KeyError::DecryptionFailed);
+    }
+
+    let (ephemeral_public, ciphertext) = sealed.split_at(X25519_KEY_LENGTH);
+    let ephemeral_public: [u8; X25519_KEY_LENGTH] = ephemeral_public
+        .try_into()
+        .map_err(|_| KeyError::DecryptionFailed)?;
+
+    let ephemeral = X25519PubKey {
+        public: Cow::Owned(PublicKey::from(ephemeral_public)),
+        private: (),
+    };
+
+    let recipient = X25519PubKey {
+        public: Cow::Borrowed(&recipient_secret.public),
+        private: (),
+    };
+
+    let shared = recipient_secret.diffie_hellman(&ephemeral);
+    let (cipher, nonce) = derive_cipher(&shared, &ephemeral_public, &recipient);
+    cipher
+        .decrypt(&nonce, ciphertext)
+        .map_err(|_| KeyError::DecryptionFailed)
+}
+
+fn derive_cipher(
+    shared: &SharedSecret,
+    ephemeral_public: &[u8; X25519_KEY_LENGTH],
+    recipient: &X25519PubKey,
+) -> (XChaCha20Poly1305, XNonce) {
+    let mut salt = [0; 2 * X25519_KEY_LENGTH];
+    salt[..X25519_KEY_LENGTH].copy_from_slice(ephemeral_public);
+    salt[X25519_KEY_LENGTH..].copy_from_slice(recipient.public.as_bytes());
+
+    let key = shared.derive_key(&salt, SEAL_INFO);
+    let nonce = Sha256::digest(salt);
+
+    (
+        XChaCha20Poly1305::new(&key.into()),
+        *XNonce::from_slice(&nonce[..24]),
+    )
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use crate::{Ed25519KeyPair, GetPublicKey, KeyPairBytes, KeyPairGenerate, PublicKeyBytes};
+
+    use super::*;
+
+    #[test]
+    fn test_seal_and_unseal() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let recipient = X25519SecretKey::generate(&mut rng)?;
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+
+        let sealed = seal(&X25519PubKey::from(&recipient), plaintext);
+        assert_eq!(sealed.len(), plaintext.len() + SEAL_OVERHEAD);
+
+        let unsealed = unseal(&recipient, &sealed)?;
+        assert_eq!(unsealed, plaintext);
+
+        // Sealing the same message twice gives different ciphertexts.
+        let sealed_again = seal(&X25519PubKey::from(&recipient), plaintext);
+        assert_ne!(sealed, sealed_again);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seal_with_seeded_rng() -> anyhow::Result<()> {
+        let recipient = X25519SecretKey::generate(&mut StdRng::seed_from_u64(7))?;
+        let recipient_again = X25519SecretKey::generate(&mut StdRng::seed_from_u64(7))?;
+        assert_eq!(
+            recipient.private_key_bytes(),
+            recipient_again.private_key_bytes()
+        );
+
+        let public = X25519PubKey::from(&recipient);
+        let sealed = seal_with(&mut StdRng::seed_from_u64(42), &public, b"hello");
+        let sealed_again = seal_with(&mut StdRng::seed_from_u64(42), &public, b"hello");
+        assert_eq!(sealed, sealed_again);
+        assert_eq!(unseal(&recipient, &sealed)?, b"hello");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_seal_with_ed25519_identity() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let identity = Ed25519KeyPair::generate(&mut rng)?;
+
+        let sealed = seal(&identity.public_key().to_x25519(), b"hello");
+        let unsealed = unseal(&identity.to_x25519_secret(), &sealed)?;
+
+        assert_eq!(unsealed, b"hello");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unseal_detects_tampering() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let recipient = X25519SecretKey::generate(&mut rng)?;
+        let other = X25519SecretKey::generate(&mut rng)?;
+
+        let sealed = seal(&X25519PubKey::from(&recipient), b"hello");
+
+        // Flipped ciphertext bit.
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            unseal(&recipient, &tampered),
+            Err(KeyError::DecryptionFailed)
+        ));
+
+        // Swapped ephemeral key.
+        let mut tampered = sealed.clone();
+        tampered[..X25519_KEY_LENGTH].copy_from_slice(&other.public_key_bytes());
+        assert!(unseal(&recipient, &tampered).is_err());
+
+        // Wrong recipient.
+        assert!(unseal(&other, &sealed).is_err());
+
+        // Truncated.
+        assert!(unseal(&recipient, &sealed[..SEAL_OVERHEAD - 1]).is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,241 @@
+use std::{fmt::Display, str::FromStr};
+
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
+use serde::{Deserialize, Serialize};
+
+use crate::{JwsAlgName, JwsAlgorithm, KeyError, KeyResult, Verify, WrappedPubKey};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// Represents a signature of any of the supported key types.
+///
+/// This is useful when the algorithm of a signature is only known at runtime. The string form is the
+/// JWS algorithm name followed by the base64url encoded signature, e.g. `EdDSA:<signature>`, so that
+/// signatures of the same length but different algorithms can be told apart.
+///
+/// Signature types supported:
+/// - `ed25519`
+/// - `NIST P-256`
+/// - `NIST P-384`
+/// - `secp256k1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WrappedSignature {
+    /// `ed25519` signature.
+    Ed25519(ed25519_dalek::Signature),
+
+    /// `NIST P-256` signature.
+    P256(p256::ecdsa::Signature),
+
+    /// `NIST P-384` signature.
+    P384(p384::ecdsa::Signature),
+
+    /// `secp256k1` signature.
+    Secp256k1(libsecp256k1::Signature),
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl WrappedSignature {
+    /// Creates a signature of the given algorithm from its raw bytes.
+    pub fn from_bytes(alg: JwsAlgorithm, bytes: &[u8]) -> KeyResult<Self> {
+        Ok(match alg {
+            JwsAlgorithm::EdDSA => Self::Ed25519(ed25519_dalek::Signature::from_slice(bytes)?),
+            JwsAlgorithm::ES256 => Self::P256(p256::ecdsa::Signature::try_from(bytes)?),
+            JwsAlgorithm::ES384 => Self::P384(p384::ecdsa::Signature::try_from(bytes)?),
+            JwsAlgorithm::ES256K => {
+                Self::Secp256k1(libsecp256k1::Signature::parse_standard_slice(bytes)?)
+            }
+        })
+    }
+
+    /// Returns the raw bytes of the signature.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match self {
+            Self::Ed25519(signature) => signature.to_bytes().to_vec(),
+            Self::P256(signature) => signature.to_bytes().to_vec(),
+            Self::P384(signature) => signature.to_bytes().to_vec(),
+            Self::Secp256k1(signature) => signature.serialize().to_vec(),
+        }
+    }
+
+    /// Returns `false` if the signature is a `secp256k1` signature in high-S form.
+    ///
+    /// Signatures of other algorithms are always considered low-S.
+    pub fn is_low_s(&self) -> bool {
+        match self {
+            Self::Secp256k1(signature) => !signature.s.is_high(),
+            _ => true,
+        }
+    }
+
+    /// Converts a `secp256k1` signature to its low-S form, which Bitcoin and EVM verifiers require.
+    ///
+    /// Signatures of other algorithms are left unchanged.
+    pub fn normalize_s(&mut self) {
+        if let Self::Secp256k1(signature) = self {
+            signature.normalize_s();
+        }
+    }
+
+    /// Verifies the signature of `msg` with the given public key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`KeyError::SignatureAlgorithmMismatch`] if the signature and the public key are of
+    /// different key types.
+    pub fn verify_with(&self, public_key: &WrappedPubKey, msg: &[u8]) -> KeyResult<()> {
+        if self.alg() != public_key.alg() {
+            return Err(KeyError::SignatureAlgorithmMismatch(
+                self.alg(),
+                public_key.alg(),
+            ));
+        }
+
+        public_key.verify(msg, &self.to_bytes())
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl JwsAlgName for WrappedSignature {
+    fn alg(&self) -> JwsAlgorithm {
+        match self {
+            Self::Ed25519(_) => JwsAlgorithm::EdDSA,
+            Self::P256(_) => JwsAlgorithm::ES256,
+            Self::P384(_) => JwsAlgorithm::ES384,
+            Self::Secp256k1(_) => JwsAlgorithm::ES256K,
+        }
+    }
+}
+
+impl Display for WrappedSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}",
+            self.alg(),
+            BASE64_URL_SAFE_NO_PAD.encode(self.to_bytes())
+        )
+    }
+}
+
+impl FromStr for WrappedSignature {
+    type Err = KeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (alg, signature) = s
+            .split_once(':')
+            .ok_or_else(|| KeyError::InvalidSignatureString(s.to_string()))?;
+
+        Self::from_bytes(alg.parse()?, &BASE64_URL_SAFE_NO_PAD.decode(signature)?)
+    }
+}
+
+impl Serialize for WrappedSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for WrappedSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Ed25519KeyPair, GetPublicKey, IntoOwned, KeyPairGenerate, P256KeyPair, P384KeyPair,
+        Secp256k1KeyPair, Sign, WrappedKeyPair,
+    };
+
+    use super::*;
+
+    fn check_roundtrip(key_pair: WrappedKeyPair, alg: JwsAlgorithm) -> anyhow::Result<()> {
+        let msg = b"hello world";
+        let signature = WrappedSignature::from_bytes(alg, &key_pair.sign(msg)?)?;
+        assert_eq!(signature.alg(), alg);
+
+        let public_key = key_pair.public_key();
+        signature.verify_with(&public_key, msg)?;
+        assert!(signature.verify_with(&public_key, b"other").is_err());
+
+        let encoded = signature.to_string();
+        assert!(encoded.starts_with(&format!("{alg}:")));
+        assert_eq!(WrappedSignature::from_str(&encoded)?, signature);
+
+        let serialized = serde_json::to_string(&signature)?;
+        assert_eq!(serialized, format!("\"{encoded}\""));
+        assert_eq!(
+            serde_json::from_str::<WrappedSignature>(&serialized)?,
+            signature
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wrapped_signature_ed25519() -> anyhow::Result<()> {
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        check_roundtrip(key_pair.into(), JwsAlgorithm::EdDSA)
+    }
+
+    #[test]
+    fn test_wrapped_signature_p256() -> anyhow::Result<()> {
+        let key_pair = P256KeyPair::generate(&mut rand::thread_rng())?;
+        check_roundtrip(key_pair.into(), JwsAlgorithm::ES256)
+    }
+
+    #[test]
+    fn test_wrapped_signature_p384() -> anyhow::Result<()> {
+        let key_pair = P384KeyPair::generate(&mut rand::thread_rng())?;
+        check_roundtrip(key_pair.into(), JwsAlgorithm::ES384)
+    }
+
+    #[test]
+    fn test_wrapped_signature_secp256k1() -> anyhow::Result<()> {
+        let key_pair = Secp256k1KeyPair::generate(&mut rand::thread_rng())?;
+        check_roundtrip(key_pair.into(), JwsAlgorithm::ES256K)
+    }
+
+    #[test]
+    fn test_wrapped_signature_algorithm_mismatch() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let ed25519 = Ed25519KeyPair::generate(&mut rng)?;
+        let p256 = P256KeyPair::generate(&mut rng)?;
+
+        // Both signatures are 64 bytes, so the algorithm has to come from the string form.
+        let signature = WrappedSignature::from_bytes(JwsAlgorithm::EdDSA, &ed25519.sign(b"hi")?)?;
+        let public_key = WrappedPubKey::from(p256.public_key().into_owned());
+        assert!(matches!(
+            signature.verify_with(&public_key, b"hi"),
+            Err(KeyError::SignatureAlgorithmMismatch(
+                JwsAlgorithm::EdDSA,
+                JwsAlgorithm::ES256
+            ))
+        ));
+
+        assert!(WrappedSignature::from_str("no-algorithm").is_err());
+        assert!(WrappedSignature::from_str("RS256:AAAA").is_err());
+
+        Ok(())
+    }
+}
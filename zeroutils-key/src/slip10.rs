@@ -0,0 +1,179 @@
+//! Hierarchical deterministic key derivation as specified in [SLIP-0010][ref].
+//!
+//! [ref]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use std::{fmt::Display, str::FromStr};
+
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::KeyError;
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The offset added to an index to make it a hardened index.
+pub const HARDENED_OFFSET: u32 = 1 << 31;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A [SLIP-0010][ref] derivation path like `m/44'/0'/0'`.
+///
+/// Hardened indices are written with a trailing `'` or `h`.
+///
+/// [ref]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct DerivationPath(Vec<u32>);
+
+/// A private key together with its chain code, both wiped from memory when dropped.
+pub(crate) struct ExtendedKey {
+    pub(crate) key: Zeroizing<[u8; 32]>,
+    pub(crate) chain_code: Zeroizing<[u8; 32]>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl DerivationPath {
+    /// Returns the path of the master key, `m`.
+    pub fn master() -> Self {
+        Self::default()
+    }
+
+    /// Returns the path extended with the non-hardened child `index`.
+    pub fn child(mut self, index: u32) -> Self {
+        self.0.push(index);
+        self
+    }
+
+    /// Returns the path extended with the hardened child `index`.
+    pub fn hardened_child(mut self, index: u32) -> Self {
+        self.0.push(index | HARDENED_OFFSET);
+        self
+    }
+
+    /// Returns the indices of the path. Hardened indices include the [`HARDENED_OFFSET`].
+    pub fn indices(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+impl ExtendedKey {
+    /// Derives the master key from a seed using the curve-specific HMAC key.
+    pub(crate) fn master(curve_key: &[u8], seed: &[u8]) -> Self {
+        Self::from_output(&hmac_sha512(curve_key, &[seed]))
+    }
+
+    /// Splits an HMAC-SHA512 output into the key and the chain code.
+    pub(crate) fn from_output(output: &[u8; 64]) -> Self {
+        let mut key = Zeroizing::new([0; 32]);
+        let mut chain_code = Zeroizing::new([0; 32]);
+        key.copy_from_slice(&output[..32]);
+        chain_code.copy_from_slice(&output[32..]);
+
+        Self { key, chain_code }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Computes the HMAC-SHA512 of the concatenated `parts` with the given key.
+pub(crate) fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> Zeroizing<[u8; 64]> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+
+    let mut output = Zeroizing::new([0; 64]);
+    output.copy_from_slice(&mac.finalize().into_bytes());
+    output
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl FromStr for DerivationPath {
+    type Err = KeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut segments = s.split('/');
+        if segments.next() != Some("m") {
+            return Err(KeyError::InvalidDerivationPath(s.to_string()));
+        }
+
+        segments
+            .map(|segment| {
+                let (index, hardened) = match segment.strip_suffix(['\'', 'h']) {
+                    Some(index) => (index, true),
+                    None => (segment, false),
+                };
+
+                match index.parse::<u32>() {
+                    Ok(index) if index < HARDENED_OFFSET => Ok(if hardened {
+                        index | HARDENED_OFFSET
+                    } else {
+                        index
+                    }),
+                    _ => Err(KeyError::InvalidDerivationPath(s.to_string())),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl Display for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if index & HARDENED_OFFSET != 0 {
+                write!(f, "/{}'", index & !HARDENED_OFFSET)?;
+            } else {
+                write!(f, "/{index}")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derivation_path_from_str() -> anyhow::Result<()> {
+        let path = DerivationPath::from_str("m/44'/0h/1")?;
+        assert_eq!(path.indices(), [44 | HARDENED_OFFSET, HARDENED_OFFSET, 1]);
+        assert_eq!(path.to_string(), "m/44'/0'/1");
+        assert_eq!(
+            path,
+            DerivationPath::master()
+                .hardened_child(44)
+                .hardened_child(0)
+                .child(1)
+        );
+
+        assert_eq!(DerivationPath::from_str("m")?, DerivationPath::master());
+
+        assert!(DerivationPath::from_str("").is_err());
+        assert!(DerivationPath::from_str("44'/0'").is_err());
+        assert!(DerivationPath::from_str("m/").is_err());
+        assert!(DerivationPath::from_str("m/x'").is_err());
+        assert!(DerivationPath::from_str("m/2147483648").is_err());
+
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use rand_core::CryptoRngCore;
+use rand_core::{CryptoRngCore, OsRng};
 
 use crate::{JwsAlgorithm, KeyResult};
 
@@ -42,6 +42,14 @@ pub trait KeyPairGenerate {
     where
         Self: Sized;
 
+    /// Generates a key pair from the operating system's random number generator.
+    fn random() -> KeyResult<Self>
+    where
+        Self: Sized,
+    {
+        Self::generate(&mut OsRng)
+    }
+
     /// Constructs a key pair from its private key bytes.
     fn from_private_key(bytes: &[u8]) -> KeyResult<Self>
     where
@@ -1,6 +1,26 @@
-use x25519_dalek::{PublicKey, SharedSecret};
+use std::{
+    borrow::Cow,
+    hash::{Hash, Hasher},
+};
 
-use crate::{AsymmetricKey, PubKey};
+use hkdf::Hkdf;
+use rand_core::CryptoRngCore;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+use x25519_dalek::{PublicKey, StaticSecret};
+use zeroize::Zeroizing;
+
+use crate::{
+    AsymmetricKey, ECDHKeyExchange, KeyError, KeyPairBytes, KeyPairGenerate, KeyResult, PubKey,
+    PublicKeyBytes, PublicKeyGenerate,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The length of an `x25519` key or shared secret in bytes.
+pub const X25519_KEY_LENGTH: usize = 32;
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -11,9 +31,214 @@ use crate::{AsymmetricKey, PubKey};
 /// [ref]: https://en.wikipedia.org/wiki/X25519
 pub type X25519PubKey<'a> = PubKey<'a, PublicKey>;
 
-/// An [`x25519`][ref] key pair with a shared secret.
+/// An [`x25519`][ref] key pair with a static secret.
 ///
 /// [ref]: https://en.wikipedia.org/wiki/X25519
-pub type X25519KeyPair<'a> = X25519Key<'a, SharedSecret>;
+pub type X25519KeyPair<'a> = X25519Key<'a, StaticSecret>;
+
+/// An [`x25519`][ref] secret key. The public key is always derived alongside it, so this is the same
+/// as [`X25519KeyPair`].
+///
+/// [ref]: https://en.wikipedia.org/wiki/X25519
+pub type X25519SecretKey<'a> = X25519KeyPair<'a>;
 
 pub(crate) type X25519Key<'a, S = ()> = AsymmetricKey<'a, PublicKey, S>;
+
+/// A 32-byte secret agreed upon by two parties through an `x25519` key exchange.
+///
+/// The raw secret is not uniformly random and should be passed through a key derivation function
+/// like [`derive_key`](Self::derive_key) before being used as an encryption key. The secret is
+/// zeroized on drop and compared in constant time.
+#[derive(Clone)]
+pub struct SharedSecret(Zeroizing<[u8; X25519_KEY_LENGTH]>);
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl X25519KeyPair<'_> {
+    /// Performs a Diffie-Hellman key agreement with the other party's public key.
+    pub fn diffie_hellman(&self, their_public: &X25519PubKey) -> SharedSecret {
+        SharedSecret(Zeroizing::new(
+            self.private.diffie_hellman(&their_public.public).to_bytes(),
+        ))
+    }
+}
+
+impl SharedSecret {
+    /// Returns the raw bytes of the shared secret.
+    pub fn to_bytes(&self) -> [u8; X25519_KEY_LENGTH] {
+        *self.0
+    }
+
+    /// Returns a reference to the raw bytes of the shared secret.
+    pub fn as_bytes(&self) -> &[u8; X25519_KEY_LENGTH] {
+        &self.0
+    }
+
+    /// Derives a 32-byte key from the shared secret using HKDF-SHA256 with the given `salt` and `info`.
+    pub fn derive_key(&self, salt: &[u8], info: &[u8]) -> [u8; X25519_KEY_LENGTH] {
+        let mut key = [0; X25519_KEY_LENGTH];
+        Hkdf::<Sha256>::new(Some(salt), self.0.as_slice())
+            .expand(info, &mut key)
+            .expect("32 bytes is a valid length for HKDF-SHA256 output");
+        key
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl PublicKeyGenerate for X25519PubKey<'_> {
+    fn from_public_key(bytes: &[u8]) -> KeyResult<Self> {
+        let bytes: [u8; X25519_KEY_LENGTH] = bytes
+            .try_into()
+            .map_err(|_| KeyError::InvalidKeyLength(bytes.len()))?;
+
+        Ok(Self {
+            public: Cow::Owned(PublicKey::from(bytes)),
+            private: (),
+        })
+    }
+}
+
+impl KeyPairGenerate for X25519KeyPair<'_> {
+    fn generate(rng: &mut impl CryptoRngCore) -> KeyResult<Self> {
+        let secret = StaticSecret::random_from_rng(rng);
+        Ok(Self {
+            public: Cow::Owned(PublicKey::from(&secret)),
+            private: secret,
+        })
+    }
+
+    fn from_private_key(bytes: &[u8]) -> KeyResult<Self> {
+        let bytes: [u8; X25519_KEY_LENGTH] = bytes
+            .try_into()
+            .map_err(|_| KeyError::InvalidKeyLength(bytes.len()))?;
+
+        let secret = StaticSecret::from(bytes);
+        Ok(Self {
+            public: Cow::Owned(PublicKey::from(&secret)),
+            private: secret,
+        })
+    }
+}
+
+impl ECDHKeyExchange for X25519KeyPair<'_> {
+    type SharedKey = SharedSecret;
+
+    fn exchange(&self, public_key: &[u8]) -> KeyResult<Self::SharedKey> {
+        let their_public = X25519PubKey::from_public_key(public_key)?;
+        Ok(self.diffie_hellman(&their_public))
+    }
+}
+
+impl<S> PublicKeyBytes for X25519Key<'_, S> {
+    fn public_key_bytes(&self) -> Vec<u8> {
+        self.public.as_bytes().to_vec()
+    }
+}
+
+impl KeyPairBytes for X25519KeyPair<'_> {
+    fn private_key_bytes(&self) -> Vec<u8> {
+        self.private.to_bytes().to_vec()
+    }
+}
+
+impl std::fmt::Debug for X25519KeyPair<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AsymmetricKey")
+            .field("public", &self.public)
+            .field("private", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl<'a> From<X25519KeyPair<'a>> for X25519PubKey<'a> {
+    fn from(key_pair: X25519KeyPair<'a>) -> Self {
+        Self {
+            public: key_pair.public,
+            private: (),
+        }
+    }
+}
+
+impl<'a, S> From<&'a X25519Key<'a, S>> for X25519PubKey<'a> {
+    fn from(key_pair: &'a X25519Key<'a, S>) -> Self {
+        Self {
+            public: Cow::Borrowed(&key_pair.public),
+            private: (),
+        }
+    }
+}
+
+impl Hash for X25519PubKey<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.public_key_bytes().hash(state);
+    }
+}
+
+impl PartialEq for SharedSecret {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.ct_eq(&*other.0).into()
+    }
+}
+
+impl Eq for SharedSecret {}
+
+impl std::fmt::Debug for SharedSecret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SharedSecret").field(&"[REDACTED]").finish()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_x25519_diffie_hellman() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let alice = X25519SecretKey::generate(&mut rng)?;
+        let bob = X25519SecretKey::generate(&mut rng)?;
+
+        let alice_secret = alice.diffie_hellman(&X25519PubKey::from(&bob));
+        let bob_secret = bob.diffie_hellman(&X25519PubKey::from(&alice));
+
+        assert_eq!(alice_secret, bob_secret);
+        assert_eq!(
+            alice_secret.derive_key(b"salt", b"info"),
+            bob_secret.derive_key(b"salt", b"info")
+        );
+
+        // Exchange using public key bytes.
+        let bob_secret = bob.exchange(&alice.public_key_bytes())?;
+        assert_eq!(alice_secret.to_bytes(), bob_secret.to_bytes());
+
+        // A third party does not derive the same secret.
+        let eve = X25519SecretKey::generate(&mut rng)?;
+        let eve_secret = eve.diffie_hellman(&X25519PubKey::from(&alice));
+        assert_ne!(alice_secret, eve_secret);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_x25519_from_private_key() -> anyhow::Result<()> {
+        let mut rng = rand::thread_rng();
+        let key_pair = X25519KeyPair::generate(&mut rng)?;
+
+        let restored = X25519KeyPair::from_private_key(&key_pair.private_key_bytes())?;
+        assert_eq!(key_pair.public_key_bytes(), restored.public_key_bytes());
+
+        assert!(X25519KeyPair::from_private_key(&[0; 31]).is_err());
+        assert!(X25519PubKey::from_public_key(&[0; 33]).is_err());
+
+        Ok(())
+    }
+}
@@ -1,3 +1,5 @@
+use std::{convert::Infallible, path::PathBuf};
+
 use thiserror::Error;
 
 //--------------------------------------------------------------------------------------------------
@@ -21,6 +23,10 @@ pub enum PathError {
     /// Out of bounds `..` in path.
     #[error("Out of bounds `..` in path")]
     OutOfBoundsParentDir,
+
+    /// A `std` path with a root or prefix component.
+    #[error("Absolute std path: {0:?}")]
+    AbsoluteStdPath(PathBuf),
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -32,3 +38,13 @@ pub enum PathError {
 pub fn Ok<T>(value: T) -> PathResult<T> {
     Result::Ok(value)
 }
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl From<Infallible> for PathError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
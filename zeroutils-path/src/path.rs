@@ -1,7 +1,7 @@
 use std::{
     convert::{TryFrom, TryInto},
     fmt::Display,
-    hash::Hash,
+    path::{Component, PathBuf},
     slice::SliceIndex,
     str::FromStr,
 };
@@ -62,6 +62,59 @@ impl Path {
         Ok(Self { segments })
     }
 
+    /// Creates a path from a relative [`std::path::Path`].
+    ///
+    /// `.`, `..` and named components map to their matching segments. Named components are kept as is,
+    /// so they don't have to be valid in the string form of a path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PathError::AbsoluteStdPath`] if the path has a root or prefix component, and
+    /// [`PathError::InvalidPathSegment`] if a component is not valid unicode.
+    pub fn try_from_std(path: &std::path::Path) -> PathResult<Self> {
+        let segments = path
+            .components()
+            .map(|component| match component {
+                Component::CurDir => Ok(PathSegment::CurrentDir),
+                Component::ParentDir => Ok(PathSegment::ParentDir),
+                Component::Normal(name) => name
+                    .to_str()
+                    .map(|name| PathSegment::Named(name.to_string()))
+                    .ok_or_else(|| {
+                        PathError::InvalidPathSegment(name.to_string_lossy().into_owned())
+                    }),
+                Component::RootDir | Component::Prefix(_) => {
+                    Err(PathError::AbsoluteStdPath(path.to_path_buf()))
+                }
+            })
+            .collect::<PathResult<Vec<_>>>()?;
+
+        Ok(Self { segments })
+    }
+
+    /// Converts the path to a relative [`PathBuf`], so it can be joined onto a directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PathError::InvalidPathSegment`] if a named segment is empty, is `.` or `..`, or contains
+    /// a path separator, since it would not stay a single named component in the `PathBuf`.
+    pub fn to_std_pathbuf(&self) -> PathResult<PathBuf> {
+        self.segments
+            .iter()
+            .map(|segment| match segment {
+                PathSegment::Named(name)
+                    if name.is_empty()
+                        || name == "."
+                        || name == ".."
+                        || name.chars().any(std::path::is_separator) =>
+                {
+                    Err(PathError::InvalidPathSegment(name.clone()))
+                }
+                segment => Ok(segment.as_str()),
+            })
+            .collect()
+    }
+
     /// Returns the segments of the path.
     pub fn get_segments(&self) -> &[PathSegment] {
         &self.segments
@@ -99,11 +152,128 @@ impl Path {
         })
     }
 
+    /// Canonicalizes the path as a relative path by removing all `.` and collapsing `..` where there is
+    /// a preceding named segment.
+    ///
+    /// Unlike [`canonicalize`][Self::canonicalize], `..` segments that go past the start of the path are
+    /// kept as leading `..` segments instead of being an error.
+    pub fn canonicalize_relative(&self) -> Self {
+        let mut resolved_segments = Vec::new();
+
+        for segment in self.segments.iter() {
+            match segment {
+                PathSegment::CurrentDir => {}
+                PathSegment::ParentDir => match resolved_segments.last() {
+                    Some(PathSegment::Named(_)) => {
+                        resolved_segments.pop();
+                    }
+                    _ => resolved_segments.push(PathSegment::ParentDir),
+                },
+                PathSegment::Named(name) => {
+                    resolved_segments.push(PathSegment::Named(name.clone()));
+                }
+            }
+        }
+
+        Self {
+            segments: resolved_segments,
+        }
+    }
+
+    /// Returns the minimal path that leads from `base` to this path, using `..` segments to leave
+    /// `base`.
+    ///
+    /// Both paths are canonicalized first, and segments are compared case-insensitively.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either path cannot be canonicalized.
+    pub fn relative_to(&self, base: &Path) -> PathResult<Path> {
+        let path = self.canonicalize()?;
+        let base = base.canonicalize()?;
+
+        let common = path
+            .segments
+            .iter()
+            .zip(&base.segments)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let segments = std::iter::repeat_n(PathSegment::ParentDir, base.segments.len() - common)
+            .chain(path.segments[common..].iter().cloned())
+            .collect();
+
+        Ok(Path { segments })
+    }
+
     /// Pushes a segment to the path.
     pub fn push(&mut self, segment: PathSegment) {
         self.segments.push(segment);
     }
 
+    /// Appends the segments of `other` to the path.
+    pub fn push_path(&mut self, other: &Path) {
+        self.segments.extend(other.segments.iter().cloned());
+    }
+
+    /// Returns a new path with `other` appended to this path.
+    ///
+    /// Paths are always relative to the same root, so joining with a path that starts with `/`
+    /// appends it too, instead of replacing the path like [`std::path::Path::join`] does.
+    pub fn join<T>(&self, other: T) -> PathResult<Path>
+    where
+        T: TryInto<Path>,
+        PathError: From<T::Error>,
+    {
+        let mut path = self.clone();
+        path.push_path(&other.try_into()?);
+        Ok(path)
+    }
+
+    /// Returns the remaining path after removing `base` from the start of the path, or `None` if `base`
+    /// is not a prefix of the path.
+    ///
+    /// Segments are compared case-insensitively.
+    pub fn strip_prefix(&self, base: &Path) -> Option<Path> {
+        self.segments
+            .strip_prefix(base.segments.as_slice())
+            .map(|segments| Path {
+                segments: segments.to_vec(),
+            })
+    }
+
+    /// Returns whether `prefix` is a prefix of the path.
+    ///
+    /// Segments are compared case-insensitively and the empty path is a prefix of every path.
+    pub fn starts_with(&self, prefix: &Path) -> bool {
+        self.segments.starts_with(&prefix.segments)
+    }
+
+    /// Returns whether `suffix` is a suffix of the path.
+    ///
+    /// Segments are compared case-insensitively and the empty path is a suffix of every path.
+    pub fn ends_with(&self, suffix: &Path) -> bool {
+        self.segments.ends_with(&suffix.segments)
+    }
+
+    /// Returns the path without its last segment, or `None` if the path is empty.
+    ///
+    /// Like [`std::path::Path::parent`], the parent of a single-segment path is the empty path.
+    pub fn parent(&self) -> Option<Path> {
+        let (_, segments) = self.segments.split_last()?;
+        Some(Path {
+            segments: segments.to_vec(),
+        })
+    }
+
+    /// Returns the last segment of the path if it is a named segment.
+    ///
+    /// Like [`std::path::Path::file_name`], this returns `None` for an empty path or a path ending in
+    /// `.` or `..`.
+    pub fn file_name(&self) -> Option<&PathSegment> {
+        self.segments.last().filter(|segment| segment.is_named())
+    }
+
     /// Pops a segment from the path.
     pub fn pop(&mut self) -> Option<PathSegment> {
         self.segments.pop()
@@ -139,7 +309,7 @@ impl Path {
     /// This method creates a borrowed view of the `Path`, allowing you to work with the segments
     /// of the path without taking ownership. This can be useful when you need a read-only
     /// view of the path.
-    pub fn as_slice(&self) -> PathSlice {
+    pub fn as_slice(&self) -> PathSlice<'_> {
         PathSlice {
             segments: &self.segments,
         }
@@ -157,14 +327,14 @@ impl Path {
     pub fn slice(
         &self,
         slice: impl SliceIndex<[PathSegment], Output = [PathSegment]>,
-    ) -> PathSlice {
+    ) -> PathSlice<'_> {
         PathSlice {
             segments: &self.segments[slice],
         }
     }
 }
 
-impl<'a> PathSlice<'a> {
+impl PathSlice<'_> {
     /// Returns the number of segments in the path.
     pub fn len(&self) -> usize {
         self.segments.len()
@@ -260,7 +430,7 @@ impl Display for Path {
 
 #[cfg(test)]
 mod tests {
-    use std::hash::DefaultHasher;
+    use std::hash::{DefaultHasher, Hash, Hasher};
 
     use super::*;
 
@@ -315,6 +485,210 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_path_canonicalize_relative() -> anyhow::Result<()> {
+        let path = Path::from_str("../a/..")?;
+        assert_eq!(path.canonicalize_relative(), Path::from_str("..")?);
+        assert!(path.canonicalize().is_err());
+
+        let path = Path::from_str("a/../..")?;
+        assert_eq!(path.canonicalize_relative(), Path::from_str("..")?);
+
+        let path = Path::from_str("../../a/b/../c")?;
+        assert_eq!(path.canonicalize_relative(), Path::from_str("../../a/c")?);
+
+        let path = Path::from_str("./a/./b/..")?;
+        assert_eq!(path.canonicalize_relative(), Path::from_str("a")?);
+
+        // Paths that can be canonicalized strictly give the same result.
+        let path = Path::from_str("a/b/../c/./d")?;
+        assert_eq!(path.canonicalize_relative(), path.canonicalize()?);
+
+        let path = Path::from_str("a/..")?;
+        assert!(path.canonicalize_relative().is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_join() -> anyhow::Result<()> {
+        let path = Path::from_str("/a/b")?;
+
+        assert_eq!(path.join("c/d")?, Path::from_str("/a/b/c/d")?);
+        assert_eq!(path.join("/c/d")?, Path::from_str("/a/b/c/d")?);
+        assert_eq!(path.join("C/D")?.to_string(), "/a/b/C/D");
+        assert_eq!(path.join(Path::from_str("/c")?)?, Path::from_str("/a/b/c")?);
+        assert_eq!(path.join("")?, path);
+
+        assert!(path.join("c/d-e").is_err());
+
+        let mut path = Path::from_str("/a")?;
+        path.push_path(&Path::from_str("/B/c")?);
+        assert_eq!(path, Path::from_str("/a/b/c")?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_strip_prefix() -> anyhow::Result<()> {
+        let path = Path::from_str("/mnt/data/a/b")?;
+
+        assert_eq!(
+            path.strip_prefix(&Path::from_str("/mnt/data")?),
+            Some(Path::from_str("/a/b")?)
+        );
+        assert_eq!(
+            path.strip_prefix(&Path::from_str("/MNT/Data")?),
+            Some(Path::from_str("/a/b")?)
+        );
+        assert_eq!(path.strip_prefix(&Path::from_str("/")?), Some(path.clone()));
+
+        // Equal paths leave an empty path.
+        let stripped = path.strip_prefix(&path).unwrap();
+        assert!(stripped.is_empty());
+        assert_eq!(stripped.to_string(), "/");
+
+        // Not a prefix
+        assert_eq!(path.strip_prefix(&Path::from_str("/mnt/dat")?), None);
+        assert_eq!(path.strip_prefix(&Path::from_str("/data")?), None);
+        assert_eq!(path.strip_prefix(&Path::from_str("/mnt/data/a/b/c")?), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_starts_with_and_ends_with() -> anyhow::Result<()> {
+        let path = Path::from_str("/mnt/data/a/b")?;
+        let empty = Path::from_str("/")?;
+
+        assert!(path.starts_with(&Path::from_str("/mnt/data")?));
+        assert!(path.starts_with(&Path::from_str("/MNT/Data")?));
+        assert!(path.starts_with(&path));
+        assert!(!path.starts_with(&Path::from_str("/mnt/dat")?));
+        assert!(!path.starts_with(&Path::from_str("/data")?));
+        assert!(!path.starts_with(&Path::from_str("/mnt/data/a/b/c")?));
+
+        assert!(path.ends_with(&Path::from_str("/a/b")?));
+        assert!(path.ends_with(&Path::from_str("/A/B")?));
+        assert!(path.ends_with(&path));
+        assert!(!path.ends_with(&Path::from_str("/a")?));
+        assert!(!path.ends_with(&Path::from_str("/x/mnt/data/a/b")?));
+
+        // The empty path is a prefix and suffix of every path.
+        assert!(path.starts_with(&empty));
+        assert!(path.ends_with(&empty));
+        assert!(empty.starts_with(&empty));
+        assert!(empty.ends_with(&empty));
+        assert!(!empty.starts_with(&path));
+        assert!(!empty.ends_with(&path));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_parent_and_file_name() -> anyhow::Result<()> {
+        let path = Path::from_str("/mnt/data/file")?;
+        assert_eq!(path.parent(), Some(Path::from_str("/mnt/data")?));
+        assert_eq!(path.file_name(), Some(&PathSegment::from_str("file")?));
+
+        let path = Path::from_str("/file")?;
+        let parent = path.parent().unwrap();
+        assert!(parent.is_empty());
+        assert_eq!(path.file_name(), Some(&PathSegment::from_str("file")?));
+
+        assert_eq!(parent.parent(), None);
+        assert_eq!(parent.file_name(), None);
+
+        let path = Path::from_str("/mnt/data/..")?;
+        assert_eq!(path.parent(), Some(Path::from_str("/mnt/data")?));
+        assert_eq!(path.file_name(), None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_std_conversions() -> anyhow::Result<()> {
+        let path = Path::try_from_iter([
+            PathSegment::Named("mnt".to_string()),
+            PathSegment::ParentDir,
+            PathSegment::Named("file.txt".to_string()),
+            PathSegment::Named("hello world".to_string()),
+        ])?;
+
+        let pathbuf = path.to_std_pathbuf()?;
+        assert!(pathbuf.is_relative());
+        assert_eq!(
+            pathbuf,
+            ["mnt", "..", "file.txt", "hello world"]
+                .iter()
+                .collect::<PathBuf>()
+        );
+        assert_eq!(Path::try_from_std(&pathbuf)?, path);
+
+        let path = Path::try_from_std(&PathBuf::from("./a/b"))?;
+        assert_eq!(path.first(), Some(&PathSegment::CurrentDir));
+        assert_eq!(path.to_std_pathbuf()?, PathBuf::from("./a/b"));
+
+        // The empty path
+        let empty = Path::from_str("/")?;
+        assert_eq!(empty.to_std_pathbuf()?, PathBuf::new());
+        assert_eq!(Path::try_from_std(&PathBuf::new())?, empty);
+
+        // Named segments that would not stay a single named component
+        for name in ["", ".", "..", "a/b"] {
+            let path = Path::try_from_iter([
+                PathSegment::Named("mnt".to_string()),
+                PathSegment::Named(name.to_string()),
+            ])?;
+            assert!(matches!(
+                path.to_std_pathbuf(),
+                Err(PathError::InvalidPathSegment(n)) if n == name
+            ));
+        }
+
+        // Absolute paths
+        let root = std::path::Path::new(std::path::MAIN_SEPARATOR_STR).join("a");
+        assert!(matches!(
+            Path::try_from_std(&root),
+            Err(PathError::AbsoluteStdPath(_))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_path_relative_to() -> anyhow::Result<()> {
+        let path = Path::from_str("/mnt/data/a")?;
+
+        // Sibling
+        let relative = path.relative_to(&Path::from_str("/mnt/data/b")?)?;
+        assert_eq!(relative.to_string(), "/../a");
+
+        // Ancestor and descendant
+        let relative = path.relative_to(&Path::from_str("/mnt")?)?;
+        assert_eq!(relative.to_string(), "/data/a");
+        let relative = Path::from_str("/mnt")?.relative_to(&path)?;
+        assert_eq!(relative.to_string(), "/../..");
+
+        // Diverging branches and the root
+        let relative = path.relative_to(&Path::from_str("/mnt/logs/x/y")?)?;
+        assert_eq!(relative.to_string(), "/../../../data/a");
+        assert_eq!(path.relative_to(&Path::from_str("/")?)?, path);
+
+        // Same path, ignoring case and non-canonical segments
+        let relative = path.relative_to(&Path::from_str("/MNT/x/../Data/a")?)?;
+        assert!(relative.is_empty());
+
+        // Joining the relative path onto the base leads back to the path.
+        let base = Path::from_str("/mnt/logs")?;
+        let relative = path.relative_to(&base)?;
+        assert_eq!(base.join(relative)?.canonicalize()?, path);
+
+        assert!(path.relative_to(&Path::from_str("/..")?).is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_path_display() -> anyhow::Result<()> {
         let path = Path::try_from_iter(vec!["0", "the", "quick", "brown", "fox"])?;
@@ -326,6 +700,43 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_path_percent_encoding() -> anyhow::Result<()> {
+        let path = Path::try_from_iter([
+            PathSegment::Named("a/b".to_string()),
+            PathSegment::Named("hello world".to_string()),
+            PathSegment::Named("file.txt".to_string()),
+            PathSegment::Named("..".to_string()),
+            PathSegment::Named("100%".to_string()),
+            PathSegment::Named("caf\u{e9}".to_string()),
+        ])?;
+
+        let displayed = path.to_string();
+        assert_eq!(
+            displayed,
+            "/a%2Fb/hello%20world/file%2Etxt/%2E%2E/100%25/caf%C3%A9"
+        );
+
+        let parsed = Path::from_str(&displayed)?;
+        assert_eq!(parsed.get_segments(), path.get_segments());
+        assert!(parsed.iter().all(PathSegment::is_named));
+        assert_eq!(parsed.get_segments()[0].as_str(), "a/b");
+
+        // Decoding is case-insensitive for hex digits.
+        assert_eq!(
+            PathSegment::from_str("a%2fb")?,
+            PathSegment::Named("a/b".to_string())
+        );
+
+        // Malformed escapes and invalid UTF-8
+        assert!(PathSegment::from_str("a%2").is_err());
+        assert!(PathSegment::from_str("a%zz").is_err());
+        assert!(PathSegment::from_str("%FF").is_err());
+        assert!(PathSegment::from_str("a b").is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_path_equality() -> anyhow::Result<()> {
         let base_path = Path::from_str("/0/the/quick/brown/fox")?;
@@ -341,27 +752,30 @@ mod tests {
     fn test_path_ordering() -> anyhow::Result<()> {
         let a = Path::from_str("/a/b/c")?;
         let b = Path::from_str("/a/b/d")?;
-        assert_eq!(a < b, true);
+        assert!(a < b);
 
         let a = Path::from_str("/a/b/c")?;
         let b = Path::from_str("/a/b/c/d")?;
-        assert_eq!(a < b, true);
+        assert!(a < b);
 
         let a = Path::from_str("/A/b/c")?;
         let b = Path::from_str("/a/b/c")?;
-        assert_eq!(a == b, true);
+        assert!(a == b);
 
         Ok(())
     }
 
     #[test]
     fn test_path_hash() -> anyhow::Result<()> {
+        let hash = |path: &Path| {
+            let mut hasher = DefaultHasher::new();
+            path.hash(&mut hasher);
+            hasher.finish()
+        };
+
         let a = Path::from_str("/a/b/c")?;
         let b = Path::from_str("/A/b/C")?;
-        assert_eq!(
-            a.hash(&mut DefaultHasher::new()),
-            b.hash(&mut DefaultHasher::new())
-        );
+        assert_eq!(hash(&a), hash(&b));
 
         Ok(())
     }
@@ -17,7 +17,8 @@ use crate::{PathError, PathResult};
 //--------------------------------------------------------------------------------------------------
 
 lazy_static! {
-    static ref RE_VALID_PATH_SEGMENT: Regex = Regex::new(r"^[a-zA-Z0-9]+$").unwrap();
+    static ref RE_VALID_PATH_SEGMENT: Regex =
+        Regex::new(r"^([a-zA-Z0-9]|%[0-9a-fA-F]{2})+$").unwrap();
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -30,6 +31,9 @@ lazy_static! {
 /// ## Important
 ///
 /// Path segments are case-insensitive, which affects their equality and hash implementations.
+///
+/// Named segments are displayed with every non-alphanumeric byte percent-encoded, so that segments
+/// containing `/`, `.` or spaces can be parsed back from their string form.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PathSegment {
     /// Represents the current directory, denoted by a single dot `.`.
@@ -47,7 +51,10 @@ pub enum PathSegment {
 //--------------------------------------------------------------------------------------------------
 
 impl PathSegment {
-    /// Validates a path segment.
+    /// Validates the string form of a path segment.
+    ///
+    /// Apart from `.` and `..`, a segment can only contain alphanumeric characters and percent-encoded
+    /// bytes.
     pub fn validate(segment: &str) -> PathResult<()> {
         if segment == "." || segment == ".." {
             return Ok(());
@@ -73,6 +80,18 @@ impl PathSegment {
         matches!(self, PathSegment::Named(_))
     }
 
+    /// Returns the key that equality, ordering and hashing are all based on.
+    ///
+    /// Named segments are compared by their lowercase name. `.` and `..` come before every named segment,
+    /// so a named segment is never equal to them, even if its name is `.` or `..`.
+    fn key(&self) -> (u8, String) {
+        match self {
+            PathSegment::CurrentDir => (0, String::new()),
+            PathSegment::ParentDir => (1, String::new()),
+            PathSegment::Named(segment) => (2, segment.to_lowercase()),
+        }
+    }
+
     /// Returns the path segment as a string.
     pub fn as_str(&self) -> &str {
         match self {
@@ -83,6 +102,35 @@ impl PathSegment {
     }
 }
 
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Decodes the percent-encoded bytes of a validated path segment.
+fn percent_decode(segment: &str) -> PathResult<String> {
+    let mut decoded = Vec::with_capacity(segment.len());
+    let mut bytes = segment.bytes();
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            decoded.push(byte);
+            continue;
+        }
+
+        let hex = [
+            bytes.next().unwrap_or_default(),
+            bytes.next().unwrap_or_default(),
+        ];
+        let byte = std::str::from_utf8(&hex)
+            .ok()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| PathError::InvalidPathSegment(segment.to_owned()))?;
+
+        decoded.push(byte);
+    }
+
+    String::from_utf8(decoded).map_err(|_| PathError::InvalidPathSegment(segment.to_owned()))
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
@@ -103,7 +151,7 @@ impl TryFrom<String> for PathSegment {
         match segment.as_str() {
             "." => Ok(PathSegment::CurrentDir),
             ".." => Ok(PathSegment::ParentDir),
-            _ => Ok(PathSegment::Named(segment)),
+            _ => Ok(PathSegment::Named(percent_decode(&segment)?)),
         }
     }
 }
@@ -121,19 +169,24 @@ impl Display for PathSegment {
         match self {
             PathSegment::CurrentDir => write!(f, "."),
             PathSegment::ParentDir => write!(f, ".."),
-            PathSegment::Named(segment) => write!(f, "{}", segment),
+            PathSegment::Named(segment) => {
+                for byte in segment.bytes() {
+                    if byte.is_ascii_alphanumeric() {
+                        write!(f, "{}", byte as char)?;
+                    } else {
+                        write!(f, "%{:02X}", byte)?;
+                    }
+                }
+
+                Ok(())
+            }
         }
     }
 }
 
 impl PartialEq for PathSegment {
     fn eq(&self, other: &Self) -> bool {
-        match (self.canonicalize(), other.canonicalize()) {
-            (PathSegment::CurrentDir, PathSegment::CurrentDir) => true,
-            (PathSegment::ParentDir, PathSegment::ParentDir) => true,
-            (PathSegment::Named(a), PathSegment::Named(b)) => a == b,
-            _ => false,
-        }
+        self.key() == other.key()
     }
 }
 
@@ -147,14 +200,58 @@ impl PartialOrd for PathSegment {
 
 impl Ord for PathSegment {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.canonicalize()
-            .as_str()
-            .cmp(other.canonicalize().as_str())
+        self.key().cmp(&other.key())
     }
 }
 
 impl Hash for PathSegment {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.canonicalize().as_str().hash(state)
+        self.key().hash(state)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::hash::DefaultHasher;
+
+    use super::*;
+
+    #[test]
+    fn test_path_segment_eq_ord_and_hash_agree() -> anyhow::Result<()> {
+        let hash = |segment: &PathSegment| {
+            let mut hasher = DefaultHasher::new();
+            segment.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let pairs = [
+            (PathSegment::Named("..".to_string()), PathSegment::ParentDir),
+            (PathSegment::Named(".".to_string()), PathSegment::CurrentDir),
+            (PathSegment::Named("".to_string()), PathSegment::CurrentDir),
+            (PathSegment::Named("".to_string()), PathSegment::ParentDir),
+        ];
+
+        for (named, dir) in pairs {
+            assert_ne!(named, dir);
+            assert_ne!(named.cmp(&dir), Ordering::Equal);
+            assert_ne!(hash(&named), hash(&dir));
+        }
+
+        // Named segments are still case-insensitive.
+        let a = PathSegment::from_str("File")?;
+        let b = PathSegment::from_str("fILE")?;
+        assert_eq!(a, b);
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_eq!(hash(&a), hash(&b));
+
+        // `.` and `..` sort before named segments.
+        assert!(PathSegment::CurrentDir < PathSegment::ParentDir);
+        assert!(PathSegment::ParentDir < PathSegment::Named(" ".to_string()));
+
+        Ok(())
     }
 }
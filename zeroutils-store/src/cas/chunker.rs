@@ -4,6 +4,28 @@ use tokio::io::AsyncRead;
 
 use super::StoreResult;
 
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// Statistics about the sizes of the chunks a chunker produced.
+///
+/// This is useful for tuning chunkers, especially content-defined ones whose chunk sizes vary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkStats {
+    /// The number of chunks.
+    count: usize,
+
+    /// The size of the smallest chunk.
+    min: usize,
+
+    /// The size of the largest chunk.
+    max: usize,
+
+    /// The total size of the chunks.
+    total: usize,
+}
+
 //--------------------------------------------------------------------------------------------------
 // Traits
 //--------------------------------------------------------------------------------------------------
@@ -18,6 +40,62 @@ pub trait Chunker {
         reader: impl AsyncRead + Send + 'a,
     ) -> impl Future<Output = StoreResult<BoxStream<'a, StoreResult<Bytes>>>> + Send;
 
+    /// Chunks bytes that are already in memory without going through a reader.
+    ///
+    /// Returns `None` if the chunker can only chunk streams, in which case [`chunk`](Self::chunk)
+    /// should be used instead. When `Some`, the chunks are the same as the ones `chunk` yields.
+    fn chunk_bytes(&self, data: Bytes) -> Option<Vec<Bytes>> {
+        let _ = data;
+        None
+    }
+
     /// Returns the allowed maximum chunk size. If there is no limit, `None` is returned.
     fn chunk_max_size(&self) -> Option<u64>;
 }
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl ChunkStats {
+    /// Records the size of another chunk.
+    pub fn record(&mut self, size: usize) {
+        self.min = if self.count == 0 {
+            size
+        } else {
+            self.min.min(size)
+        };
+        self.max = self.max.max(size);
+        self.count += 1;
+        self.total += size;
+    }
+
+    /// Returns the number of chunks.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the size of the smallest chunk, or `0` if there are no chunks.
+    pub fn min(&self) -> usize {
+        self.min
+    }
+
+    /// Returns the size of the largest chunk, or `0` if there are no chunks.
+    pub fn max(&self) -> usize {
+        self.max
+    }
+
+    /// Returns the total size of the chunks.
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Returns the mean size of the chunks, or `0.0` if there are no chunks.
+    pub fn mean(&self) -> f64 {
+        if self.count == 0 {
+            return 0.0;
+        }
+
+        self.total as f64 / self.count as f64
+    }
+}
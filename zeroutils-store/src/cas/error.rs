@@ -39,6 +39,11 @@ pub enum StoreError {
     #[error("Custom error: {0}")]
     Custom(#[from] AnyError),
 
+    /// A `PlaceholderStore` was used for an operation that needs a real store. Contains the name of
+    /// the operation.
+    #[error("Placeholder store cannot be used to read or write data: {0}")]
+    PlaceholderStoreUsed(&'static str),
+
     /// Layout error.
     #[error("Layout error: {0}")]
     LayoutError(#[from] LayoutError),
@@ -0,0 +1,26 @@
+//! Fixtures shared by the tests of the `cas` module.
+
+use libipld::Cid;
+use serde::{Deserialize, Serialize};
+
+use super::IpldReferences;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+#[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub(crate) struct Directory {
+    pub(crate) name: String,
+    pub(crate) entries: Vec<Cid>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl IpldReferences for Directory {
+    fn references<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Cid> + Send + 'a> {
+        Box::new(self.entries.iter())
+    }
+}
@@ -64,6 +64,21 @@ impl Chunker for FixedSizeChunker {
         Ok(Box::pin(s))
     }
 
+    fn chunk_bytes(&self, data: Bytes) -> Option<Vec<Bytes>> {
+        // Like the streaming path, a chunk size of zero yields no chunks.
+        if self.chunk_size == 0 {
+            return Some(vec![]);
+        }
+
+        let chunk_size = usize::try_from(self.chunk_size).unwrap_or(usize::MAX);
+        let chunks = (0..data.len())
+            .step_by(chunk_size)
+            .map(|start| data.slice(start..data.len().min(start.saturating_add(chunk_size))))
+            .collect();
+
+        Some(chunks)
+    }
+
     fn chunk_max_size(&self) -> Option<u64> {
         Some(self.chunk_size)
     }
@@ -107,4 +122,27 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_fixed_size_chunker_chunk_bytes() -> anyhow::Result<()> {
+        let data = Bytes::from_static(b"Lorem ipsum dolor sit amet, consectetur adipiscing elit.");
+
+        for chunk_size in [1, 10, 28, 56, 100] {
+            let chunker = FixedSizeChunker::new(chunk_size);
+            let streamed = chunker
+                .chunk(&data[..])
+                .await?
+                .collect::<Vec<_>>()
+                .await
+                .into_iter()
+                .collect::<StoreResult<Vec<_>>>()?;
+
+            assert_eq!(chunker.chunk_bytes(data.clone()), Some(streamed));
+        }
+
+        let chunker = FixedSizeChunker::new(10);
+        assert_eq!(chunker.chunk_bytes(Bytes::new()), Some(vec![]));
+
+        Ok(())
+    }
 }
@@ -16,6 +16,7 @@ use super::DEFAULT_CHUNK_MAX_SIZE;
 /// variable sizes. This algorithm is particularly effective for identifying duplicate content within
 /// files, as well as across different files, by creating consistent chunk boundaries. The resulting
 /// chunks are then processed and stored in an IPLD form.
+#[derive(Clone, Debug)]
 pub struct RabinChunker {
     /// The size of each chunk.
     chunk_size: u64,
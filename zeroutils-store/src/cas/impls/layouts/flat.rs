@@ -2,13 +2,13 @@ use std::{
     cmp::Ordering,
     io::{Error, ErrorKind, SeekFrom},
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
-use aliasable::boxed::AliasableBox;
 use async_stream::try_stream;
 use bytes::Bytes;
-use futures::{ready, stream::BoxStream, Future, StreamExt};
+use futures::{future, ready, stream::BoxStream, Future, StreamExt};
 use libipld::Cid;
 use tokio::io::{AsyncRead, AsyncSeek, ReadBuf};
 
@@ -35,8 +35,14 @@ use crate::cas::{
 /// │0 │1 │2 │  │3 │4 │5 │6 │7 │  │8 │9 │10│11│  │12│13│14│15│16│17│
 /// └──┴──┴──┘  └──┴──┴──┴──┴──┘  └──┴──┴──┴──┘  └──┴──┴──┴──┴──┴──┘
 /// ```
-#[derive(Clone, Debug, PartialEq, Default)]
-pub struct FlatLayout {}
+///
+/// Chunks are stored one at a time by default. [`FlatLayout::with_concurrency`] allows storing
+/// several chunks concurrently, which helps when the store is slower than the chunker.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FlatLayout {
+    /// The maximum number of chunks stored concurrently.
+    concurrency: usize,
+}
 
 /// A reader for the flat DAG layout.
 ///
@@ -64,7 +70,14 @@ pub struct FlatLayout {}
 ///                    │
 ///                Byte Cursor = 5
 /// ```
-pub struct FlatLayoutReader<S>
+///
+/// ## Soundness
+///
+/// The reader does not hold a future that borrows from itself. The store is kept behind an [`Arc`]
+/// and every pending block fetch is an `async move` block that owns a clone of that `Arc`, the `Cid`
+/// of the chunk and the offset into it. The future therefore only borrows data that lives for `'a`,
+/// the lifetime of the store, and the reader can be moved freely without any `unsafe` code.
+pub struct FlatLayoutReader<'a, S>
 where
     S: IpldStore,
 {
@@ -77,27 +90,14 @@ where
     /// The distance (in bytes) of the current chunk index from the start.
     chunk_distance: u64,
 
-    /// A function to get a raw block.
-    ///
-    /// ## Important
-    ///
-    /// Holds a reference to other fields in this struct. Declared first to ensure it is dropped
-    /// before the other fields.
-    get_raw_block_fn: Pin<Box<dyn Future<Output = StoreResult<Bytes>> + Send + Sync + 'static>>,
+    /// A future that resolves to the bytes of the current chunk starting at the byte cursor.
+    get_raw_block_fn: Pin<Box<dyn Future<Output = StoreResult<Bytes>> + Send + Sync + 'a>>,
 
     /// The store associated with the reader.
-    ///
-    /// ## Warning
-    ///
-    /// Field must not be moved as it is referenced by `get_raw_block_fn`.
-    store: AliasableBox<S>,
+    store: Arc<S>,
 
     /// The node that the reader is reading from.
-    ///
-    /// ## Warning
-    ///
-    /// Field must not be moved as it is referenced by `get_raw_block_fn`.
-    node: AliasableBox<MerkleNode>,
+    node: MerkleNode,
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -107,86 +107,71 @@ where
 impl FlatLayout {
     /// Create a new flat DAG layout.
     pub fn new() -> Self {
-        FlatLayout {}
+        FlatLayout { concurrency: 1 }
+    }
+
+    /// Create a new flat DAG layout that stores up to `concurrency` chunks at the same time.
+    ///
+    /// The children of the resulting merkle node are in the same order as the chunks regardless of
+    /// the concurrency. A concurrency of `0` is treated as `1`.
+    pub fn with_concurrency(concurrency: usize) -> Self {
+        FlatLayout {
+            concurrency: concurrency.max(1),
+        }
     }
 }
 
-impl<S> FlatLayoutReader<S>
+impl<'a, S> FlatLayoutReader<'a, S>
 where
-    S: IpldStore + Sync,
+    S: IpldStore + Send + Sync + 'a,
 {
     /// Create a new flat DAG reader.
+    ///
+    /// A node without children represents empty data, and the reader returns EOF right away.
     fn new(node: MerkleNode, store: S) -> StoreResult<Self> {
-        // Store node and store in the heap and make them aliasable.
-        let node = AliasableBox::from_unique(Box::new(node));
-        let store = AliasableBox::from_unique(Box::new(store));
-
-        // Create future to get the first node child.
-        let get_raw_block_fn: Pin<Box<dyn Future<Output = StoreResult<Bytes>> + Send + Sync>> =
-            Box::pin(
-                store.get_raw_block(
-                    node.children
-                        .first()
-                        .map(|(cid, _)| cid)
-                        .ok_or(StoreError::from(LayoutError::NoLeafBlock))?,
-                ),
-            );
-
-        // Unsafe magic to escape Rust ownership grip.
-        let get_raw_block_fn: Pin<
-            Box<dyn Future<Output = StoreResult<Bytes>> + Send + Sync + 'static>,
-        > = unsafe { std::mem::transmute(get_raw_block_fn) };
-
-        Ok(FlatLayoutReader {
+        let mut reader = FlatLayoutReader {
             byte_cursor: 0,
             chunk_index: 0,
             chunk_distance: 0,
-            get_raw_block_fn,
+            get_raw_block_fn: Box::pin(async { Ok(Bytes::new()) }),
+            store: Arc::new(store),
             node,
-            store,
-        })
+        };
+
+        // Create future to get the first node child, if there is one.
+        reader.seek_update(0)?;
+
+        Ok(reader)
     }
 
     fn fix_future(&mut self) {
+        // Everything the future needs is moved into it, so it does not borrow from `self`.
+        let store = Arc::clone(&self.store);
+        let cid = self
+            .node
+            .children
+            .get(self.chunk_index as usize)
+            .map(|(cid, _)| *cid);
+        let offset = (self.byte_cursor - self.chunk_distance) as usize;
+
         // Create future to get the next child.
-        let get_raw_block_fn: Pin<Box<dyn Future<Output = StoreResult<Bytes>> + Send + Sync>> =
-            Box::pin(async {
-                let bytes = self
-                    .store
-                    .get_raw_block(
-                        self.node
-                            .children
-                            .get(self.chunk_index as usize)
-                            .map(|(cid, _)| cid)
-                            .ok_or(StoreError::from(LayoutError::NoLeafBlock))?,
-                    )
-                    .await?;
-
-                // We just need bytes starting from byte cursor.
-                let bytes = Bytes::copy_from_slice(
-                    &bytes[(self.byte_cursor - self.chunk_distance) as usize..],
-                );
-
-                Ok(bytes)
-            });
-
-        // Unsafe magic to escape Rust ownership grip.
-        let get_raw_block_fn: Pin<
-            Box<dyn Future<Output = StoreResult<Bytes>> + Send + Sync + 'static>,
-        > = unsafe { std::mem::transmute(get_raw_block_fn) };
-
-        // Update type's future.
-        self.get_raw_block_fn = get_raw_block_fn;
+        self.get_raw_block_fn = Box::pin(async move {
+            let cid = cid.ok_or(StoreError::from(LayoutError::NoLeafBlock))?;
+            let bytes = store.get_raw_block(&cid).await?;
+
+            // We just need bytes starting from byte cursor. Slicing shares the block's buffer
+            // instead of copying it.
+            Ok(bytes.slice(offset..))
+        });
     }
 
-    fn read_update(&mut self, left_over: &[u8], consumed: u64) -> StoreResult<()> {
+    fn read_update(&mut self, left_over: Bytes, consumed: u64) -> StoreResult<()> {
         // Update the byte cursor.
         self.byte_cursor += consumed;
 
         // If there's left over bytes, we create a future to return the left over bytes.
         if !left_over.is_empty() {
-            let bytes = Bytes::copy_from_slice(left_over);
-            let get_raw_block_fn = Box::pin(async { Ok(bytes) });
+            let get_raw_block_fn = Box::pin(async { Ok(left_over) });
             self.get_raw_block_fn = get_raw_block_fn;
             return Ok(());
         }
@@ -236,8 +221,8 @@ where
                     continue;
                 }
                 Ordering::Greater => {
-                    self.chunk_distance -= self.node.children[self.chunk_index as usize].1 as u64;
                     self.chunk_index -= 1;
+                    self.chunk_distance -= self.node.children[self.chunk_index as usize].1 as u64;
 
                     continue;
                 }
@@ -256,17 +241,37 @@ where
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
 
+impl Default for FlatLayout {
+    fn default() -> Self {
+        FlatLayout::new()
+    }
+}
+
 impl Layout for FlatLayout {
     async fn organize<'a>(
         &self,
-        mut stream: BoxStream<'a, StoreResult<Bytes>>,
+        stream: BoxStream<'a, StoreResult<Bytes>>,
         store: impl IpldStore + Send + 'a,
     ) -> StoreResult<BoxStream<'a, StoreResult<Cid>>> {
+        let chunk_store = store.clone();
+        let mut cids = stream
+            .take_while(|chunk| future::ready(chunk.is_ok()))
+            .filter_map(|chunk| future::ready(chunk.ok()))
+            .map(move |chunk| {
+                let store = chunk_store.clone();
+                async move {
+                    let len = chunk.len();
+                    let cid = store.put_raw_block(chunk).await?;
+                    Ok::<_, StoreError>((cid, len))
+                }
+            })
+            // `buffered` yields in the order of the chunks, not the order the stores complete in.
+            .buffered(self.concurrency.max(1));
+
         let s = try_stream! {
             let mut children = Vec::new();
-            while let Some(Ok(chunk)) = stream.next().await {
-                let len = chunk.len();
-                let cid = store.put_raw_block(chunk).await?;
+            while let Some(result) = cids.next().await {
+                let (cid, len) = result?;
                 children.push((cid, len));
                 yield cid;
             }
@@ -303,9 +308,9 @@ impl LayoutSeekable for FlatLayout {
     }
 }
 
-impl<S> AsyncRead for FlatLayoutReader<S>
+impl<'a, S> AsyncRead for FlatLayoutReader<'a, S>
 where
-    S: IpldStore + Sync,
+    S: IpldStore + Send + Sync + 'a,
 {
     fn poll_read(
         mut self: Pin<&mut Self>,
@@ -317,28 +322,38 @@ where
             .map_err(|e| Error::new(ErrorKind::Other, e))?;
 
         // If the bytes is longer than the buffer, we only take the amount that fits.
-        let (taken, left_over) = if bytes.len() > buf.remaining() {
-            bytes.split_at(buf.remaining())
-        } else {
-            (&bytes[..], &[][..])
-        };
+        let taken = bytes.len().min(buf.remaining());
 
         // Copy the slice to the buffer.
-        buf.put_slice(taken);
+        buf.put_slice(&bytes[..taken]);
 
-        // Update the reader's state.
-        self.read_update(left_over, taken.len() as u64)
+        // Update the reader's state. The left over bytes share the buffer of `bytes`.
+        self.read_update(bytes.slice(taken..), taken as u64)
             .map_err(|e| Error::new(ErrorKind::Other, e))?;
 
         Poll::Ready(Ok(()))
     }
 }
 
-impl<S> AsyncSeek for FlatLayoutReader<S>
+impl<'a, S> AsyncSeek for FlatLayoutReader<'a, S>
 where
-    S: IpldStore + Sync,
+    S: IpldStore + Send + Sync + 'a,
 {
     fn start_seek(mut self: Pin<&mut Self>, position: SeekFrom) -> std::io::Result<()> {
+        // Empty data has no bytes to seek to, but seeking to the start is still valid.
+        if self.node.size == 0 {
+            if position != SeekFrom::Start(0) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    "Seek position out of bounds of empty data",
+                ));
+            }
+
+            return self
+                .seek_update(0)
+                .map_err(|e| Error::new(ErrorKind::Other, e));
+        }
+
         let byte_cursor = match position {
             SeekFrom::Start(offset) => {
                 if offset >= self.node.size as u64 {
@@ -437,6 +452,97 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_flat_dag_layout_organize_with_concurrency() -> anyhow::Result<()> {
+        let mut outputs = Vec::new();
+        for concurrency in [1, 8] {
+            let store = MemoryStore::default();
+            let (data, _, chunk_stream) = fixtures::data_and_chunk_stream();
+
+            let layout = FlatLayout::with_concurrency(concurrency);
+            let cid_stream = layout.organize(chunk_stream, store.clone()).await?;
+            let cids = cid_stream.try_collect::<Vec<_>>().await?;
+
+            let mut reader = layout.retrieve(cids.last().unwrap(), store).await?;
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            assert_eq!(bytes, data);
+
+            outputs.push(cids);
+        }
+
+        assert_eq!(outputs[0].len(), 11);
+        assert_eq!(outputs[0], outputs[1]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flat_dag_layout_empty_data() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+
+        let cid = store.put_bytes(&[][..]).await?;
+        let node = store.get_node::<MerkleNode>(&cid).await?;
+        assert_eq!(node.size, 0);
+        assert!(node.children.is_empty());
+
+        let layout = FlatLayout::default();
+        let mut reader = layout.retrieve(&cid, store.clone()).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        assert!(bytes.is_empty());
+
+        let mut reader = layout.retrieve_seekable(&cid, store).await?;
+        assert_eq!(reader.seek(SeekFrom::Start(0)).await?, 0);
+        assert!(reader.seek(SeekFrom::Start(1)).await.is_err());
+        assert!(reader.seek(SeekFrom::Current(1)).await.is_err());
+        assert!(reader.seek(SeekFrom::End(0)).await.is_err());
+
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        assert!(bytes.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_flat_dag_layout_retrieve_with_buffer_sizes() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+        let (data, _, chunk_stream) = fixtures::data_and_chunk_stream();
+
+        let layout = FlatLayout::default();
+        let cid_stream = layout.organize(chunk_stream, store.clone()).await?;
+        let cids = cid_stream.try_collect::<Vec<_>>().await?;
+        let cid = cids.last().unwrap();
+
+        // Buffers smaller than, equal to, not aligned with, and larger than the chunks.
+        for size in [1, 3, 5, 7, 64] {
+            let mut reader = layout.retrieve(cid, store.clone()).await?;
+            let mut bytes = Vec::<u8>::with_capacity(data.len());
+            let mut buf = vec![0; size];
+            loop {
+                let filled = reader.read(&mut buf).await?;
+                if filled == 0 {
+                    break;
+                }
+
+                bytes.extend(&buf[..filled]);
+            }
+
+            assert_eq!(bytes, data, "buffer size {size}");
+        }
+
+        // Left over bytes after seeking into the middle of a chunk
+        let mut reader = layout.retrieve_seekable(cid, store).await?;
+        reader.seek(SeekFrom::Start(7)).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        assert_eq!(bytes, data[7..]);
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_flat_dag_layout_seek() -> anyhow::Result<()> {
         let store = MemoryStore::default();
@@ -506,6 +612,38 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_flat_dag_layout_seek_from_end_after_eof() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+        let (data, chunks, chunk_stream) = fixtures::data_and_chunk_stream();
+
+        // The last chunk is shorter than the one before it.
+        assert!(chunks[9].len() < chunks[8].len());
+
+        let layout = FlatLayout::default();
+        let cid_stream = layout.organize(chunk_stream, store.clone()).await?;
+        let cids = cid_stream.try_collect::<Vec<_>>().await?;
+        let cid = cids.last().unwrap();
+
+        let mut reader = layout.retrieve_seekable(cid, store).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        assert_eq!(bytes, data);
+
+        // Seek back into the last chunk, to its start, into the chunk before it and to the start of
+        // the data, each time after reading to EOF.
+        for n in [3, 5, 8, data.len()] {
+            reader.seek(SeekFrom::End(-(n as i64))).await?;
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+
+            assert_eq!(bytes, data[data.len() - n..], "seek from end {n}");
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
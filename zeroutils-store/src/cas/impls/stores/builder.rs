@@ -0,0 +1,137 @@
+use crate::cas::{
+    Chunker, FixedSizeChunker, FlatLayout, Layout, MemoryStore, NullStore,
+    DEFAULT_NODE_BLOCK_MAX_SIZE,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A builder for stores that chunk and organize data, like [`MemoryStore`] and [`NullStore`].
+///
+/// The chunker and layout types follow from the values they are set to, so they don't have to be
+/// spelled out at the call site.
+#[derive(Debug, Clone)]
+pub struct StoreBuilder<C = FixedSizeChunker, L = FlatLayout> {
+    chunker: C,
+    layout: L,
+    node_block_max_size: Option<u64>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl StoreBuilder {
+    /// Creates a builder with the default chunker and layout.
+    ///
+    /// Node blocks are limited to [`DEFAULT_NODE_BLOCK_MAX_SIZE`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<C, L> StoreBuilder<C, L> {
+    /// Sets the chunking algorithm used to split data into chunks.
+    pub fn chunker<D>(self, chunker: D) -> StoreBuilder<D, L>
+    where
+        D: Chunker,
+    {
+        StoreBuilder {
+            chunker,
+            layout: self.layout,
+            node_block_max_size: self.node_block_max_size,
+        }
+    }
+
+    /// Sets the layout strategy used to organize chunked data.
+    pub fn layout<M>(self, layout: M) -> StoreBuilder<C, M>
+    where
+        M: Layout,
+    {
+        StoreBuilder {
+            chunker: self.chunker,
+            layout,
+            node_block_max_size: self.node_block_max_size,
+        }
+    }
+
+    /// Sets the maximum size of a node block, or removes the limit with `None`.
+    pub fn node_block_max_size(mut self, node_block_max_size: Option<u64>) -> Self {
+        self.node_block_max_size = node_block_max_size;
+        self
+    }
+}
+
+impl<C, L> StoreBuilder<C, L>
+where
+    C: Chunker,
+    L: Layout,
+{
+    /// Builds a [`MemoryStore`].
+    pub fn build_memory_store(self) -> MemoryStore<C, L> {
+        MemoryStore::new(self.chunker, self.layout)
+            .with_node_block_max_size(self.node_block_max_size)
+    }
+
+    /// Builds a [`NullStore`].
+    pub fn build_null_store(self) -> NullStore<C, L> {
+        NullStore::new(self.chunker, self.layout).with_node_block_max_size(self.node_block_max_size)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl Default for StoreBuilder {
+    fn default() -> Self {
+        StoreBuilder {
+            chunker: FixedSizeChunker::default(),
+            layout: FlatLayout::default(),
+            node_block_max_size: Some(DEFAULT_NODE_BLOCK_MAX_SIZE),
+        }
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::cas::{IpldStore, IpldStoreExt};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_store_builder() -> anyhow::Result<()> {
+        let builder = StoreBuilder::new()
+            .chunker(FixedSizeChunker::new(16))
+            .layout(FlatLayout::new())
+            .node_block_max_size(Some(1024));
+
+        let store = builder.clone().build_memory_store();
+        assert_eq!(store.get_raw_block_max_size(), Some(16));
+        assert_eq!(store.get_node_block_max_size(), Some(1024));
+
+        let data = (0..100).collect::<Vec<u8>>();
+        let (cid, stats) = store.put_bytes_with_stats(&data[..]).await?;
+        assert_eq!(store.read_all(&cid).await?, data);
+        assert_eq!(stats.count(), 7);
+        assert_eq!(stats.max(), 16);
+
+        // A null store built the same way computes the same `Cid`s.
+        let null_store = builder.build_null_store();
+        assert_eq!(null_store.put_bytes(&data[..]).await?, cid);
+
+        // The defaults match the default store.
+        let store = StoreBuilder::new().build_memory_store();
+        assert_eq!(
+            store.put_bytes(&data[..]).await?,
+            MemoryStore::default().put_bytes(&data[..]).await?
+        );
+
+        Ok(())
+    }
+}
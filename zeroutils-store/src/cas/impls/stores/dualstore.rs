@@ -5,7 +5,9 @@ use libipld::Cid;
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::io::AsyncRead;
 
-use crate::cas::{Codec, IpldReferences, IpldStore, StoreError, StoreResult};
+use crate::cas::{
+    Codec, IpldReferences, IpldStore, IpldStoreSeekable, SeekableReader, StoreError, StoreResult,
+};
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -132,6 +134,24 @@ where
     }
 }
 
+impl<A, B> DualStore<A, B>
+where
+    A: IpldStoreSeekable,
+    B: IpldStoreSeekable,
+{
+    /// Gets a seekable reader for the bytes stored in a chosen store by its `Cid`.
+    pub async fn get_seekable_bytes_from<'a>(
+        &'a self,
+        cid: &'a Cid,
+        choice: Choice,
+    ) -> StoreResult<Pin<Box<dyn SeekableReader + Send + 'a>>> {
+        match choice {
+            Choice::A => self.store_a.get_seekable_bytes(cid).await,
+            Choice::B => self.store_b.get_seekable_bytes(cid).await,
+        }
+    }
+}
+
 impl Choice {
     /// Returns the other choice.
     pub fn other(&self) -> Self {
@@ -162,6 +182,16 @@ where
         self.put_bytes_into(bytes, self.config.default).await
     }
 
+    async fn cid_of_bytes<'a>(
+        &'a self,
+        bytes: impl AsyncRead + Send + Sync + 'a,
+    ) -> StoreResult<Cid> {
+        match self.config.default {
+            Choice::A => self.store_a.cid_of_bytes(bytes).await,
+            Choice::B => self.store_b.cid_of_bytes(bytes).await,
+        }
+    }
+
     async fn put_raw_block(&self, bytes: impl Into<Bytes> + Send) -> StoreResult<Cid> {
         self.put_raw_block_into(bytes, self.config.default).await
     }
@@ -233,6 +263,26 @@ where
     }
 }
 
+impl<A, B> IpldStoreSeekable for DualStore<A, B>
+where
+    A: IpldStoreSeekable + Sync,
+    B: IpldStoreSeekable + Sync,
+{
+    async fn get_seekable_bytes<'a>(
+        &'a self,
+        cid: &'a Cid,
+    ) -> StoreResult<Pin<Box<dyn SeekableReader + Send + 'a>>> {
+        match self.get_seekable_bytes_from(cid, self.config.default).await {
+            Ok(reader) => Ok(reader),
+            Err(StoreError::BlockNotFound(_)) => {
+                let choice = self.config.default.other();
+                self.get_seekable_bytes_from(cid, choice).await
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
 impl Default for DualStoreConfig {
     fn default() -> Self {
         Self { default: Choice::A }
@@ -245,7 +295,11 @@ impl Default for DualStoreConfig {
 
 #[cfg(test)]
 mod tests {
-    use crate::cas::MemoryStore;
+    use std::io::SeekFrom;
+
+    use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+    use crate::cas::{FixedSizeChunker, FlatLayout, MemoryStore};
 
     use super::*;
 
@@ -267,4 +321,41 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_dual_store_get_seekable_bytes() -> anyhow::Result<()> {
+        let store_a = MemoryStore::new(FixedSizeChunker::new(4), FlatLayout::new());
+        let store_b = MemoryStore::new(FixedSizeChunker::new(4), FlatLayout::new());
+        let dual_store = DualStore::new(store_a, store_b, Default::default());
+
+        let near = b"served from the near store";
+        let far = b"served from the far store";
+        let cid_near = dual_store.put_bytes_into(&near[..], Choice::A).await?;
+        let cid_far = dual_store.put_bytes_into(&far[..], Choice::B).await?;
+
+        for (cid, data) in [(cid_near, &near[..]), (cid_far, &far[..])] {
+            let mut reader = dual_store.get_seekable_bytes(&cid).await?;
+
+            // Seek into the middle of a chunk
+            assert_eq!(reader.seek(SeekFrom::Start(7)).await?, 7);
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf).await?;
+            assert_eq!(buf, &data[7..]);
+
+            // Seek back from the end
+            assert_eq!(reader.seek(SeekFrom::End(-5)).await?, data.len() as u64 - 5);
+            let mut buf = Vec::new();
+            reader.read_to_end(&mut buf).await?;
+            assert_eq!(buf, &data[data.len() - 5..]);
+        }
+
+        assert!(matches!(
+            dual_store
+                .get_seekable_bytes_from(&cid_far, Choice::A)
+                .await,
+            Err(StoreError::BlockNotFound(_))
+        ));
+
+        Ok(())
+    }
 }
@@ -1,18 +1,19 @@
 use std::{
     collections::{HashMap, HashSet},
     pin::Pin,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use bytes::Bytes;
-use futures::StreamExt;
+use futures::{stream::BoxStream, StreamExt};
 use libipld::Cid;
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::{io::AsyncRead, sync::RwLock};
 
 use crate::cas::{
-    utils, Chunker, Codec, FixedSizeChunker, FlatLayout, IpldReferences, IpldStore,
+    utils, ChunkStats, Chunker, Codec, FixedSizeChunker, FlatLayout, IpldReferences, IpldStore,
     IpldStoreSeekable, Layout, LayoutSeekable, SeekableReader, StoreError, StoreResult,
+    DEFAULT_NODE_BLOCK_MAX_SIZE,
 };
 
 //--------------------------------------------------------------------------------------------------
@@ -43,6 +44,9 @@ where
 
     /// The layout strategy used to store chunked data.
     layout: L,
+
+    /// The maximum size of a node block. Raw blocks are limited by the chunk size instead.
+    node_block_max_size: Option<u64>,
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -55,14 +59,23 @@ where
     L: Layout,
 {
     /// Creates a new `MemoryStore` with the given `chunker` and `layout`.
+    ///
+    /// Node blocks are limited to [`DEFAULT_NODE_BLOCK_MAX_SIZE`].
     pub fn new(chunker: C, layout: L) -> Self {
         MemoryStore {
             blocks: Arc::new(RwLock::new(HashMap::new())),
             chunker,
             layout,
+            node_block_max_size: Some(DEFAULT_NODE_BLOCK_MAX_SIZE),
         }
     }
 
+    /// Sets the maximum size of a node block, or removes the limit with `None`.
+    pub fn with_node_block_max_size(mut self, node_block_max_size: Option<u64>) -> Self {
+        self.node_block_max_size = node_block_max_size;
+        self
+    }
+
     /// Prints all the blocks in the store.
     // TODO: Probably change to display implementation with tokio spawn.
     pub async fn print(&self) {
@@ -81,6 +94,70 @@ where
         }
     }
 
+    /// Saves the bytes of `reader` to the store like [`put_bytes`](IpldStore::put_bytes) and also
+    /// returns statistics about the sizes of the chunks the chunker produced.
+    pub async fn put_bytes_with_stats<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+    ) -> StoreResult<(Cid, ChunkStats)>
+    where
+        C: Chunker + Clone + Send + Sync,
+        L: Layout + Clone + Send + Sync,
+    {
+        let stats = Arc::new(Mutex::new(ChunkStats::default()));
+        let recorder = Arc::clone(&stats);
+        let chunk_stream = self
+            .chunker
+            .chunk(reader)
+            .await?
+            .inspect(move |chunk| {
+                if let Ok(chunk) = chunk {
+                    recorder.lock().unwrap().record(chunk.len());
+                }
+            })
+            .boxed();
+
+        let cid = self.organize_chunks(chunk_stream, self.clone()).await?;
+        let stats = stats.lock().unwrap().clone();
+
+        Ok((cid, stats))
+    }
+
+    /// Chunks the bytes of `reader` and organizes them into `store`, returning the root `Cid`.
+    async fn organize_bytes<'a>(
+        &self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+        store: Self,
+    ) -> StoreResult<Cid>
+    where
+        C: Chunker + Clone + Send + Sync,
+        L: Layout + Clone + Send + Sync,
+    {
+        let chunk_stream = self.chunker.chunk(reader).await?;
+        self.organize_chunks(chunk_stream, store).await
+    }
+
+    /// Organizes `chunk_stream` into `store`, returning the root `Cid`.
+    async fn organize_chunks<'a>(
+        &self,
+        chunk_stream: BoxStream<'a, StoreResult<Bytes>>,
+        store: Self,
+    ) -> StoreResult<Cid>
+    where
+        C: Chunker + Clone + Send + Sync,
+        L: Layout + Clone + Send + Sync,
+    {
+        let mut cid_stream = self.layout.organize(chunk_stream, store).await?;
+
+        // Take the last `Cid` from the stream.
+        let mut cid = cid_stream.next().await.unwrap()?;
+        while let Some(result) = cid_stream.next().await {
+            cid = result?;
+        }
+
+        Ok(cid)
+    }
+
     /// Stores raw bytes in the store without any size checks.
     async fn store_raw(&self, bytes: Bytes, codec: Codec) -> Cid {
         let cid = utils::make_cid(codec, &bytes);
@@ -122,16 +199,18 @@ where
         &'a self,
         reader: impl AsyncRead + Send + Sync + 'a,
     ) -> StoreResult<Cid> {
-        let chunk_stream = self.chunker.chunk(reader).await?;
-        let mut cid_stream = self.layout.organize(chunk_stream, self.clone()).await?;
+        self.organize_bytes(reader, self.clone()).await
+    }
 
-        // Take the last `Cid` from the stream.
-        let mut cid = cid_stream.next().await.unwrap()?;
-        while let Some(result) = cid_stream.next().await {
-            cid = result?;
-        }
+    async fn cid_of_bytes<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+    ) -> StoreResult<Cid> {
+        // Organize the bytes into a scratch store that is dropped afterwards.
+        let scratch = MemoryStore::new(self.chunker.clone(), self.layout.clone())
+            .with_node_block_max_size(self.node_block_max_size);
 
-        Ok(cid)
+        self.organize_bytes(reader, scratch).await
     }
 
     async fn put_raw_block(&self, bytes: impl Into<Bytes>) -> StoreResult<Cid> {
@@ -195,7 +274,7 @@ where
 
     #[inline]
     fn get_node_block_max_size(&self) -> Option<u64> {
-        self.chunker.chunk_max_size()
+        self.node_block_max_size
     }
 
     #[inline]
@@ -219,11 +298,7 @@ where
 
 impl Default for MemoryStore {
     fn default() -> Self {
-        MemoryStore {
-            blocks: Arc::new(RwLock::new(HashMap::new())),
-            chunker: FixedSizeChunker::default(),
-            layout: FlatLayout::default(),
-        }
+        MemoryStore::new(FixedSizeChunker::default(), FlatLayout::default())
     }
 }
 
@@ -233,8 +308,13 @@ impl Default for MemoryStore {
 
 #[cfg(test)]
 mod tests {
+    use std::io::SeekFrom;
+
+    use futures::TryStreamExt;
     use tokio::io::AsyncReadExt;
 
+    use crate::cas::{fixtures, IpldStoreExt, LineReader};
+
     use super::*;
 
     #[tokio::test]
@@ -269,31 +349,193 @@ mod tests {
 
         Ok(())
     }
-}
 
-#[cfg(test)]
-mod fixtures {
-    use serde::Deserialize;
+    #[tokio::test]
+    async fn test_memory_store_node_block_max_size() -> anyhow::Result<()> {
+        let store = MemoryStore::new(FixedSizeChunker::new(4), FlatLayout::new());
+        assert_eq!(
+            store.get_node_block_max_size(),
+            Some(DEFAULT_NODE_BLOCK_MAX_SIZE)
+        );
+        assert_eq!(store.get_raw_block_max_size(), Some(4));
+
+        // A node with many links is larger than the chunk size but under the node limit.
+        let data = fixtures::Directory {
+            name: "root".to_string(),
+            entries: (0..16u8)
+                .map(|i| utils::make_cid(Codec::Raw, &[i]))
+                .collect(),
+        };
 
-    use super::*;
+        let cid = store.put_node(&data).await?;
+        assert_eq!(store.get_node::<fixtures::Directory>(&cid).await?, data);
+
+        // Chunked data is stored with a merkle node that is also larger than the chunk size.
+        let bytes = (0..40).collect::<Vec<u8>>();
+        let cid = store.put_bytes(&bytes[..]).await?;
+        assert_eq!(store.read_all(&cid).await?, bytes);
+
+        // The node limit can be set independently of the chunk size.
+        let store = store.with_node_block_max_size(Some(64));
+        assert!(matches!(
+            store.put_node(&data).await,
+            Err(StoreError::NodeBlockTooLarge(_, 64))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_get_node_info() -> anyhow::Result<()> {
+        let store = MemoryStore::new(FixedSizeChunker::new(512), FlatLayout::new());
+
+        let data = (0..1100).map(|i| i as u8).collect::<Vec<u8>>();
+        let cid = store.put_bytes(&data[..]).await?;
 
-    //--------------------------------------------------------------------------------------------------
-    // Types
-    //--------------------------------------------------------------------------------------------------
+        let node = store.get_node_info(&cid).await?;
+        assert_eq!(node.size(), 1100);
+        assert_eq!(node.children().len(), 3);
+        assert_eq!(
+            node.children()
+                .iter()
+                .map(|(_, size)| *size)
+                .collect::<Vec<_>>(),
+            [512, 512, 76]
+        );
+
+        let (leaf, _) = node.children()[2];
+        assert_eq!(store.get_raw_block(&leaf).await?, data[1024..]);
+        assert!(store.get_node_info(&leaf).await.is_err());
 
-    #[derive(Clone, Serialize, Deserialize, Debug, PartialEq)]
-    pub(super) struct Directory {
-        pub(super) name: String,
-        pub(super) entries: Vec<Cid>,
+        Ok(())
     }
 
-    //--------------------------------------------------------------------------------------------------
-    // Trait Implementations
-    //--------------------------------------------------------------------------------------------------
+    #[tokio::test]
+    async fn test_memory_store_typed_bytes() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+
+        let data = b"{ \"hello\": \"world\" }";
+        let cid = store.put_typed_bytes(&data[..], "application/json").await?;
+
+        let blob = store.get_typed_blob(&cid).await?;
+        assert_eq!(blob.size, data.len() as u64);
+        assert_eq!(blob.content_type, "application/json");
+
+        let mut reader = store.get_bytes(&blob.root).await?;
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
 
-    impl IpldReferences for Directory {
-        fn references<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Cid> + Send + 'a> {
-            Box::new(self.entries.iter())
+        assert_eq!(bytes, data);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_line_reader() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+
+        let text = "first line\r\nsecond\n\nfourth after an empty line\nlast without newline";
+        let cid = store.put_bytes(text.as_bytes()).await?;
+
+        let reader = LineReader::new(store.get_seekable_bytes(&cid).await?);
+        let lines = reader.into_stream().try_collect::<Vec<_>>().await?;
+        assert_eq!(
+            lines,
+            [
+                "first line",
+                "second",
+                "",
+                "fourth after an empty line",
+                "last without newline"
+            ]
+        );
+
+        // Seeking discards buffered lines.
+        let mut reader = LineReader::new(store.get_seekable_bytes(&cid).await?);
+        assert_eq!(reader.next_line().await?.as_deref(), Some("first line"));
+        assert_eq!(reader.seek(SeekFrom::Start(19)).await?, 19);
+        assert_eq!(reader.next_line().await?.as_deref(), Some(""));
+        assert_eq!(
+            reader.next_line().await?.as_deref(),
+            Some("fourth after an empty line")
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_read_range() -> anyhow::Result<()> {
+        let store = MemoryStore::new(FixedSizeChunker::new(512), FlatLayout::new());
+
+        let data = (0..2000).map(|i| i as u8).collect::<Vec<u8>>();
+        let cid = store.put_bytes(&data[..]).await?;
+
+        // Within a single chunk, across chunks and truncated at the end.
+        for (start, len) in [
+            (5, 2),
+            (500, 30),
+            (100, 1500),
+            (1024, 512),
+            (0, 2000),
+            (1990, 50),
+            (2000, 5),
+            (4000, 1),
+            (7, 0),
+        ] {
+            let bytes = store.read_range(&cid, start, len).await?;
+            let from = (start as usize).min(data.len());
+            let to = (start + len).min(data.len() as u64) as usize;
+            assert_eq!(bytes, data[from..to.max(from)], "range {start}+{len}");
         }
+
+        // A single raw block.
+        let cid = store
+            .put_raw_block(Bytes::from_static(b"hello world"))
+            .await?;
+        assert_eq!(store.read_range(&cid, 6, 5).await?, "world");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_cid_of_bytes() -> anyhow::Result<()> {
+        let store = MemoryStore::new(FixedSizeChunker::new(10), FlatLayout::new());
+        let data = [42; 95];
+
+        // The bytes are chunked like `put_bytes` but not stored.
+        let cid = store.cid_of_bytes(&data[..]).await?;
+        assert!(!store.has(&cid).await);
+
+        assert_eq!(cid, store.put_bytes(&data[..]).await?);
+        assert!(store.has(&cid).await);
+
+        // A store with a different chunk size gives a different `Cid`.
+        assert_ne!(cid, MemoryStore::default().cid_of_bytes(&data[..]).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_store_put_bytes_with_stats() -> anyhow::Result<()> {
+        let store = MemoryStore::new(FixedSizeChunker::new(10), FlatLayout::new());
+        let data = vec![42; 95];
+
+        let (cid, stats) = store.put_bytes_with_stats(&data[..]).await?;
+        assert_eq!(cid, store.cid_of_bytes(&data[..]).await?);
+        assert_eq!(store.read_all(&cid).await?, data);
+
+        // All chunks have the chunk size except the last one.
+        assert_eq!(stats.count(), 10);
+        assert_eq!(stats.min(), 5);
+        assert_eq!(stats.max(), 10);
+        assert_eq!(stats.mean(), 9.5);
+
+        let (_, stats) = store.put_bytes_with_stats(&data[..90]).await?;
+        assert_eq!(stats.count(), 9);
+        assert_eq!(stats.min(), 10);
+        assert_eq!(stats.max(), 10);
+        assert_eq!(stats.mean(), 10.0);
+
+        Ok(())
     }
 }
@@ -1,11 +1,15 @@
+mod builder;
 mod dualstore;
 mod memstore;
+mod nullstore;
 mod plcstore;
 
 //--------------------------------------------------------------------------------------------------
 // Exports
 //--------------------------------------------------------------------------------------------------
 
+pub use builder::*;
 pub use dualstore::*;
 pub use memstore::*;
+pub use nullstore::*;
 pub use plcstore::*;
@@ -0,0 +1,215 @@
+use std::{collections::HashSet, pin::Pin};
+
+use bytes::Bytes;
+use futures::StreamExt;
+use libipld::Cid;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::AsyncRead;
+
+use crate::cas::{
+    utils, Chunker, Codec, FixedSizeChunker, FlatLayout, IpldReferences, IpldStore, Layout,
+    StoreError, StoreResult, DEFAULT_NODE_BLOCK_MAX_SIZE,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A store that computes the `Cid`s of the data put in it but discards the data itself.
+///
+/// It is meant for benchmarking chunkers and layouts independent of the cost of storage. Since nothing
+/// is kept, the store never has a block and every read fails with [`StoreError::BlockNotFound`].
+#[derive(Debug, Clone)]
+pub struct NullStore<C = FixedSizeChunker, L = FlatLayout>
+where
+    C: Chunker,
+    L: Layout,
+{
+    /// The chunking algorithm used to split data into chunks.
+    chunker: C,
+
+    /// The layout strategy used to organize chunked data.
+    layout: L,
+
+    /// The maximum size of a node block. Raw blocks are limited by the chunk size instead.
+    node_block_max_size: Option<u64>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<C, L> NullStore<C, L>
+where
+    C: Chunker,
+    L: Layout,
+{
+    /// Creates a new `NullStore` with the given `chunker` and `layout`.
+    ///
+    /// Node blocks are limited to [`DEFAULT_NODE_BLOCK_MAX_SIZE`].
+    pub fn new(chunker: C, layout: L) -> Self {
+        NullStore {
+            chunker,
+            layout,
+            node_block_max_size: Some(DEFAULT_NODE_BLOCK_MAX_SIZE),
+        }
+    }
+
+    /// Sets the maximum size of a node block, or removes the limit with `None`.
+    pub fn with_node_block_max_size(mut self, node_block_max_size: Option<u64>) -> Self {
+        self.node_block_max_size = node_block_max_size;
+        self
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl<C, L> IpldStore for NullStore<C, L>
+where
+    C: Chunker + Clone + Send + Sync,
+    L: Layout + Clone + Send + Sync,
+{
+    async fn put_node<T>(&self, data: &T) -> StoreResult<Cid>
+    where
+        T: Serialize + IpldReferences + Sync,
+    {
+        let bytes = serde_ipld_dagcbor::to_vec(&data).map_err(StoreError::custom)?;
+
+        // Check if the data exceeds the node maximum block size.
+        if let Some(max_size) = self.get_node_block_max_size() {
+            if bytes.len() as u64 > max_size {
+                return Err(StoreError::NodeBlockTooLarge(bytes.len() as u64, max_size));
+            }
+        }
+
+        Ok(utils::make_cid(Codec::DagCbor, &bytes))
+    }
+
+    async fn put_bytes<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+    ) -> StoreResult<Cid> {
+        let chunk_stream = self.chunker.chunk(reader).await?;
+        let mut cid_stream = self.layout.organize(chunk_stream, self.clone()).await?;
+
+        // Take the last `Cid` from the stream.
+        let mut cid = cid_stream.next().await.unwrap()?;
+        while let Some(result) = cid_stream.next().await {
+            cid = result?;
+        }
+
+        Ok(cid)
+    }
+
+    async fn cid_of_bytes<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+    ) -> StoreResult<Cid> {
+        self.put_bytes(reader).await
+    }
+
+    async fn put_raw_block(&self, bytes: impl Into<Bytes>) -> StoreResult<Cid> {
+        let bytes = bytes.into();
+        if let Some(max_size) = self.get_raw_block_max_size() {
+            if bytes.len() as u64 > max_size {
+                return Err(StoreError::RawBlockTooLarge(bytes.len() as u64, max_size));
+            }
+        }
+
+        Ok(utils::make_cid(Codec::Raw, &bytes))
+    }
+
+    async fn get_node<T>(&self, cid: &Cid) -> StoreResult<T>
+    where
+        T: DeserializeOwned,
+    {
+        Err(StoreError::BlockNotFound(*cid))
+    }
+
+    async fn get_bytes<'a>(
+        &'a self,
+        cid: &'a Cid,
+    ) -> StoreResult<Pin<Box<dyn AsyncRead + Send + Sync + 'a>>> {
+        Err(StoreError::BlockNotFound(*cid))
+    }
+
+    async fn get_raw_block(&self, cid: &Cid) -> StoreResult<Bytes> {
+        Err(StoreError::BlockNotFound(*cid))
+    }
+
+    #[inline]
+    async fn has(&self, _: &Cid) -> bool {
+        false
+    }
+
+    fn get_supported_codecs(&self) -> HashSet<Codec> {
+        let mut codecs = HashSet::new();
+        codecs.insert(Codec::DagCbor);
+        codecs.insert(Codec::Raw);
+        codecs
+    }
+
+    #[inline]
+    fn get_node_block_max_size(&self) -> Option<u64> {
+        self.node_block_max_size
+    }
+
+    #[inline]
+    fn get_raw_block_max_size(&self) -> Option<u64> {
+        self.chunker.chunk_max_size()
+    }
+}
+
+impl Default for NullStore {
+    fn default() -> Self {
+        NullStore::new(FixedSizeChunker::default(), FlatLayout::default())
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::cas::MemoryStore;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_null_store_computes_cids_without_storing() -> anyhow::Result<()> {
+        let store = NullStore::default();
+        let memory_store = MemoryStore::default();
+
+        let cid = store.put_raw_block(b"hello".to_vec()).await?;
+        assert_eq!(cid, utils::make_cid(Codec::Raw, b"hello"));
+        assert_eq!(cid, memory_store.put_raw_block(b"hello".to_vec()).await?);
+        assert!(!store.has(&cid).await);
+        assert_eq!(
+            store.get_raw_block(&cid).await,
+            Err(StoreError::BlockNotFound(cid))
+        );
+
+        let cid = store.put_node(&"hello".to_string()).await?;
+        assert_eq!(cid, memory_store.put_node(&"hello".to_string()).await?);
+        assert!(!store.has(&cid).await);
+        assert_eq!(
+            store.get_node::<String>(&cid).await,
+            Err(StoreError::BlockNotFound(cid))
+        );
+
+        // Chunked data gets the same `Cid` as in a real store.
+        let data = vec![42; 1_000_000];
+        let cid = store.put_bytes(&data[..]).await?;
+        assert_eq!(cid, memory_store.put_bytes(&data[..]).await?);
+        assert!(!store.has(&cid).await);
+        assert!(matches!(
+            store.get_bytes(&cid).await,
+            Err(StoreError::BlockNotFound(c)) if c == cid
+        ));
+
+        Ok(())
+    }
+}
@@ -5,13 +5,17 @@ use libipld::Cid;
 use serde::Serialize;
 use tokio::io::AsyncRead;
 
-use crate::cas::{Codec, IpldReferences, IpldStore, StoreResult};
+use crate::cas::{Codec, IpldReferences, IpldStore, StoreError, StoreResult};
 
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
 
 /// A placeholder store that does nothing.
+///
+/// It is meant for cases where a store is required by type but never actually used, like parsing a
+/// UCAN without resolving its proofs. Any operation that reads or writes data fails with
+/// [`StoreError::PlaceholderStoreUsed`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PlaceholderStore;
 
@@ -24,48 +28,99 @@ impl IpldStore for PlaceholderStore {
     where
         T: Serialize + IpldReferences,
     {
-        unimplemented!("placeholder")
+        Err(StoreError::PlaceholderStoreUsed("put_node"))
     }
 
     async fn put_bytes<'a>(&'a self, _: impl AsyncRead + Send + 'a) -> StoreResult<Cid> {
-        unimplemented!("placeholder")
+        Err(StoreError::PlaceholderStoreUsed("put_bytes"))
+    }
+
+    async fn cid_of_bytes<'a>(&'a self, _: impl AsyncRead + Send + 'a) -> StoreResult<Cid> {
+        Err(StoreError::PlaceholderStoreUsed("cid_of_bytes"))
     }
 
     async fn put_raw_block(&self, _: impl Into<Bytes>) -> StoreResult<Cid> {
-        unimplemented!("placeholder")
+        Err(StoreError::PlaceholderStoreUsed("put_raw_block"))
     }
 
     async fn get_node<D>(&self, _: &Cid) -> StoreResult<D>
     where
         D: serde::de::DeserializeOwned,
     {
-        unimplemented!("placeholder")
+        Err(StoreError::PlaceholderStoreUsed("get_node"))
     }
 
     async fn get_bytes<'a>(
         &'a self,
         _: &'a Cid,
     ) -> StoreResult<Pin<Box<dyn AsyncRead + Send + Sync + 'a>>> {
-        unimplemented!("placeholder")
+        Err(StoreError::PlaceholderStoreUsed("get_bytes"))
     }
 
     async fn get_raw_block(&self, _: &Cid) -> StoreResult<Bytes> {
-        unimplemented!("placeholder")
+        Err(StoreError::PlaceholderStoreUsed("get_raw_block"))
     }
 
     async fn has(&self, _: &Cid) -> bool {
-        unimplemented!("placeholder")
+        false
     }
 
     fn get_supported_codecs(&self) -> HashSet<Codec> {
-        unimplemented!("placeholder")
+        HashSet::new()
     }
 
     fn get_node_block_max_size(&self) -> Option<u64> {
-        unimplemented!("placeholder")
+        Some(0)
     }
 
     fn get_raw_block_max_size(&self) -> Option<u64> {
-        unimplemented!("placeholder")
+        Some(0)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_placeholder_store_fails_on_use() -> anyhow::Result<()> {
+        let store = PlaceholderStore;
+        let cid = Cid::default();
+
+        assert_eq!(
+            store.put_node(&"hello".to_string()).await,
+            Err(StoreError::PlaceholderStoreUsed("put_node"))
+        );
+        assert_eq!(
+            store.put_bytes(&b"hello"[..]).await,
+            Err(StoreError::PlaceholderStoreUsed("put_bytes"))
+        );
+        assert_eq!(
+            store.cid_of_bytes(&b"hello"[..]).await,
+            Err(StoreError::PlaceholderStoreUsed("cid_of_bytes"))
+        );
+        assert_eq!(
+            store.put_raw_block(b"hello".to_vec()).await,
+            Err(StoreError::PlaceholderStoreUsed("put_raw_block"))
+        );
+        assert_eq!(
+            store.get_node::<String>(&cid).await,
+            Err(StoreError::PlaceholderStoreUsed("get_node"))
+        );
+        assert!(matches!(
+            store.get_bytes(&cid).await,
+            Err(StoreError::PlaceholderStoreUsed("get_bytes"))
+        ));
+        assert_eq!(
+            store.get_raw_block(&cid).await,
+            Err(StoreError::PlaceholderStoreUsed("get_raw_block"))
+        );
+        assert!(!store.has(&cid).await);
+
+        Ok(())
     }
 }
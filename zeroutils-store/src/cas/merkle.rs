@@ -51,6 +51,17 @@ impl MerkleNode {
             children: deps,
         }
     }
+
+    /// Returns the size in bytes of the data this node represents.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the `Cid`s of the direct children of this node and the size in bytes of the data
+    /// each of them references.
+    pub fn children(&self) -> &[(Cid, usize)] {
+        &self.children
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -8,6 +8,8 @@
 
 mod chunker;
 mod error;
+#[cfg(test)]
+mod fixtures;
 mod impls;
 mod layout;
 mod merkle;
@@ -15,6 +17,7 @@ mod references;
 mod seekable;
 mod storable;
 mod store;
+mod typed;
 pub(crate) mod utils;
 
 //--------------------------------------------------------------------------------------------------
@@ -30,6 +33,7 @@ pub use references::*;
 pub use seekable::*;
 pub use storable::*;
 pub use store::*;
+pub use typed::*;
 
 //--------------------------------------------------------------------------------------------------
 // Re-Exports
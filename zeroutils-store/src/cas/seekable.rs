@@ -1,4 +1,21 @@
-use tokio::io::{AsyncRead, AsyncSeek};
+use std::io::SeekFrom;
+
+use futures::{stream, Stream};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncSeek, AsyncSeekExt, BufReader};
+
+use super::{StoreError, StoreResult};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// Reads text line by line from a [`SeekableReader`], e.g. one returned by
+/// [`get_seekable_bytes`][crate::cas::IpldStoreSeekable::get_seekable_bytes].
+///
+/// Lines are read through a buffer so the underlying data is never loaded all at once.
+pub struct LineReader<R> {
+    reader: BufReader<R>,
+}
 
 //--------------------------------------------------------------------------------------------------
 // Traits
@@ -7,6 +24,68 @@ use tokio::io::{AsyncRead, AsyncSeek};
 /// A trait that extends the `AsyncRead` and `AsyncSeek` traits to allow for seeking.
 pub trait SeekableReader: AsyncRead + AsyncSeek {}
 
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<R> LineReader<R>
+where
+    R: SeekableReader + Unpin,
+{
+    /// Creates a new line reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+        }
+    }
+
+    /// Reads the next line without its `\n` or `\r\n` terminator.
+    ///
+    /// Returns `None` once the end of the data is reached.
+    pub async fn next_line(&mut self) -> StoreResult<Option<String>> {
+        let mut line = String::new();
+        let read = self
+            .reader
+            .read_line(&mut line)
+            .await
+            .map_err(StoreError::custom)?;
+
+        if read == 0 {
+            return Ok(None);
+        }
+
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+
+        Ok(Some(line))
+    }
+
+    /// Seeks to a byte position in the underlying reader and returns the new position.
+    ///
+    /// Buffered data is discarded, so the next line starts at the new position.
+    pub async fn seek(&mut self, position: SeekFrom) -> StoreResult<u64> {
+        self.reader.seek(position).await.map_err(StoreError::custom)
+    }
+
+    /// Converts the line reader into a stream of lines.
+    pub fn into_stream(self) -> impl Stream<Item = StoreResult<String>> {
+        stream::try_unfold(self, |mut reader| async move {
+            Ok(reader.next_line().await?.map(|line| (line, reader)))
+        })
+    }
+
+    /// Returns the underlying reader.
+    ///
+    /// Any buffered data that has not been read yet is lost.
+    pub fn into_inner(self) -> R {
+        self.reader.into_inner()
+    }
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
@@ -1,7 +1,8 @@
 use futures::Future;
 use libipld::Cid;
+use serde::{de::DeserializeOwned, Serialize};
 
-use super::{IpldStore, StoreResult};
+use super::{IpldReferences, IpldStore, StoreResult};
 
 //--------------------------------------------------------------------------------------------------
 // Traits
@@ -18,3 +19,72 @@ where
     /// Loads the type from the IPLD store.
     fn load(cid: &Cid, store: S) -> impl Future<Output = StoreResult<Self>>;
 }
+
+/// A trait for types that are stored as a single IPLD node.
+///
+/// Unlike [`Storable`], this doesn't have to be implemented by hand. It is implemented for every type
+/// that can be serialized to IPLD and reports its references, and the store is passed in since such
+/// types don't hold one.
+pub trait StorableNode: Serialize + DeserializeOwned + IpldReferences + Send + Sync {
+    /// Stores the type in `store` with [`put_node`](IpldStore::put_node) and returns the Cid.
+    fn store_in<S>(&self, store: &S) -> impl Future<Output = StoreResult<Cid>>
+    where
+        S: IpldStore,
+    {
+        store.put_node(self)
+    }
+
+    /// Loads the type from `store` with [`get_node`](IpldStore::get_node).
+    fn load_from<S>(cid: &Cid, store: &S) -> impl Future<Output = StoreResult<Self>>
+    where
+        S: IpldStore,
+    {
+        store.get_node(cid)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl<T> StorableNode for T where T: Serialize + DeserializeOwned + IpldReferences + Send + Sync {}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use crate::cas::{fixtures::Directory, MemoryStore};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_storable_node_store_and_load() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+
+        let readme = store.put_bytes(&b"# Hello"[..]).await?;
+        let license = store.put_bytes(&b"MIT"[..]).await?;
+
+        let directory = Directory {
+            name: "docs".to_string(),
+            entries: vec![readme, license],
+        };
+
+        let cid = directory.store_in(&store).await?;
+        assert!(store.has(&cid).await);
+        assert_eq!(
+            directory.references().collect::<Vec<_>>(),
+            [&readme, &license]
+        );
+
+        let loaded = Directory::load_from(&cid, &store).await?;
+        assert_eq!(loaded, directory);
+
+        // Nodes of other types are not mistaken for the struct.
+        let cid = "docs".to_string().store_in(&store).await?;
+        assert!(Directory::load_from(&cid, &store).await.is_err());
+
+        Ok(())
+    }
+}
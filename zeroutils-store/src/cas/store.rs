@@ -4,8 +4,18 @@ use bytes::Bytes;
 use libipld::Cid;
 use serde::{de::DeserializeOwned, Serialize};
 use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio_util::io::InspectReader;
 
-use super::{IpldReferences, SeekableReader, StoreError, StoreResult};
+use super::{IpldReferences, MerkleNode, SeekableReader, StoreError, StoreResult, TypedBlob};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The default maximum size of a node block is 1 MiB.
+///
+/// This is independent of the chunk size, as nodes with many links can be larger than a chunk.
+pub const DEFAULT_NODE_BLOCK_MAX_SIZE: u64 = 1024 * 1024;
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -73,6 +83,16 @@ pub trait IpldStore: Clone {
         reader: impl AsyncRead + Send + Sync + 'a,
     ) -> impl Future<Output = StoreResult<Cid>> + 'a;
 
+    /// Computes the `Cid` that `put_bytes` would return for the bytes of `reader` without saving
+    /// anything to the store.
+    ///
+    /// This is useful for referencing data by its `Cid` before or without persisting it. The bytes
+    /// must be chunked and organized with the same chunker and layout as `put_bytes`.
+    fn cid_of_bytes<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+    ) -> impl Future<Output = StoreResult<Cid>> + 'a;
+
     /// Tries to save `bytes` as a single block to the store. Unlike `put_bytes`, this method does
     /// not chunk the data and does not create intermediate merkle nodes.
     ///
@@ -141,6 +161,104 @@ pub trait IpldStoreExt: IpldStore {
             Ok(Bytes::from(bytes))
         }
     }
+
+    /// Saves the bytes of `reader` to the store along with their media type.
+    ///
+    /// Returns the `Cid` of the [`TypedBlob`] that wraps the bytes.
+    fn put_typed_bytes<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+        content_type: impl Into<String> + 'a,
+    ) -> impl Future<Output = StoreResult<Cid>> + 'a {
+        async move {
+            let mut size = 0;
+            let reader = InspectReader::new(reader, |bytes| size += bytes.len() as u64);
+            let root = self.put_bytes(reader).await?;
+
+            let blob = TypedBlob {
+                content_type: content_type.into(),
+                size,
+                root,
+            };
+
+            self.put_node(&blob).await
+        }
+    }
+
+    /// Gets the [`TypedBlob`] with the given `Cid`.
+    ///
+    /// The bytes are not read here. Stream them with [`get_bytes`][IpldStore::get_bytes] on the
+    /// `root` of the blob, so data of any size is never held in memory at once.
+    fn get_typed_blob(&self, cid: &Cid) -> impl Future<Output = StoreResult<TypedBlob>> {
+        self.get_node(cid)
+    }
+
+    /// Gets the [`MerkleNode`] with the given `Cid` to inspect the shape of a DAG.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the `Cid` does not refer to a `MerkleNode`, e.g. a raw leaf block.
+    fn get_node_info(&self, cid: &Cid) -> impl Future<Output = StoreResult<MerkleNode>> {
+        self.get_node(cid)
+    }
+
+    /// Reads `len` bytes starting at byte `start` of the data associated with the given `Cid`.
+    ///
+    /// Only the leaf blocks covering the range are fetched, using the sizes recorded in the
+    /// [`MerkleNode`]s of the layout. The range is truncated at the end of the data, so a range
+    /// starting past the end returns empty bytes.
+    ///
+    /// This expects the data to be a raw block or a tree of `MerkleNode`s with raw leaves, which is
+    /// how the layouts in this crate organize data.
+    fn read_range(
+        &self,
+        cid: &Cid,
+        start: u64,
+        len: u64,
+    ) -> impl Future<Output = StoreResult<Bytes>> {
+        async move {
+            let end = start.saturating_add(len);
+            let mut slices = Vec::new();
+
+            // Depth-first traversal in data order. Each entry is a `Cid` and its byte offset.
+            let mut stack = vec![(*cid, 0u64)];
+            while let Some((cid, offset)) = stack.pop() {
+                match Codec::try_from(cid.codec())? {
+                    Codec::Raw => {
+                        let block = self.get_raw_block(&cid).await?;
+                        let block_end = offset + block.len() as u64;
+                        if start < block_end && offset < end {
+                            let from = start.max(offset) - offset;
+                            let to = end.min(block_end) - offset;
+                            slices.push(block.slice(from as usize..to as usize));
+                        }
+                    }
+                    _ => {
+                        let node: MerkleNode = self.get_node(&cid).await?;
+                        let mut distance = offset;
+                        let mut covering = Vec::new();
+                        for (child, size) in node.children {
+                            let child_end = distance + size as u64;
+                            if start < child_end && distance < end {
+                                covering.push((child, distance));
+                            }
+
+                            distance = child_end;
+                        }
+
+                        stack.extend(covering.into_iter().rev());
+                    }
+                }
+            }
+
+            // A range within a single leaf shares the buffer of the block.
+            match slices.len() {
+                0 => Ok(Bytes::new()),
+                1 => Ok(slices.remove(0)),
+                _ => Ok(Bytes::from(slices.concat())),
+            }
+        }
+    }
 }
 
 /// `IpldStoreSeekable` is a trait that extends the `IpldStore` trait to allow for seeking.
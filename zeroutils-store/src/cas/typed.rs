@@ -0,0 +1,35 @@
+use libipld::Cid;
+use serde::{Deserialize, Serialize};
+
+use super::IpldReferences;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A metadata node that tags stored bytes with their media type.
+///
+/// The store itself is untyped. This node wraps the `Cid` of the bytes so applications can remember
+/// what the bytes are. See [`IpldStoreExt::put_typed_bytes`][crate::cas::IpldStoreExt::put_typed_bytes]
+/// and [`IpldStoreExt::get_typed_blob`][crate::cas::IpldStoreExt::get_typed_blob].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TypedBlob {
+    /// The media type of the bytes, e.g. `text/plain`.
+    pub content_type: String,
+
+    /// The size of the bytes.
+    pub size: u64,
+
+    /// The `Cid` of the bytes.
+    pub root: Cid,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl IpldReferences for TypedBlob {
+    fn references<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Cid> + Send + 'a> {
+        Box::new(std::iter::once(&self.root))
+    }
+}
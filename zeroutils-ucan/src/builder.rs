@@ -1,12 +1,17 @@
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
 use libipld::Cid;
+use rand_core::{CryptoRngCore, OsRng};
 use serde_json::Value;
 use zeroutils_did::{did_wk::WrappedDidWebKey, Base};
 use zeroutils_key::{GetPublicKey, IntoOwned, JwsAlgName, Sign};
 use zeroutils_store::cas::IpldStore;
 
-use crate::{Capabilities, Facts, Proofs, SignedUcan, Ucan, UcanPayload, UcanResult};
+use crate::{
+    payload::truncate_to_secs, Capabilities, Facts, ProofReference, Proofs, ResourceUri,
+    SignedUcan, Ucan, UcanError, UcanPayload, UcanResult,
+};
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -25,6 +30,17 @@ pub struct UcanBuilder<I = (), A = (), E = (), C = (), P = (), S = ()> {
     store: S,
 }
 
+/// The expiration state of a [`UcanBuilder`].
+///
+/// The state starts out unset (`()`), which does not implement this trait, so a UCAN cannot be built
+/// or signed until [`expiration`](UcanBuilder::expiration),
+/// [`no_expiration`](UcanBuilder::no_expiration) or [`default_ttl`](UcanBuilder::default_ttl) is
+/// called.
+pub trait BuilderExpiration {
+    /// Resolves the expiration time of the UCAN being built.
+    fn resolve(self) -> Option<SystemTime>;
+}
+
 /// A builder for creating UCAN (User-Controlled Authorization Network) tokens.
 pub type DefaultUcanBuilder = UcanBuilder<(), (), (), (), (), ()>;
 
@@ -73,6 +89,9 @@ impl<I, A, E, C, P, S> UcanBuilder<I, A, E, C, P, S> {
     }
 
     /// Sets the expiration time of the UCAN.
+    ///
+    /// This replaces a [`default_ttl`](Self::default_ttl) set earlier. Passing `None` is the same as
+    /// [`no_expiration`](Self::no_expiration).
     pub fn expiration(
         self,
         expiration: impl Into<Option<SystemTime>>,
@@ -90,6 +109,11 @@ impl<I, A, E, C, P, S> UcanBuilder<I, A, E, C, P, S> {
         }
     }
 
+    /// Explicitly builds a UCAN that never expires.
+    pub fn no_expiration(self) -> UcanBuilder<I, A, Option<SystemTime>, C, P, S> {
+        self.expiration(None)
+    }
+
     /// Sets the time before which the UCAN is not valid.
     pub fn not_before(mut self, not_before: impl Into<SystemTime>) -> Self {
         self.not_before = Some(not_before.into());
@@ -97,11 +121,27 @@ impl<I, A, E, C, P, S> UcanBuilder<I, A, E, C, P, S> {
     }
 
     /// Sets a nonce to prevent replay attacks.
+    ///
+    /// An empty nonce provides no replay protection, so it leaves the UCAN without a nonce.
     pub fn nonce(mut self, nonce: impl Into<String>) -> Self {
-        self.nonce = Some(nonce.into());
+        self.nonce = Some(nonce.into()).filter(|nonce| !nonce.is_empty());
         self
     }
 
+    /// Sets a random nonce from the operating system's random number generator.
+    pub fn random_nonce(self) -> Self {
+        self.random_nonce_with(&mut OsRng)
+    }
+
+    /// Sets a random nonce from the given random number generator.
+    ///
+    /// The nonce is 12 random bytes encoded as unpadded base64url.
+    pub fn random_nonce_with(self, rng: &mut impl CryptoRngCore) -> Self {
+        let mut bytes = [0; 12];
+        rng.fill_bytes(&mut bytes);
+        self.nonce(BASE64_URL_SAFE_NO_PAD.encode(bytes))
+    }
+
     /// Adds facts (claims) to the UCAN.
     pub fn facts(mut self, facts: impl IntoIterator<Item = (String, Value)>) -> Self {
         self.facts = Some(facts.into_iter().collect());
@@ -145,6 +185,27 @@ impl<I, A, E, C, P, S> UcanBuilder<I, A, E, C, P, S> {
     }
 }
 
+impl<I, A, C, P, S> UcanBuilder<I, A, (), C, P, S> {
+    /// Sets the time-to-live used to derive the expiration, so the UCAN expires `ttl` after it is
+    /// built.
+    ///
+    /// This is only available while the expiration is unset. Calling
+    /// [`expiration`](Self::expiration) afterwards replaces it.
+    pub fn default_ttl(self, ttl: Duration) -> UcanBuilder<I, A, Duration, C, P, S> {
+        UcanBuilder {
+            issuer: self.issuer,
+            audience: self.audience,
+            expiration: ttl,
+            not_before: self.not_before,
+            nonce: self.nonce,
+            facts: self.facts,
+            capabilities: self.capabilities,
+            proofs: self.proofs,
+            store: self.store,
+        }
+    }
+}
+
 impl<I, A, E, C, P, S> UcanBuilder<I, A, E, C, P, S>
 where
     S: IpldStore,
@@ -168,25 +229,22 @@ where
     }
 }
 
-impl<'a, S>
-    UcanBuilder<
-        WrappedDidWebKey<'a>,
-        WrappedDidWebKey<'a>,
-        Option<SystemTime>,
-        Capabilities<'a>,
-        Proofs<S>,
-        S,
-    >
+impl<'a, E, S>
+    UcanBuilder<WrappedDidWebKey<'a>, WrappedDidWebKey<'a>, E, Capabilities<'a>, Proofs<S>, S>
 where
+    E: BuilderExpiration,
     S: IpldStore,
 {
     /// Builds a UCAN from the specified components.
+    ///
+    /// The expiration and not-before times are truncated to whole seconds, as they are encoded, so
+    /// the built UCAN is the same as the one parsed from its encoding.
     pub fn build(self) -> Ucan<'a, S, ()> {
         let payload = UcanPayload {
             issuer: self.issuer,
             audience: self.audience,
-            expiration: self.expiration,
-            not_before: self.not_before,
+            expiration: self.expiration.resolve().map(truncate_to_secs),
+            not_before: self.not_before.map(truncate_to_secs),
             nonce: self.nonce,
             facts: self.facts,
             capabilities: self.capabilities,
@@ -196,11 +254,44 @@ where
 
         Ucan::from_parts((), payload, ())
     }
+
+    /// Builds a UCAN like [`build`](Self::build), but first checks what the builder's type-state
+    /// cannot.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UcanError::MissingField`] if:
+    /// - a `ucan:./*` capability is delegated without proofs, as `prf`
+    /// - a `ucan:<cid>` capability references a CID that is not in the proofs, as `prf[<cid>]`
+    ///
+    /// Invalid time bounds are reported like [`Ucan::validate`] does.
+    pub fn build_validated(self) -> UcanResult<Ucan<'a, S, ()>> {
+        for (resource, _) in self.capabilities.iter() {
+            match resource {
+                ResourceUri::Reference(ProofReference::AllProofsInCurrentUcan)
+                    if self.proofs.is_empty() =>
+                {
+                    return Err(UcanError::MissingField("prf".to_string()));
+                }
+                ResourceUri::Reference(ProofReference::SpecificProofByCid(cid))
+                    if !self.proofs.contains_cid(cid) =>
+                {
+                    return Err(UcanError::MissingField(format!("prf[{cid}]")));
+                }
+                _ => {}
+            }
+        }
+
+        let ucan = self.build();
+        ucan.validate()?;
+
+        Ok(ucan)
+    }
 }
 
-impl<'a, S>
-    UcanBuilder<(), WrappedDidWebKey<'a>, Option<SystemTime>, Capabilities<'a>, Proofs<S>, S>
+impl<'a, E, S> UcanBuilder<(), WrappedDidWebKey<'a>, E, Capabilities<'a>, Proofs<S>, S>
 where
+    E: BuilderExpiration,
     S: IpldStore,
 {
     /// Signs the built UCAN with a given keypair.
@@ -216,16 +307,10 @@ where
     }
 }
 
-impl<'a, S>
-    UcanBuilder<
-        WrappedDidWebKey<'a>,
-        WrappedDidWebKey<'a>,
-        Option<SystemTime>,
-        Capabilities<'a>,
-        Proofs<S>,
-        S,
-    >
+impl<'a, E, S>
+    UcanBuilder<WrappedDidWebKey<'a>, WrappedDidWebKey<'a>, E, Capabilities<'a>, Proofs<S>, S>
 where
+    E: BuilderExpiration,
     S: IpldStore,
 {
     /// Signs the built UCAN with a given keypair.
@@ -241,6 +326,18 @@ where
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
 
+impl BuilderExpiration for Option<SystemTime> {
+    fn resolve(self) -> Option<SystemTime> {
+        self
+    }
+}
+
+impl BuilderExpiration for Duration {
+    fn resolve(self) -> Option<SystemTime> {
+        Some(SystemTime::now() + self)
+    }
+}
+
 impl Default for UcanBuilder<(), (), (), (), (), ()> {
     fn default() -> Self {
         UcanBuilder {
@@ -266,6 +363,7 @@ mod tests {
     use std::{str::FromStr, time::Duration};
 
     use anyhow::Ok;
+    use rand::{rngs::StdRng, SeedableRng};
     use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate};
     use zeroutils_store::cas::PlaceholderStore;
 
@@ -303,9 +401,9 @@ mod tests {
         );
         assert_eq!(
             ucan.payload.expiration,
-            Some(now + Duration::from_secs(360_000))
+            Some(truncate_to_secs(now + Duration::from_secs(360_000)))
         );
-        assert_eq!(ucan.payload.not_before, Some(now));
+        assert_eq!(ucan.payload.not_before, Some(truncate_to_secs(now)));
         assert_eq!(ucan.payload.nonce, Some("1100263a4012".to_string()));
         assert_eq!(ucan.payload.facts, Some(Facts::default()));
         assert_eq!(ucan.payload.capabilities, Capabilities::default());
@@ -332,4 +430,185 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_ucan_builder_default_ttl() -> anyhow::Result<()> {
+        let before = SystemTime::now();
+        let ttl = Duration::from_secs(3_600);
+
+        // Applied when the expiration is absent.
+        let ucan = UcanBuilder::default()
+            .store(PlaceholderStore)
+            .issuer("did:wk:b44aqepqvrvaix2aosv2oluhoa3kf7yan6xevmn2asn3scuev2iydukkv")
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .default_ttl(ttl)
+            .capabilities(caps!()?)
+            .build();
+
+        let expiration = ucan.payload.expiration.unwrap();
+        assert!(expiration >= truncate_to_secs(before + ttl));
+        assert!(expiration <= SystemTime::now() + ttl);
+
+        // Replaced when the expiration is set afterwards.
+        let expiration = before + Duration::from_secs(60);
+        let ucan = UcanBuilder::default()
+            .store(PlaceholderStore)
+            .issuer("did:wk:b44aqepqvrvaix2aosv2oluhoa3kf7yan6xevmn2asn3scuev2iydukkv")
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .default_ttl(ttl)
+            .expiration(expiration)
+            .capabilities(caps!()?)
+            .build();
+
+        assert_eq!(ucan.payload.expiration, Some(truncate_to_secs(expiration)));
+
+        // A UCAN that never expires has to be asked for explicitly.
+        let ucan = UcanBuilder::default()
+            .store(PlaceholderStore)
+            .issuer("did:wk:b44aqepqvrvaix2aosv2oluhoa3kf7yan6xevmn2asn3scuev2iydukkv")
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .no_expiration()
+            .capabilities(caps!()?)
+            .build();
+
+        assert_eq!(ucan.payload.expiration, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ucan_builder_build_validated() -> anyhow::Result<()> {
+        let now = SystemTime::now();
+        let cid = Cid::from_str("bafkreih43byuv2f6ils5kpsj2qwzbwgdd2pqzs6anwm3nhfrhlagqjektm")?;
+        let builder = || {
+            UcanBuilder::default()
+                .store(PlaceholderStore)
+                .issuer("did:wk:b44aqepqvrvaix2aosv2oluhoa3kf7yan6xevmn2asn3scuev2iydukkv")
+                .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+        };
+
+        // Valid
+        let ucan = builder()
+            .expiration(now + Duration::from_secs(60))
+            .capabilities(caps! {
+                "ucan:./*": { "ucan/*": [{}] },
+                "ucan:bafkreih43byuv2f6ils5kpsj2qwzbwgdd2pqzs6anwm3nhfrhlagqjektm": { "ucan/*": [{}] }
+            }?)
+            .proofs([cid])
+            .build_validated()?;
+
+        assert_eq!(ucan.payload.proofs.len(), 1);
+
+        let ucan = builder()
+            .default_ttl(Duration::from_secs(60))
+            .capabilities(caps!()?)
+            .build_validated()?;
+
+        assert!(ucan.payload.expiration.is_some());
+
+        assert!(builder()
+            .no_expiration()
+            .capabilities(caps!()?)
+            .build_validated()
+            .is_ok());
+
+        // Delegating all proofs without proofs
+        let result = builder()
+            .expiration(now + Duration::from_secs(60))
+            .capabilities(caps! {
+                "ucan:./*": { "ucan/*": [{}] }
+            }?)
+            .build_validated();
+        assert!(matches!(result, Err(UcanError::MissingField(field)) if field == "prf"));
+
+        // Referencing an undefined proof
+        let other_cid =
+            Cid::from_str("bafkreifiul3oxyugnf6fe7vtljmlku4vglu3hlr3mtkowcsg7nsxwqkwfq")?;
+        let result = builder()
+            .expiration(now + Duration::from_secs(60))
+            .capabilities(caps! {
+                "ucan:bafkreifiul3oxyugnf6fe7vtljmlku4vglu3hlr3mtkowcsg7nsxwqkwfq": { "ucan/*": [{}] }
+            }?)
+            .proofs([cid])
+            .build_validated();
+        assert!(matches!(
+            result,
+            Err(UcanError::MissingField(field)) if field == format!("prf[{other_cid}]")
+        ));
+
+        // Invalid time bounds
+        let result = builder()
+            .expiration(now + Duration::from_secs(60))
+            .not_before(now + Duration::from_secs(120))
+            .capabilities(caps!()?)
+            .build_validated();
+        assert!(matches!(result, Err(UcanError::InvalidTimeBounds(_, _))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ucan_builder_empty_nonce() -> anyhow::Result<()> {
+        let builder = || {
+            UcanBuilder::default()
+                .store(PlaceholderStore)
+                .issuer("did:wk:b44aqepqvrvaix2aosv2oluhoa3kf7yan6xevmn2asn3scuev2iydukkv")
+                .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+                .expiration(None)
+        };
+
+        let ucan = builder().nonce("").capabilities(caps!()?).build();
+        assert_eq!(ucan.payload().nonce(), None);
+        let serialized = serde_json::to_value(ucan.payload())?;
+        assert!(serialized.get("nnc").is_none());
+
+        // An empty nonce replaces a nonce set before it.
+        let ucan = builder()
+            .nonce("1100263a4012")
+            .nonce("")
+            .capabilities(caps!()?)
+            .build();
+        assert_eq!(ucan.payload().nonce(), None);
+
+        let ucan = builder()
+            .nonce("1100263a4012")
+            .capabilities(caps!()?)
+            .build();
+        let serialized = serde_json::to_value(ucan.payload())?;
+        assert_eq!(serialized["nnc"], "1100263a4012");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ucan_builder_seeded_rng() -> anyhow::Result<()> {
+        let ucan = |seed| -> anyhow::Result<_> {
+            let rng = &mut StdRng::seed_from_u64(seed);
+            let key_pair = Ed25519KeyPair::generate(rng)?;
+            let did = WrappedDidWebKey::from_key(&key_pair, Base::Base58Btc)?;
+            let ucan = UcanBuilder::default()
+                .store(PlaceholderStore)
+                .issuer(did.clone())
+                .audience(did)
+                .expiration(None)
+                .random_nonce_with(rng)
+                .capabilities(caps!()?)
+                .sign(&key_pair)?;
+
+            Ok(ucan.to_string())
+        };
+
+        // The same seed gives the same keys and nonce, and so the same signed UCAN.
+        assert_eq!(ucan(7)?, ucan(7)?);
+        assert_ne!(ucan(7)?, ucan(8)?);
+
+        let builder = UcanBuilder::default().random_nonce_with(&mut StdRng::seed_from_u64(7));
+        assert_eq!(builder.nonce.as_deref().map(str::len), Some(16));
+        assert_ne!(
+            UcanBuilder::default().random_nonce().nonce,
+            UcanBuilder::default().random_nonce().nonce
+        );
+
+        Ok(())
+    }
 }
@@ -71,6 +71,29 @@ impl<'a> Capabilities<'a> {
         None
     }
 
+    /// Returns the capabilities in `requested` that are permitted by the main capabilities.
+    ///
+    /// Each `resource ✕ ability ✕ caveats` tuple of `requested` is kept as is if it is permitted, so
+    /// the result is the part of a required set that a UCAN can actually exercise.
+    pub fn intersect<'b>(&self, requested: &Capabilities<'b>) -> Capabilities<'b> {
+        let intersection = requested
+            .0
+            .iter()
+            .filter_map(|(resource, abilities)| {
+                let permitted = abilities
+                    .0
+                    .iter()
+                    .filter(|(ability, caveats)| self.permits(resource, ability, caveats).is_some())
+                    .map(|(ability, caveats)| (ability.clone(), caveats.clone()))
+                    .collect::<BTreeMap<_, _>>();
+
+                (!permitted.is_empty()).then(|| (resource.clone(), Abilities(permitted)))
+            })
+            .collect();
+
+        Capabilities(intersection)
+    }
+
     /// Gets the abilities for a given resource.
     pub fn get(&'a self, resource: &'a ResourceUri) -> Option<&Abilities> {
         self.0.get(resource)
@@ -87,6 +110,9 @@ impl<'a> Capabilities<'a> {
     }
 
     /// Inserts a resource and its abilities into the capabilities.
+    ///
+    /// A `ucan:` resource must have exactly the `ucan/*` ability with the `[{}]` caveats, which is
+    /// the canonical form of delegating proofs.
     pub fn insert(
         &mut self,
         resource: ResourceUri<'a>,
@@ -94,12 +120,18 @@ impl<'a> Capabilities<'a> {
     ) -> UcanResult<Option<Abilities>> {
         if let ResourceUri::Reference(_) = resource {
             if !abilities.is_ucan() {
-                return Err(UcanError::InvalidUcanResourceAbility(abilities));
+                return Err(UcanError::InvalidUcanResourceAbility(
+                    resource.to_string(),
+                    abilities,
+                ));
             }
 
             let caveats = abilities.get(&Ability::Ucan).unwrap();
             if !caveats.is_any() {
-                return Err(UcanError::InvalidUcanResourceCaveats(caveats.clone()));
+                return Err(UcanError::InvalidUcanResourceCaveats(
+                    resource.to_string(),
+                    caveats.clone(),
+                ));
             }
         }
 
@@ -132,6 +164,33 @@ impl Abilities {
     pub fn get(&self, ability: &Ability) -> Option<&Caveats> {
         self.0.get(ability)
     }
+
+    /// Inserts an ability and its caveats, returning the caveats it replaced, if any.
+    pub fn insert(&mut self, ability: Ability, caveats: Caveats) -> Option<Caveats> {
+        self.0.insert(ability, caveats)
+    }
+
+    /// Removes an ability, returning its caveats if it was present.
+    ///
+    /// Fails without removing anything if the ability is the last one, since abilities must contain at
+    /// least one entry.
+    pub fn remove(&mut self, ability: &Ability) -> UcanResult<Option<Caveats>> {
+        if self.0.len() == 1 && self.0.contains_key(ability) {
+            return Err(UcanError::NoAbility);
+        }
+
+        Ok(self.0.remove(ability))
+    }
+
+    /// Replaces the caveats of an existing ability, returning the previous caveats.
+    pub fn with_caveats(&mut self, ability: &Ability, caveats: Caveats) -> UcanResult<Caveats> {
+        let existing = self
+            .0
+            .get_mut(ability)
+            .ok_or_else(|| UcanError::AbilityNotFound(ability.clone()))?;
+
+        Ok(std::mem::replace(existing, caveats))
+    }
 }
 
 impl CapabilityTuple {
@@ -284,6 +343,64 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_capabilities_insert_ucan_resource() -> anyhow::Result<()> {
+        let mut capabilities = Capabilities::new();
+
+        // `ucan/*` with the single empty caveat is the canonical form.
+        for resource in [
+            "ucan:*",
+            "ucan:./*",
+            "ucan://did:wk:z6MkqAywjQVwsr7m1HMamynCZZjH8AKPqYZNXwpHg842pPsG/*",
+        ] {
+            capabilities.insert(
+                resource.parse()?,
+                Abilities::try_from_iter([("ucan/*".parse()?, caveats![{}]?)])?,
+            )?;
+        }
+
+        assert_eq!(capabilities.len(), 3);
+
+        // Wrong ability
+        let result = capabilities.insert(
+            "ucan:./*".parse()?,
+            Abilities::try_from_iter([("crud/read".parse()?, Caveats::any())])?,
+        );
+        assert!(matches!(
+            result,
+            Err(UcanError::InvalidUcanResourceAbility(resource, abilities))
+                if resource == "ucan:./*" && abilities.len() == 1
+        ));
+
+        // `ucan/*` along with other abilities
+        let result = capabilities.insert(
+            "ucan:*".parse()?,
+            Abilities::try_from_iter([
+                ("ucan/*".parse()?, Caveats::any()),
+                ("crud/read".parse()?, Caveats::any()),
+            ])?,
+        );
+        assert!(matches!(
+            result,
+            Err(UcanError::InvalidUcanResourceAbility(resource, abilities))
+                if resource == "ucan:*" && abilities.len() == 2
+        ));
+
+        // Wrong caveats
+        let caveats = Caveats::try_from_iter([json!({"public": true})])?;
+        let result = capabilities.insert(
+            "ucan:*".parse()?,
+            Abilities::try_from_iter([("ucan/*".parse()?, caveats.clone())])?,
+        );
+        assert!(matches!(
+            result,
+            Err(UcanError::InvalidUcanResourceCaveats(resource, c))
+                if resource == "ucan:*" && c == caveats
+        ));
+
+        Ok(())
+    }
+
     #[test]
     fn test_abilities_constructors() -> anyhow::Result<()> {
         let abilities = Abilities::try_from_iter(vec![
@@ -299,6 +416,50 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_abilities_editing() -> anyhow::Result<()> {
+        let read: Ability = "crud/read".parse()?;
+        let delete: Ability = "crud/delete".parse()?;
+        let update: Ability = "crud/update".parse()?;
+
+        let mut abilities = Abilities::try_from_iter([(read.clone(), Caveats::any())])?;
+        assert!(abilities.insert(delete.clone(), Caveats::any()).is_none());
+        assert!(abilities
+            .insert(update.clone(), caveats![{ "public": true }]?)
+            .is_none());
+        assert_eq!(abilities.len(), 3);
+
+        // Inserting an existing ability replaces its caveats
+        assert_eq!(
+            abilities.insert(update.clone(), Caveats::any()),
+            Some(caveats![{ "public": true }]?)
+        );
+        assert_eq!(abilities.len(), 3);
+
+        let previous = abilities.with_caveats(&read, caveats![{ "max_count": 5 }]?)?;
+        assert_eq!(previous, Caveats::any());
+        assert_eq!(abilities["crud/read"], caveats![{ "max_count": 5 }]?);
+
+        assert!(matches!(
+            abilities.with_caveats(&"crud/create".parse()?, Caveats::any()),
+            Err(UcanError::AbilityNotFound(a)) if a.to_string() == "crud/create"
+        ));
+
+        assert_eq!(abilities.remove(&delete)?, Some(Caveats::any()));
+        assert_eq!(abilities.remove(&delete)?, None);
+        assert_eq!(abilities.remove(&update)?, Some(Caveats::any()));
+
+        // The last ability cannot be removed
+        assert!(matches!(abilities.remove(&read), Err(UcanError::NoAbility)));
+        assert_eq!(abilities.len(), 1);
+        assert!(abilities.get(&read).is_some());
+
+        // Removing a missing ability is fine even with a single entry left
+        assert_eq!(abilities.remove(&delete)?, None);
+
+        Ok(())
+    }
+
     #[test]
     fn test_capabilities_indexing() -> anyhow::Result<()> {
         let capabilities = caps! {
@@ -397,4 +558,65 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_capabilities_intersect() -> anyhow::Result<()> {
+        let granted = caps! {
+            "example://example.com/public/": {
+                "crud/read": [{}],
+                "crud/delete": [{ "public": true }],
+            },
+            "zerodb://app/users/": {
+                "db/table/*": [{}],
+            }
+        }?;
+
+        // Partial overlap
+        let requested = caps! {
+            "example://example.com/public/photos/": {
+                "crud/read": [{}],
+                "crud/delete": [{}],
+                "crud/update": [{}],
+            },
+            "zerodb://app/users/": {
+                "db/table/read": [{ "rate_limit": 100 }],
+            },
+            "mailto:username@example.com": {
+                "msg/send": [{}],
+            }
+        }?;
+
+        let intersection = granted.intersect(&requested);
+        assert_eq!(
+            intersection,
+            caps! {
+                "example://example.com/public/photos/": {
+                    "crud/read": [{}],
+                },
+                "zerodb://app/users/": {
+                    "db/table/read": [{ "rate_limit": 100 }],
+                }
+            }?
+        );
+
+        // Everything requested is permitted
+        assert_eq!(intersection.intersect(&intersection), intersection);
+        assert_eq!(granted.intersect(&intersection), intersection);
+
+        // Disjoint sets
+        let requested = caps! {
+            "example://example.com/private/": {
+                "crud/read": [{}],
+            },
+            "mailto:username@example.com": {
+                "msg/send": [{}],
+            }
+        }?;
+
+        assert!(granted.intersect(&requested).is_empty());
+        assert!(granted.intersect(&Capabilities::new()).is_empty());
+        assert!(Capabilities::new().intersect(&granted).is_empty());
+
+        Ok(())
+    }
 }
@@ -9,6 +9,15 @@ use serde_json::{Map, Value};
 
 use crate::{UcanError, UcanResult};
 
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The default maximum nesting depth of objects and arrays that is compared when checking caveats.
+///
+/// Caveats nested deeper than this are never permitted, so untrusted caveats can't exhaust the stack.
+pub const DEFAULT_MAX_CAVEAT_DEPTH: usize = 32;
+
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
@@ -82,6 +91,58 @@ impl Caveats {
         self.0.get(index)
     }
 
+    /// Combines the caveats with `other` so that the ability applies in the cases of either.
+    ///
+    /// The caveat arrays are concatenated. Since an empty caveat object applies in all cases and can't be
+    /// mixed with other caveats, the result is [`any`](Self::any) if either of the caveats is.
+    pub fn or(self, other: Caveats) -> UcanResult<Self> {
+        if self.is_any() || other.is_any() {
+            return Ok(Caveats::any());
+        }
+
+        let mut caveats = self.0;
+        for caveat in other.0 {
+            if !caveats.contains(&caveat) {
+                caveats.push(caveat);
+            }
+        }
+
+        Caveats::try_from_iter(caveats)
+    }
+
+    /// Adds the `fields` to every caveat object, further restricting each case the ability applies in.
+    ///
+    /// Fails if a caveat already has one of the fields with a different value, since the caveat could
+    /// never be satisfied.
+    pub fn and(self, fields: Map<String, Value>) -> UcanResult<Self> {
+        let caveats = self
+            .0
+            .into_iter()
+            .map(|caveat| {
+                let mut map = match caveat.0 {
+                    Value::Object(map) => map,
+                    value => return Err(UcanError::InvalidCaveat(value)),
+                };
+
+                for (key, value) in fields.iter() {
+                    match map.get(key) {
+                        Some(existing) if existing != value => {
+                            return Err(UcanError::InvalidCaveat(Value::Object(map)));
+                        }
+                        Some(_) => {}
+                        None => {
+                            map.insert(key.clone(), value.clone());
+                        }
+                    }
+                }
+
+                Ok(Caveat(Value::Object(map)))
+            })
+            .collect::<UcanResult<Vec<_>>>()?;
+
+        Caveats::try_from_iter(caveats)
+    }
+
     /// Checks if the given `requested` caveats are permitted by main caveats.
     ///
     /// An object in the caveat array represents a caveat. When checking the `requested` caveats array against
@@ -104,16 +165,23 @@ impl Caveats {
     /// For example, if the main caveats array contains `[{ "max_count": 5 }, { "status": "active" }]` and `requested` has
     /// `[{ "max_count": 5 }]`, the `requested` caveats are permitted. This is due to the `OR` semantics of the caveats array,
     /// the removal of a caveat object here adds additional restriction, reducing the number of valid cases.
+    ///
+    /// Caveats nested deeper than [`DEFAULT_MAX_CAVEAT_DEPTH`] are not permitted.
     pub fn permits(&self, requested: &Caveats) -> bool {
+        self.permits_with_max_depth(requested, DEFAULT_MAX_CAVEAT_DEPTH)
+    }
+
+    /// Checks if the given `requested` caveats are permitted by main caveats like [`permits`][Self::permits],
+    /// comparing nested objects and arrays down to `max_depth` levels only.
+    pub fn permits_with_max_depth(&self, requested: &Caveats, max_depth: usize) -> bool {
         if requested.len() > self.len() {
             return false;
         }
 
         for requested_caveat in requested.iter() {
-            if !self
-                .iter()
-                .any(|caveat| Caveat::is_subset(&caveat.0, &requested_caveat.0))
-            {
+            if !self.iter().any(|caveat| {
+                Caveat::is_subset_with_max_depth(&caveat.0, &requested_caveat.0, max_depth)
+            }) {
                 return false;
             }
         }
@@ -148,11 +216,22 @@ impl Caveat {
 
     /// Checks if the given `this` json value is a subset of the `that` json value. Nested fields are also taken into account.
     pub(crate) fn is_subset(this: &Value, that: &Value) -> bool {
+        Caveat::is_subset_with_max_depth(this, that, DEFAULT_MAX_CAVEAT_DEPTH)
+    }
+
+    /// Checks if the given `this` json value is a subset of the `that` json value, returning `false` if
+    /// objects or arrays are nested deeper than `max_depth`.
+    pub(crate) fn is_subset_with_max_depth(this: &Value, that: &Value, max_depth: usize) -> bool {
         match (this, that) {
+            (Value::Object(_), Value::Object(_)) | (Value::Array(_), Value::Array(_))
+                if max_depth == 0 =>
+            {
+                return false;
+            }
             (Value::Object(this_map), Value::Object(that_map)) => {
                 for (key, value) in this_map.iter() {
                     if let Some(that_value) = that_map.get(key) {
-                        if !Caveat::is_subset(value, that_value) {
+                        if !Caveat::is_subset_with_max_depth(value, that_value, max_depth - 1) {
                             return false;
                         }
                     } else {
@@ -166,7 +245,7 @@ impl Caveat {
                 }
 
                 for (this_value, that_value) in this_array.iter().zip(that_array.iter()) {
-                    if !Caveat::is_subset(this_value, that_value) {
+                    if !Caveat::is_subset_with_max_depth(this_value, that_value, max_depth - 1) {
                         return false;
                     }
                 }
@@ -262,6 +341,7 @@ impl Default for Caveat {
 
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
 
     use crate::caveats;
 
@@ -419,6 +499,38 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_caveats_permits_max_depth() -> anyhow::Result<()> {
+        fn nested(depth: usize) -> Value {
+            let mut value = json!(true);
+            for _ in 0..depth {
+                value = json!({ "a": [value] });
+            }
+
+            value
+        }
+
+        // Within the limit
+        let caveats = Caveats::try_from_iter([nested(DEFAULT_MAX_CAVEAT_DEPTH / 2)])?;
+        assert!(caveats.permits(&caveats));
+        assert!(!caveats.permits_with_max_depth(&caveats, 4));
+
+        // Pathologically nested caveats are rejected without overflowing the stack.
+        let caveats = Caveats::try_from_iter([nested(500)])?;
+        assert!(!caveats.permits(&caveats));
+
+        // Only the compared depth counts, so shallow main caveats still permit deep requests.
+        let main = caveats![{ "b": 1 }]?;
+        let requested = Caveats::try_from_iter([{
+            let mut value = nested(500);
+            value["b"] = json!(1);
+            value
+        }])?;
+        assert!(main.permits(&requested));
+
+        Ok(())
+    }
+
     #[test]
     fn test_caveats_permits() -> anyhow::Result<()> {
         let main = caveats![{}]?;
@@ -481,4 +593,65 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_caveats_or() -> anyhow::Result<()> {
+        let public = caveats![{ "public": true }]?;
+        let limited = caveats![{ "max_count": 5 }]?;
+
+        let combined = public.clone().or(limited.clone())?;
+        assert_eq!(combined, caveats![{ "public": true }, { "max_count": 5 }]?);
+        assert!(combined.permits(&public));
+        assert!(combined.permits(&limited));
+        assert!(!public.permits(&combined));
+
+        // Duplicates are dropped
+        assert_eq!(combined.clone().or(public.clone())?, combined);
+
+        // Any caveats absorb the others
+        assert!(combined.clone().or(Caveats::any())?.is_any());
+        assert!(Caveats::any().or(combined)?.is_any());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_caveats_and() -> anyhow::Result<()> {
+        let fields =
+            |value: Value| -> Map<String, Value> { value.as_object().cloned().unwrap_or_default() };
+
+        let caveats = caveats![{ "public": true }, { "max_count": 5 }]?;
+        let restricted = caveats.clone().and(fields(json!({ "status": "active" })))?;
+        assert_eq!(
+            restricted,
+            caveats![
+                { "public": true, "status": "active" },
+                { "max_count": 5, "status": "active" }
+            ]?
+        );
+        assert!(caveats.permits(&restricted));
+        assert!(!restricted.permits(&caveats));
+
+        // ANDing into any caveats yields a single restricted caveat
+        let restricted = Caveats::any().and(fields(json!({ "public": true })))?;
+        assert_eq!(restricted, caveats![{ "public": true }]?);
+        assert!(Caveats::any().permits(&restricted));
+
+        // ANDing no fields changes nothing
+        assert!(Caveats::any().and(Map::new())?.is_any());
+
+        // Fields that are already there with the same value are fine
+        assert_eq!(
+            caveats.clone().and(fields(json!({ "public": true })))?,
+            caveats![{ "public": true }, { "max_count": 5, "public": true }]?
+        );
+
+        // Conflicting fields can never be satisfied
+        assert!(matches!(
+            caveats.and(fields(json!({ "public": false }))),
+            Err(UcanError::InvalidCaveat(_))
+        ));
+
+        Ok(())
+    }
 }
@@ -171,7 +171,7 @@ impl FromStr for ProofReference<'_> {
             let scheme = captures.get(2).unwrap().as_str();
             Ok(ProofReference::AllUcansByDidAndScheme(
                 WrappedDidWebKey::from_str(did)?,
-                scheme.to_string(),
+                scheme.to_ascii_lowercase(),
             ))
         } else if let Some(captures) = UCAN_CID_REGEX.captures(s) {
             let cid = captures.get(1).unwrap().as_str();
@@ -213,6 +213,27 @@ impl TryFrom<Uri<String>> for NonUcanUri {
     type Error = UcanError;
 
     fn try_from(uri: Uri<String>) -> Result<Self, Self::Error> {
+        // Schemes are case-insensitive, but the rest of the URI is not.
+        // See https://datatracker.ietf.org/doc/html/rfc3986#section-3.1
+        let normalized = uri
+            .scheme()
+            .filter(|s| s.as_str().bytes().any(|b| b.is_ascii_uppercase()))
+            .map(|s| {
+                let scheme = s.as_str();
+                format!(
+                    "{}{}",
+                    scheme.to_ascii_lowercase(),
+                    &uri.as_str()[scheme.len()..]
+                )
+            });
+
+        let uri = match normalized {
+            Some(normalized) => {
+                Uri::parse_from(normalized).map_err(|(_, e)| UcanError::UriParseError(e))?
+            }
+            None => uri,
+        };
+
         if uri.scheme().map_or(false, |s| s.as_str() == "ucan") {
             return Err(UcanError::InvalidNonUcanUri(uri.to_string()));
         }
@@ -377,6 +398,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_uri_scheme_is_case_insensitive() -> anyhow::Result<()> {
+        let uri = NonUcanUri::from_str("ZeroFS://Photos/2024")?;
+        assert_eq!(uri.to_string(), "zerofs://Photos/2024");
+        assert_eq!(uri, NonUcanUri::from_str("zerofs://Photos/2024")?);
+        assert_ne!(uri, NonUcanUri::from_str("zerofs://photos/2024")?);
+
+        assert!(ResourceUri::from_str("ZEROFS://Photos")?
+            .permits(&ResourceUri::from_str("zerofs://Photos/2024")?));
+        assert!(!ResourceUri::from_str("zerofs://photos")?
+            .permits(&ResourceUri::from_str("ZeroFS://Photos/2024")?));
+
+        let uri = ResourceUri::from_str(
+            "ucan://did:wk:z6MkhZCL2zJsfqdqSLkGdocC3rkU436qYvK8bsnPdFCW1iXp/ZeroFS",
+        )?;
+        assert_eq!(
+            uri,
+            ResourceUri::from_str(
+                "ucan://did:wk:z6MkhZCL2zJsfqdqSLkGdocC3rkU436qYvK8bsnPdFCW1iXp/zerofs"
+            )?
+        );
+
+        assert!(NonUcanUri::from_str("UCAN:*").is_err());
+
+        Ok(())
+    }
+
     #[test]
     fn test_uri_display() -> anyhow::Result<()> {
         let uri = ResourceUri::Reference(ProofReference::AllUcansTransient);
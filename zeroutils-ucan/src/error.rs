@@ -9,8 +9,8 @@ use serde_json::Value;
 use thiserror::Error;
 
 use crate::{
-    Abilities, CapabilityTuple, Caveats, Trace, UnresolvedCapWithRootIss, UnresolvedUcanWithAud,
-    UnresolvedUcanWithCid,
+    Abilities, Ability, CapabilityTuple, Caveats, Trace, UnresolvedCapWithRootIss,
+    UnresolvedUcanWithAud, UnresolvedUcanWithCid,
 };
 
 //--------------------------------------------------------------------------------------------------
@@ -51,6 +51,10 @@ pub enum UcanError {
     #[error("The abilities map of a resource must contain at least one ability")]
     NoAbility,
 
+    /// Ability not found in the abilities map
+    #[error("Ability not found: {0}")]
+    AbilityNotFound(Ability),
+
     /// Caveats must contain at least an empty object
     #[error("Caveats must contain at least an empty object")]
     EmptyCaveats,
@@ -87,6 +91,10 @@ pub enum UcanError {
     #[error("UCAN expired: {0:?}")]
     Expired(Option<SystemTime>),
 
+    /// Proof UCAN expired
+    #[error("Proof UCAN expired: {0}")]
+    ProofExpired(Cid),
+
     /// UCAN not yet valid
     #[error("UCAN not yet valid: {0:?}")]
     NotYetValid(Option<SystemTime>),
@@ -119,10 +127,47 @@ pub enum UcanError {
     #[error("Utf8 error: {0}")]
     Utf8Error(#[from] std::str::Utf8Error),
 
+    /// Io error
+    #[error("Io error: {0}")]
+    IoError(#[from] std::io::Error),
+
+    /// An encoded token read from a stream is longer than the given maximum length in bytes
+    #[error("Token is longer than {0} bytes")]
+    TokenTooLong(usize),
+
     /// Proof Cid not found
     #[error("Proof Cid not found: {0}")]
     ProofCidNotFound(Cid),
 
+    /// Links of a linked UCAN node do not match the proofs of the UCAN
+    #[error("Links of linked UCAN node do not match its proofs: {0}")]
+    LinkedProofsMismatch(Cid),
+
+    /// A linked UCAN node links back to itself through its proofs
+    #[error("Linked UCAN node links back to itself: {0}")]
+    LinkedProofCycle(Cid),
+
+    /// The proof links of a linked UCAN are nested deeper than the given maximum depth
+    #[error("Linked UCAN proofs are nested deeper than {0}")]
+    LinkedDepthExceeded(usize),
+
+    /// Not enough valid signatures from the signer set of a multi-signed UCAN
+    #[error("Signature threshold not met: {0} valid signatures, {1} required")]
+    SignatureThresholdNotMet(usize, usize),
+
+    /// Signature length does not match its algorithm
+    #[error("Invalid signature length for {0}: expected {1} bytes, got {2}")]
+    InvalidSignatureLength(zeroutils_key::JwsAlgorithm, usize, usize),
+
+    /// Algorithm of a signature does not match the key of its signer, with the signer, the algorithm
+    /// of the signature and the algorithm of the key
+    #[error("Signature algorithm {1} does not match the key of signer {0}, expected {2}")]
+    SignatureAlgorithmMismatch(
+        String,
+        zeroutils_key::JwsAlgorithm,
+        zeroutils_key::JwsAlgorithm,
+    ),
+
     /// Principal alignment error
     #[error("Principal alignment failed: our issuer: {0}, their aud: {1}")]
     PrincipalAlignmentFailed(String, String),
@@ -143,17 +188,34 @@ pub enum UcanError {
     #[error(transparent)]
     PermissionError(#[from] PermissionError),
 
+    /// An error that occurred while processing a proof, with the CID of the proof and the trace leading to it
+    #[error("In proof {cid}: {source}")]
+    InProof {
+        /// The CID of the proof being processed.
+        cid: Cid,
+
+        /// The trace of CIDs from the proof up to the UCAN being resolved.
+        trace: Trace,
+
+        /// The error that occurred.
+        source: Box<UcanError>,
+    },
+
     /// Unresolved capabilities
     #[error("Unresolved capabilities: {0:?}")]
     UnresolvedCapabilities(Box<Unresolved>, Trace),
 
-    /// Invalid UCAN resource ability
-    #[error("Invalid UCAN resource ability: {0:?}. Expected `ucan/*`")]
-    InvalidUcanResourceAbility(Abilities),
+    /// Invalid abilities of a UCAN resource, with the resource and its abilities
+    #[error("Invalid abilities for UCAN resource `{0}`: {1:?}. Expected only `ucan/*`")]
+    InvalidUcanResourceAbility(String, Abilities),
+
+    /// Invalid caveats of a UCAN resource, with the resource and the caveats of its `ucan/*` ability
+    #[error("Invalid caveats for UCAN resource `{0}`: {1}. Expected `[{{}}]`")]
+    InvalidUcanResourceCaveats(String, Caveats),
 
-    /// Invalid UCAN resource caveats
-    #[error("Invalid UCAN resource caveats: {0}. Expected `[{{}}]`")]
-    InvalidUcanResourceCaveats(Caveats),
+    /// A field that the UCAN builder could not check at compile time is missing
+    #[error("Missing field: {0}")]
+    MissingField(String),
 
     /// Expiration constraint violated
     #[error("Expiration constraint violated: {0:?}, {1:?}")]
@@ -163,6 +225,14 @@ pub enum UcanError {
     #[error("Not before constraint violated: {0:?}, {1:?}")]
     NotBeforeConstraintViolated(Option<SystemTime>, Option<SystemTime>),
 
+    /// The nonce of the issuer was already seen, with the issuer and the nonce
+    #[error("Replayed nonce of issuer {0}: {1}")]
+    ReplayedNonce(String, String),
+
+    /// The nonce store is full of nonces that have not expired yet, with its capacity
+    #[error("Nonce store is full: {0} unexpired nonces")]
+    NonceStoreFull(usize),
+
     /// Custom error.
     #[error("Custom error: {0}")]
     Custom(#[from] AnyError),
@@ -233,6 +303,37 @@ impl UcanError {
             error: error.into(),
         })
     }
+
+    /// Wraps the error with the CID of the proof it occurred in and the trace leading to it.
+    pub fn context(self, cid: Cid, trace: Trace) -> UcanError {
+        UcanError::InProof {
+            cid,
+            trace,
+            source: Box::new(self),
+        }
+    }
+
+    /// Returns the error without any of the proof context around it.
+    pub fn root_cause(&self) -> &UcanError {
+        let mut error = self;
+        while let UcanError::InProof { source, .. } = error {
+            error = source;
+        }
+
+        error
+    }
+
+    /// Returns the CIDs of the proofs the error occurred in, from the outermost to the innermost proof.
+    pub fn proof_path(&self) -> Vec<Cid> {
+        let mut path = vec![];
+        let mut error = self;
+        while let UcanError::InProof { cid, source, .. } = error {
+            path.push(*cid);
+            error = source;
+        }
+
+        path
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
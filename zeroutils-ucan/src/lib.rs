@@ -30,6 +30,9 @@ mod capabilities;
 mod error;
 mod facts;
 mod header;
+mod linked;
+mod multisig;
+mod nonce;
 mod payload;
 mod proofs;
 mod resolution;
@@ -48,6 +51,9 @@ pub use capabilities::*;
 pub use error::*;
 pub use facts::*;
 pub use header::*;
+pub use linked::*;
+pub use multisig::*;
+pub use nonce::*;
 pub use payload::*;
 pub use proofs::*;
 pub use resolution::*;
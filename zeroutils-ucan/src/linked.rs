@@ -0,0 +1,325 @@
+use std::collections::BTreeMap;
+
+use async_once_cell::OnceCell;
+use async_recursion::async_recursion;
+use libipld::Cid;
+use serde::{Deserialize, Serialize};
+use zeroutils_store::cas::{IpldReferences, IpldStore};
+
+use crate::{SignedUcan, UcanError, UcanResult};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The maximum number of proof links followed from a linked UCAN when storing or loading it.
+pub const MAX_LINKED_DEPTH: usize = 64;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A DAG-CBOR representation of a signed UCAN where the proofs are stored as IPLD links.
+///
+/// Unlike the default [`Storable`][zeroutils_store::cas::Storable] representation which stores the
+/// encoded token as raw bytes, this makes the proof chain a DAG that generic IPLD tooling can traverse.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkedUcanNode {
+    /// The encoded signed UCAN.
+    pub ucan: String,
+
+    /// Links to the [`LinkedUcanNode`]s of the proofs of the UCAN.
+    pub prf: Vec<Cid>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<'a, S> SignedUcan<'a, S>
+where
+    S: IpldStore,
+{
+    /// Stores the UCAN and, recursively, its proofs as [`LinkedUcanNode`]s and returns the CID of the
+    /// UCAN's node.
+    ///
+    /// The proofs are fetched from the store of the UCAN.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UcanError::LinkedDepthExceeded`] if the proof chain is deeper than
+    /// [`MAX_LINKED_DEPTH`].
+    pub async fn store_linked(&self) -> UcanResult<Cid> {
+        self.store_linked_at(0).await
+    }
+
+    /// Loads a UCAN stored with [`store_linked`](Self::store_linked), following the links to its
+    /// proofs recursively.
+    ///
+    /// The loaded proofs are cached in the returned UCAN, so its capabilities can be resolved even if
+    /// the store only holds the linked nodes and not the encoded proofs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UcanError::LinkedProofsMismatch`] if the proofs linked from a node do not match the
+    /// proofs of its encoded UCAN, [`UcanError::LinkedProofCycle`] if a node links back to itself,
+    /// and [`UcanError::LinkedDepthExceeded`] if the links are nested deeper than
+    /// [`MAX_LINKED_DEPTH`].
+    pub async fn load_linked(cid: &Cid, store: S) -> UcanResult<Self> {
+        Self::load_linked_at(cid, store, &mut Vec::new()).await
+    }
+
+    #[async_recursion(?Send)]
+    async fn store_linked_at(&self, depth: usize) -> UcanResult<Cid> {
+        if depth > MAX_LINKED_DEPTH {
+            return Err(UcanError::LinkedDepthExceeded(MAX_LINKED_DEPTH));
+        }
+
+        let mut prf = Vec::with_capacity(self.payload.proofs.len());
+        for proof in self.payload.proofs.iter() {
+            let ucan = proof.fetch_ucan(&self.payload.store).await?;
+            prf.push(ucan.store_linked_at(depth + 1).await?);
+        }
+
+        let node = LinkedUcanNode {
+            ucan: self.to_string(),
+            prf,
+        };
+
+        Ok(self.payload.store.put_node(&node).await?)
+    }
+
+    /// Loads the linked UCAN at `cid`, where `path` holds the CIDs of the nodes that link to it.
+    #[async_recursion(?Send)]
+    async fn load_linked_at(cid: &Cid, store: S, path: &mut Vec<Cid>) -> UcanResult<Self>
+    where
+        S: 'async_recursion,
+    {
+        if path.contains(cid) {
+            return Err(UcanError::LinkedProofCycle(*cid));
+        }
+
+        if path.len() > MAX_LINKED_DEPTH {
+            return Err(UcanError::LinkedDepthExceeded(MAX_LINKED_DEPTH));
+        }
+
+        let node: LinkedUcanNode = store.get_node(cid).await?;
+        let mut ucan = SignedUcan::try_from_str(&node.ucan, store.clone())?;
+
+        path.push(*cid);
+        let mut linked = BTreeMap::new();
+        for link in node.prf.iter() {
+            let proof = SignedUcan::<'static, S>::load_linked_at(link, store.clone(), path).await?;
+            linked.insert(proof.cid().await?, proof);
+        }
+        path.pop();
+
+        if !linked
+            .keys()
+            .copied()
+            .eq(ucan.payload.proofs.iter().map(|proof| *proof.cid()))
+        {
+            return Err(UcanError::LinkedProofsMismatch(*cid));
+        }
+
+        for (proof_cid, proof) in linked {
+            ucan.payload
+                .proofs
+                .0
+                .insert(proof_cid, OnceCell::from(proof));
+        }
+
+        Ok(ucan)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl IpldReferences for LinkedUcanNode {
+    fn references<'a>(&'a self) -> Box<dyn Iterator<Item = &'a Cid> + Send + 'a> {
+        Box::new(self.prf.iter())
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        time::{Duration, SystemTime},
+    };
+
+    use zeroutils_store::cas::{MemoryStore, Storable};
+
+    use crate::{
+        caps,
+        resolution::tests::fixtures::{chain, delegate, principals, Chain, Principals},
+        Ucan,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ucan_linked_store_and_load() -> anyhow::Result<()> {
+        let now = SystemTime::now();
+        let store = MemoryStore::default();
+        let Principals {
+            keys: [p0, p1, ..],
+            dids: [_, p1_did, p2_did, _],
+        } = principals()?;
+
+        let proof0 = Ucan::builder()
+            .store(store.clone())
+            .audience(p1_did.clone())
+            .expiration(now + Duration::from_secs(720_000))
+            .capabilities(caps! {
+                "zerodb://": { "db/read": [{}] }
+            }?)
+            .sign(&p0)?;
+
+        let proof1 = Ucan::builder()
+            .store(store.clone())
+            .audience(p1_did)
+            .expiration(now + Duration::from_secs(720_000))
+            .capabilities(caps! {
+                "zerofs://": { "fs/read": [{}] }
+            }?)
+            .sign(&p0)?;
+
+        let cid0 = proof0.store().await?;
+        let cid1 = proof1.store().await?;
+
+        let ucan = delegate(
+            &p1,
+            &p2_did,
+            now + Duration::from_secs(360_000),
+            [cid0, cid1],
+            store.clone(),
+        )?;
+
+        let cid = ucan.store_linked().await?;
+
+        let node: LinkedUcanNode = store.get_node(&cid).await?;
+        let references = node.references().cloned().collect::<BTreeSet<_>>();
+        let linked0 = proof0.store_linked().await?;
+        let linked1 = proof1.store_linked().await?;
+        assert_eq!(references, [linked0, linked1].into_iter().collect());
+
+        let loaded = SignedUcan::load_linked(&cid, store).await?;
+        assert_eq!(loaded, ucan);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_linked_follows_links_of_links() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+        let Chain {
+            principals,
+            proof: proof0,
+            ucan: proof1,
+            ..
+        } = chain(
+            store.clone(),
+            caps! {
+                "zerodb://": { "db/read": [{}] }
+            }?,
+        )
+        .await?;
+
+        let [_, _, p2, _] = &principals.keys;
+        let [_, p1_did, ..] = &principals.dids;
+
+        let cid1 = proof1.store().await?;
+        let ucan = delegate(
+            p2,
+            p1_did,
+            SystemTime::now() + Duration::from_secs(10),
+            [cid1],
+            store.clone(),
+        )?;
+
+        let cid = ucan.store_linked().await?;
+
+        // Each node links to the node of its proof, down to the root proof.
+        let node: LinkedUcanNode = store.get_node(&cid).await?;
+        assert_eq!(node.prf, vec![proof1.store_linked().await?]);
+
+        let node1: LinkedUcanNode = store.get_node(&node.prf[0]).await?;
+        assert_eq!(node1.ucan, proof1.to_string());
+        assert_eq!(node1.prf, vec![proof0.store_linked().await?]);
+
+        let node0: LinkedUcanNode = store.get_node(&node1.prf[0]).await?;
+        assert_eq!(node0.ucan, proof0.to_string());
+        assert!(node0.prf.is_empty());
+
+        let loaded = SignedUcan::load_linked(&cid, store.clone()).await?;
+        assert_eq!(loaded, ucan);
+
+        // A node whose proof's own links do not match its proofs is rejected.
+        let bad_node1 = LinkedUcanNode {
+            ucan: proof1.to_string(),
+            prf: vec![],
+        };
+        let bad = LinkedUcanNode {
+            ucan: ucan.to_string(),
+            prf: vec![store.put_node(&bad_node1).await?],
+        };
+        let bad_cid = store.put_node(&bad).await?;
+        assert!(matches!(
+            SignedUcan::load_linked(&bad_cid, store).await,
+            Err(UcanError::LinkedProofsMismatch(cid)) if cid != bad_cid
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_linked_load_resolves_from_linked_nodes_only() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+        let Chain {
+            principals,
+            proof_cid: cid0,
+            ucan,
+            ..
+        } = chain(
+            store.clone(),
+            caps! {
+                "zerodb://": { "db/read": [{}] }
+            }?,
+        )
+        .await?;
+
+        let [p0, ..] = &principals.keys;
+
+        let cid = ucan.store_linked().await?;
+
+        // Only the linked nodes are copied, not the encoded proofs.
+        let linked_store = MemoryStore::default();
+        let mut pending = vec![cid];
+        while let Some(cid) = pending.pop() {
+            let node: LinkedUcanNode = store.get_node(&cid).await?;
+            pending.extend(node.prf.iter().copied());
+            assert_eq!(linked_store.put_node(&node).await?, cid);
+        }
+
+        assert!(!linked_store.has(&cid0).await);
+
+        let loaded = SignedUcan::load_linked(&cid, linked_store).await?;
+        let resolved = loaded.resolve_capabilities(p0).await?;
+        assert_eq!(
+            resolved
+                .iter()
+                .map(|tuple| (tuple.0.to_string(), tuple.1.to_string()))
+                .collect::<Vec<_>>(),
+            [("zerodb://".to_string(), "db/read".to_string())]
+        );
+
+        Ok(())
+    }
+}
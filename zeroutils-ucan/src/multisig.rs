@@ -0,0 +1,423 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
+use libipld::Cid;
+use serde::{Deserialize, Serialize};
+use zeroutils_did::{did_wk::WrappedDidWebKey, Base};
+use zeroutils_key::{GetPublicKey, JwsAlgName, JwsAlgorithm, Sign, Verify};
+use zeroutils_store::cas::{IpldStore, IpldStoreExt, Storable, StoreError, StoreResult};
+
+use crate::{Ucan, UcanError, UcanHeader, UcanPayload, UcanResult, UcanSignature, UnsignedUcan};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A set of signatures over the same UCAN, keyed by the DID of each signer.
+///
+/// This is used for UCANs that require M-of-N approval, where a UCAN is only valid if a threshold of
+/// signers from a known set have signed it.
+///
+/// Signers may use different key types, so each signature carries its own algorithm rather than
+/// relying on the `alg` of the UCAN header. When encoded, the signatures are a Base64 encoded JSON
+/// array of `{"iss", "alg", "sig"}` objects.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MultiSignature(BTreeMap<WrappedDidWebKey<'static>, (JwsAlgorithm, UcanSignature)>);
+
+/// Represents a [UCAN][ucan] token signed by multiple signers.
+///
+/// Every signer signs the same encoded header and payload. The issuer of the UCAN does not have to be
+/// one of the signers.
+///
+/// [ucan]: https://github.com/ucan-wg/spec
+pub type MultiSignedUcan<'a, S> = Ucan<'a, S, UcanHeader, MultiSignature>;
+
+/// A single signature of a [`MultiSignature`] in its encoded form.
+#[derive(Serialize, Deserialize)]
+struct MultiSignatureEntry {
+    iss: WrappedDidWebKey<'static>,
+    alg: JwsAlgorithm,
+    sig: String,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl MultiSignature {
+    /// Returns the algorithm and signature of the given signer.
+    pub fn get(&self, signer: &WrappedDidWebKey) -> Option<(JwsAlgorithm, &UcanSignature)> {
+        self.0
+            .iter()
+            .find(|(did, _)| *did == signer)
+            .map(|(_, (alg, signature))| (*alg, signature))
+    }
+
+    /// Returns an iterator over the signers, their algorithms and their signatures.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (&WrappedDidWebKey<'static>, JwsAlgorithm, &UcanSignature)> {
+        self.0
+            .iter()
+            .map(|(did, (alg, signature))| (did, *alg, signature))
+    }
+
+    /// Returns the number of signatures.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if there are no signatures.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a, S> UnsignedUcan<'a, S, UcanHeader>
+where
+    S: IpldStore,
+{
+    /// Converts the UCAN into a [`MultiSignedUcan`] without any signatures.
+    ///
+    /// Signatures can then be added with [`MultiSignedUcan::add_signature`].
+    pub fn into_multi_signed(self) -> MultiSignedUcan<'a, S> {
+        Ucan::from_parts(self.header, self.payload, MultiSignature::default())
+    }
+}
+
+impl<'a, S> MultiSignedUcan<'a, S>
+where
+    S: IpldStore,
+{
+    /// Signs the UCAN with the given keypair and adds the signature, replacing any previous signature
+    /// by the same signer.
+    pub fn add_signature<K>(&mut self, keypair: &K) -> UcanResult<()>
+    where
+        K: Sign + GetPublicKey + JwsAlgName,
+    {
+        let signer = WrappedDidWebKey::from_key(keypair, Base::Base58Btc)?;
+        let signature = keypair.sign(self.signing_input().as_bytes())?;
+        self.signature
+            .0
+            .insert(signer, (keypair.alg(), signature.into()));
+
+        Ok(())
+    }
+
+    /// Verifies that at least `threshold` of the `signers` have validly signed the UCAN.
+    ///
+    /// Signatures from DIDs that are not in `signers` are ignored, as are invalid signatures and
+    /// signatures whose algorithm does not match the key of their signer.
+    pub fn verify_signature<'b, 'c: 'b>(
+        &self,
+        signers: impl IntoIterator<Item = &'b WrappedDidWebKey<'c>>,
+        threshold: usize,
+    ) -> UcanResult<()> {
+        let signing_input = self.signing_input();
+
+        let mut seen = HashSet::new();
+        let valid = signers
+            .into_iter()
+            .filter(|signer| seen.insert(*signer))
+            .filter(|signer| {
+                self.signature.get(signer).is_some_and(|(alg, signature)| {
+                    let public_key = signer.public_key();
+                    public_key.alg() == alg
+                        && public_key
+                            .verify(signing_input.as_bytes(), signature)
+                            .is_ok()
+                })
+            })
+            .count();
+
+        if valid < threshold {
+            return Err(UcanError::SignatureThresholdNotMet(valid, threshold));
+        }
+
+        Ok(())
+    }
+
+    /// Attempts to create a `MultiSignedUcan` instance by parsing provided Base64 encoded string.
+    pub fn try_from_str(string: impl AsRef<str>, store: S) -> UcanResult<Self> {
+        let parts: Vec<&str> = string.as_ref().split('.').collect();
+
+        if parts.len() != 3 {
+            return Err(UcanError::UnableToParse);
+        }
+
+        let header = parts[0].parse()?;
+        let payload = UcanPayload::try_from_str(parts[1], store)?;
+        let signature: MultiSignature = parts[2].parse()?;
+
+        Ok(Ucan::from_parts(header, payload, signature))
+    }
+
+    fn signing_input(&self) -> String {
+        format!("{}.{}", self.header, self.payload)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl Display for MultiSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries = self
+            .0
+            .iter()
+            .map(|(did, (alg, signature))| MultiSignatureEntry {
+                iss: did.clone(),
+                alg: *alg,
+                sig: signature.to_string(),
+            })
+            .collect::<Vec<_>>();
+
+        let json = serde_json::to_string(&entries).map_err(|_| std::fmt::Error)?;
+        let encoded = BASE64_URL_SAFE_NO_PAD.encode(json.as_bytes());
+        write!(f, "{}", encoded)
+    }
+}
+
+impl FromStr for MultiSignature {
+    type Err = UcanError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decoded = BASE64_URL_SAFE_NO_PAD.decode(s.as_bytes())?;
+        let entries: Vec<MultiSignatureEntry> = serde_json::from_slice(&decoded)?;
+
+        let mut signatures = BTreeMap::new();
+        for MultiSignatureEntry { iss, alg, sig } in entries {
+            let key_alg = iss.public_key().alg();
+            if key_alg != alg {
+                return Err(UcanError::SignatureAlgorithmMismatch(
+                    iss.to_string(),
+                    alg,
+                    key_alg,
+                ));
+            }
+
+            let bytes = BASE64_URL_SAFE_NO_PAD.decode(sig.as_bytes())?;
+            let signature = UcanSignature::from_bytes(alg, &bytes)?;
+            if signatures.insert(iss, (alg, signature)).is_some() {
+                return Err(UcanError::UnableToParse);
+            }
+        }
+
+        Ok(MultiSignature(signatures))
+    }
+}
+
+impl<'a, S> Display for MultiSignedUcan<'a, S>
+where
+    S: IpldStore,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.header, self.payload, self.signature)
+    }
+}
+
+impl<'a, S> Storable<S> for MultiSignedUcan<'a, S>
+where
+    S: IpldStore,
+{
+    async fn store(&self) -> StoreResult<Cid> {
+        let encoded = self.to_string();
+        self.payload.store.put_bytes(encoded.as_bytes()).await
+    }
+
+    async fn load(cid: &Cid, store: S) -> StoreResult<Self> {
+        let bytes = store.read_all(cid).await?;
+        let encoded = std::str::from_utf8(&bytes).map_err(StoreError::custom)?;
+        MultiSignedUcan::try_from_str(encoded, store).map_err(StoreError::custom)
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate, P256KeyPair};
+    use zeroutils_store::cas::{MemoryStore, PlaceholderStore};
+
+    use crate::caps;
+
+    use super::*;
+
+    fn unsigned_ucan(
+        issuer: &Ed25519KeyPair,
+    ) -> anyhow::Result<UnsignedUcan<'static, PlaceholderStore, UcanHeader>> {
+        unsigned_ucan_with_store(issuer, PlaceholderStore)
+    }
+
+    fn unsigned_ucan_with_store<S>(
+        issuer: &Ed25519KeyPair,
+        store: S,
+    ) -> anyhow::Result<UnsignedUcan<'static, S, UcanHeader>>
+    where
+        S: IpldStore,
+    {
+        let ucan = Ucan::builder()
+            .store(store)
+            .issuer(WrappedDidWebKey::from_key(issuer, Base::Base58Btc)?)
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .expiration(SystemTime::now() + Duration::from_secs(60))
+            .capabilities(caps! {
+                "zerodb://": { "db/read": [{}] }
+            }?)
+            .build()
+            .use_alg(JwsAlgorithm::EdDSA);
+
+        Ok(ucan)
+    }
+
+    #[test]
+    fn test_multi_signed_ucan_threshold_met() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let k0 = Ed25519KeyPair::generate(rng)?;
+        let k1 = Ed25519KeyPair::generate(rng)?;
+        let k2 = P256KeyPair::generate(rng)?;
+        let signers = [
+            WrappedDidWebKey::from_key(&k0, Base::Base58Btc)?,
+            WrappedDidWebKey::from_key(&k1, Base::Base58Btc)?,
+            WrappedDidWebKey::from_key(&k2, Base::Base64)?,
+        ];
+
+        let mut ucan = unsigned_ucan(&k0)?.into_multi_signed();
+        ucan.add_signature(&k0)?;
+        ucan.add_signature(&k2)?;
+
+        assert_eq!(ucan.signature().len(), 2);
+        ucan.verify_signature(&signers, 2)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_signed_ucan_threshold_not_met() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let k0 = Ed25519KeyPair::generate(rng)?;
+        let k1 = Ed25519KeyPair::generate(rng)?;
+        let k2 = Ed25519KeyPair::generate(rng)?;
+        let outsider = Ed25519KeyPair::generate(rng)?;
+        let signers = [
+            WrappedDidWebKey::from_key(&k0, Base::Base58Btc)?,
+            WrappedDidWebKey::from_key(&k1, Base::Base58Btc)?,
+            WrappedDidWebKey::from_key(&k2, Base::Base58Btc)?,
+        ];
+
+        let mut ucan = unsigned_ucan(&k0)?.into_multi_signed();
+        ucan.add_signature(&k1)?;
+
+        // Signatures from outside the signer set do not count.
+        ucan.add_signature(&outsider)?;
+
+        assert!(matches!(
+            ucan.verify_signature(&signers, 2),
+            Err(UcanError::SignatureThresholdNotMet(1, 2))
+        ));
+
+        // Signing the same UCAN twice does not count twice.
+        ucan.add_signature(&k1)?;
+        assert!(ucan.verify_signature(&signers, 2).is_err());
+
+        // A tampered payload invalidates the signatures.
+        ucan.add_signature(&k2)?;
+        ucan.verify_signature(&signers, 2)?;
+        ucan.payload.nonce = Some("tampered".into());
+        assert!(matches!(
+            ucan.verify_signature(&signers, 2),
+            Err(UcanError::SignatureThresholdNotMet(0, 2))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_signed_ucan_display_parse_roundtrip() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let k0 = Ed25519KeyPair::generate(rng)?;
+        let k1 = P256KeyPair::generate(rng)?;
+        let signers = [
+            WrappedDidWebKey::from_key(&k0, Base::Base58Btc)?,
+            WrappedDidWebKey::from_key(&k1, Base::Base58Btc)?,
+        ];
+
+        let mut ucan = unsigned_ucan(&k0)?.into_multi_signed();
+        ucan.add_signature(&k0)?;
+        ucan.add_signature(&k1)?;
+
+        let encoded = ucan.to_string();
+        let parsed = MultiSignedUcan::try_from_str(&encoded, PlaceholderStore)?;
+
+        assert_eq!(parsed.header(), ucan.header());
+        assert_eq!(parsed.payload(), ucan.payload());
+        assert_eq!(parsed.signature(), ucan.signature());
+        assert_eq!(parsed.to_string(), encoded);
+
+        // Each signature keeps its own algorithm.
+        assert_eq!(
+            parsed.signature().get(&signers[0]).map(|(alg, _)| alg),
+            Some(JwsAlgorithm::EdDSA)
+        );
+        assert_eq!(
+            parsed.signature().get(&signers[1]).map(|(alg, _)| alg),
+            Some(JwsAlgorithm::ES256)
+        );
+
+        parsed.verify_signature(&signers, 2)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_signature_parse_fails_on_algorithm_mismatch() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let k0 = Ed25519KeyPair::generate(rng)?;
+
+        let mut ucan = unsigned_ucan(&k0)?.into_multi_signed();
+        ucan.add_signature(&k0)?;
+
+        let json = String::from_utf8(BASE64_URL_SAFE_NO_PAD.decode(ucan.signature().to_string())?)?;
+        let tampered = BASE64_URL_SAFE_NO_PAD.encode(json.replace("EdDSA", "ES256"));
+
+        assert!(matches!(
+            tampered.parse::<MultiSignature>(),
+            Err(UcanError::SignatureAlgorithmMismatch(
+                _,
+                JwsAlgorithm::ES256,
+                JwsAlgorithm::EdDSA
+            ))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_multi_signed_ucan_store_load() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        let k0 = Ed25519KeyPair::generate(rng)?;
+        let k1 = Ed25519KeyPair::generate(rng)?;
+        let store = MemoryStore::default();
+
+        let mut ucan = unsigned_ucan_with_store(&k0, store.clone())?.into_multi_signed();
+        ucan.add_signature(&k0)?;
+        ucan.add_signature(&k1)?;
+
+        let cid = ucan.store().await?;
+        let loaded = MultiSignedUcan::load(&cid, store).await?;
+
+        assert_eq!(loaded.to_string(), ucan.to_string());
+        assert_eq!(loaded.signature(), ucan.signature());
+
+        Ok(())
+    }
+}
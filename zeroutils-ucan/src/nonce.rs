@@ -0,0 +1,183 @@
+use std::{collections::HashMap, future::Future, sync::Mutex, time::SystemTime};
+
+use zeroutils_did::did_wk::WrappedDidWebKey;
+
+use crate::{UcanError, UcanResult};
+
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The default number of unexpired nonces a [`MemoryNonceStore`] holds.
+pub const DEFAULT_NONCE_CAPACITY: usize = 65_536;
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// A [`NonceStore`] that keeps the seen nonces in memory.
+///
+/// Each nonce is kept until the UCAN it came from expires, and expired nonces are pruned on insert.
+/// Nonces of UCANs that never expire are kept forever. Once the store holds `capacity` unexpired
+/// nonces, new ones are rejected with [`UcanError::NonceStoreFull`] instead of forgetting nonces that
+/// could still be replayed.
+#[derive(Debug)]
+pub struct MemoryNonceStore {
+    seen: Mutex<SeenNonces>,
+    capacity: usize,
+}
+
+/// The seen nonces keyed by the canonical bytes of their issuer and the nonce, with the expiration
+/// of the UCAN they came from.
+type SeenNonces = HashMap<(Vec<u8>, String), Option<SystemTime>>;
+
+//--------------------------------------------------------------------------------------------------
+// Traits
+//--------------------------------------------------------------------------------------------------
+
+/// A trait for tracking the nonces that issuers have used, so that replayed UCANs can be rejected.
+pub trait NonceStore {
+    /// Records the `nonce` of `issuer`, returning `false` if it was already recorded.
+    ///
+    /// `expiration` is the `exp` of the UCAN. The nonce only has to be remembered until then, as the
+    /// UCAN is rejected afterwards anyway. `None` means the UCAN never expires.
+    fn insert(
+        &self,
+        issuer: &WrappedDidWebKey,
+        nonce: &str,
+        expiration: Option<SystemTime>,
+    ) -> impl Future<Output = UcanResult<bool>>;
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl MemoryNonceStore {
+    /// Creates a new empty `MemoryNonceStore` that holds up to [`DEFAULT_NONCE_CAPACITY`] nonces.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_NONCE_CAPACITY)
+    }
+
+    /// Creates a new empty `MemoryNonceStore` that holds up to `capacity` unexpired nonces.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            seen: Mutex::new(HashMap::new()),
+            capacity,
+        }
+    }
+
+    /// Gets the number of nonces in the store, including expired ones that were not pruned yet.
+    pub fn len(&self) -> usize {
+        self.seen.lock().unwrap().len()
+    }
+
+    /// Checks if the store is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl NonceStore for MemoryNonceStore {
+    async fn insert(
+        &self,
+        issuer: &WrappedDidWebKey<'_>,
+        nonce: &str,
+        expiration: Option<SystemTime>,
+    ) -> UcanResult<bool> {
+        let now = SystemTime::now();
+        let mut seen = self.seen.lock().unwrap();
+        seen.retain(|_, exp| exp.is_none_or(|exp| exp > now));
+
+        // Issuers are keyed by their canonical bytes, so the same key in another base is the same issuer.
+        let key = (issuer.canonical_bytes(), nonce.to_string());
+        if seen.contains_key(&key) {
+            return Ok(false);
+        }
+
+        if seen.len() >= self.capacity {
+            return Err(UcanError::NonceStoreFull(self.capacity));
+        }
+
+        seen.insert(key, expiration);
+        Ok(true)
+    }
+}
+
+impl Default for MemoryNonceStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use zeroutils_did::Base;
+    use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_memory_nonce_store_insert() -> anyhow::Result<()> {
+        let store = MemoryNonceStore::new();
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let issuer = WrappedDidWebKey::from_key(&key_pair, Base::Base58Btc)?;
+        let other = WrappedDidWebKey::from_key(
+            &Ed25519KeyPair::generate(&mut rand::thread_rng())?,
+            Base::Base58Btc,
+        )?;
+
+        let expiration = Some(SystemTime::now() + Duration::from_secs(3_600));
+
+        assert!(store.insert(&issuer, "abc", expiration).await?);
+        assert!(!store.insert(&issuer, "abc", expiration).await?);
+        assert!(store.insert(&issuer, "def", expiration).await?);
+        assert!(store.insert(&other, "abc", expiration).await?);
+
+        // The same issuer in another base
+        let issuer = WrappedDidWebKey::from_key(&key_pair, Base::Base64)?;
+        assert!(!store.insert(&issuer, "abc", expiration).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_nonce_store_prunes_expired_nonces() -> anyhow::Result<()> {
+        let store = MemoryNonceStore::with_capacity(2);
+        let issuer = WrappedDidWebKey::from_key(
+            &Ed25519KeyPair::generate(&mut rand::thread_rng())?,
+            Base::Base58Btc,
+        )?;
+        let expiring = Some(SystemTime::now() + Duration::from_millis(100));
+        let unexpired = Some(SystemTime::now() + Duration::from_secs(3_600));
+
+        assert!(store.insert(&issuer, "abc", expiring).await?);
+        assert!(store.insert(&issuer, "def", None).await?);
+        assert_eq!(store.len(), 2);
+
+        // Once it expires, the first nonce is pruned to make room.
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert!(store.insert(&issuer, "ghi", unexpired).await?);
+        assert_eq!(store.len(), 2);
+
+        // Unexpired nonces are never evicted.
+        assert!(matches!(
+            store.insert(&issuer, "jkl", unexpired).await,
+            Err(UcanError::NonceStoreFull(2))
+        ));
+        assert!(!store.insert(&issuer, "def", unexpired).await?);
+        assert!(!store.insert(&issuer, "ghi", unexpired).await?);
+
+        Ok(())
+    }
+}
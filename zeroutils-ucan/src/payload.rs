@@ -4,7 +4,6 @@ use std::{
     collections::BTreeSet,
     fmt::{Debug, Display},
     marker::PhantomData,
-    str::FromStr,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -14,8 +13,8 @@ use serde::{
     de::{self, DeserializeSeed},
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use zeroutils_did::did_wk::WrappedDidWebKey;
-use zeroutils_store::cas::IpldStore;
+use zeroutils_did::{did_wk::WrappedDidWebKey, DidError};
+use zeroutils_store::cas::{Codec, IpldStore};
 
 use crate::{Capabilities, Facts, Proofs, UcanError, UcanResult};
 
@@ -31,6 +30,9 @@ pub const VERSION: &str = "0.10.0-alpha.1";
 //--------------------------------------------------------------------------------------------------
 
 /// Represents the payload part of a UCAN token, which contains all the claims and data necessary for the authorization process.
+///
+/// `exp` and `nbf` are encoded as whole seconds since the Unix epoch, so times are truncated to
+/// whole seconds wherever they are built, compared or validated.
 pub struct UcanPayload<'a, S>
 where
     S: IpldStore,
@@ -149,6 +151,24 @@ where
     pub fn store(&self) -> &S {
         &self.store
     }
+
+    /// Rebinds the payload and its proofs to another store.
+    pub fn use_store<T>(self, store: T) -> UcanPayload<'a, T>
+    where
+        T: IpldStore,
+    {
+        UcanPayload {
+            issuer: self.issuer,
+            audience: self.audience,
+            expiration: self.expiration,
+            not_before: self.not_before,
+            nonce: self.nonce,
+            facts: self.facts,
+            capabilities: self.capabilities,
+            proofs: self.proofs.use_store(&store),
+            store,
+        }
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -166,26 +186,50 @@ where
     }
 
     /// Checks if the UCAN's time bounds (`exp`, `nbf`) are valid relative to the current time (`now`).
+    ///
+    /// All times are compared truncated to whole seconds.
     pub fn validate_time_bounds(&self) -> UcanResult<()> {
-        if self.expiration < self.not_before {
+        let expiration = self.expiration.map(truncate_to_secs);
+        let not_before = self.not_before.map(truncate_to_secs);
+        if expiration < not_before {
             return Err(UcanError::InvalidTimeBounds(
                 self.not_before,
                 self.expiration,
             ));
         }
 
-        let now = SystemTime::now();
-        if self.expiration.map_or(false, |t| t < now) {
+        let now = truncate_to_secs(SystemTime::now());
+        if expiration.map_or(false, |t| t < now) {
             return Err(UcanError::Expired(self.expiration));
         }
 
-        if self.not_before.map_or(false, |t| t > now) {
+        if not_before.map_or(false, |t| t > now) {
             return Err(UcanError::NotYetValid(self.not_before));
         }
 
         Ok(())
     }
 
+    /// Returns how long the UCAN remains valid, or `None` if it never expires.
+    ///
+    /// Like [`is_expired`](Self::is_expired), this works in whole seconds, and the UCAN stays valid
+    /// until the end of the second of its expiration. Returns [`Duration::ZERO`] exactly when the UCAN
+    /// has expired.
+    pub fn time_remaining(&self) -> Option<Duration> {
+        let now = truncate_to_secs(SystemTime::now());
+        self.expiration.map(|t| {
+            (truncate_to_secs(t) + Duration::from_secs(1))
+                .duration_since(now)
+                .unwrap_or(Duration::ZERO)
+        })
+    }
+
+    /// Returns `true` if the expiration time of the UCAN has passed.
+    pub fn is_expired(&self) -> bool {
+        let now = truncate_to_secs(SystemTime::now());
+        self.expiration.map_or(false, |t| truncate_to_secs(t) < now)
+    }
+
     /// Deserializes to a 'UcanPayload' using an arbitrary deserializer and store.
     pub fn deserialize_with<'de>(
         deserializer: impl Deserializer<'de, Error: Into<UcanError>>,
@@ -221,24 +265,23 @@ where
             //     )));
             // }
 
+            // Proofs are either raw encoded UCANs or DAG-CBOR nodes like the roots of `put_bytes`.
             let codec = cid.codec();
-            if codec != 0x55 {
+            if !matches!(Codec::try_from(codec), Ok(Codec::Raw | Codec::DagCbor)) {
                 return Err(UcanError::InvalidProofCidCodec(codec));
             }
         }
 
-        let issuer = WrappedDidWebKey::from_str(&serializable.iss).map_err(UcanError::from)?;
-        let audience = WrappedDidWebKey::from_str(&serializable.aud).map_err(UcanError::from)?;
-
-        // `did:wk` with locator component not supported for issuer
-        if issuer.locator_component().is_some() {
-            return Err(UcanError::UnsupportedDidWkLocator(issuer.to_string()));
-        }
+        // `did:wk` with locator component not supported for issuer and audience
+        let parse_did = |did: &str| {
+            WrappedDidWebKey::from_str_no_locator(did).map_err(|e| match e {
+                DidError::UnexpectedLocator(did) => UcanError::UnsupportedDidWkLocator(did),
+                e => UcanError::from(e),
+            })
+        };
 
-        // `did:wk` with locator component not supported for audience
-        if audience.locator_component().is_some() {
-            return Err(UcanError::UnsupportedDidWkLocator(audience.to_string()));
-        }
+        let issuer = parse_did(&serializable.iss)?;
+        let audience = parse_did(&serializable.aud)?;
 
         Ok(UcanPayload {
             issuer,
@@ -267,6 +310,18 @@ impl<'a, S> UcanPayloadDeserializeSeed<'a, S> {
     }
 }
 
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Truncates `time` to the whole seconds that `exp` and `nbf` are encoded with.
+///
+/// Times before the Unix epoch cannot be encoded and are returned as is.
+pub(crate) fn truncate_to_secs(time: SystemTime) -> SystemTime {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(time, |d| UNIX_EPOCH + Duration::from_secs(d.as_secs()))
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
@@ -286,7 +341,8 @@ where
             nbf: value
                 .not_before
                 .map(|t| t.duration_since(UNIX_EPOCH).unwrap().as_secs()),
-            nnc: value.nonce.clone(),
+            // An empty nonce provides no replay protection, so it is omitted like an absent one.
+            nnc: value.nonce.clone().filter(|nonce| !nonce.is_empty()),
             fct: value.facts.clone(),
             cap: value.capabilities.clone(),
             prf: value.proofs.iter().map(|prf| *prf.cid()).collect(),
@@ -358,13 +414,8 @@ where
     fn eq(&self, other: &Self) -> bool {
         self.issuer == other.issuer
             && self.audience == other.audience
-            && self
-                .expiration
-                .map(|t| t.duration_since(UNIX_EPOCH).unwrap().as_secs())
-                == other
-                    .expiration
-                    .map(|t| t.duration_since(UNIX_EPOCH).unwrap().as_secs())
-            && self.not_before == other.not_before
+            && self.expiration.map(truncate_to_secs) == other.expiration.map(truncate_to_secs)
+            && self.not_before.map(truncate_to_secs) == other.not_before.map(truncate_to_secs)
             && self.nonce == other.nonce
             && self.facts == other.facts
             && self.capabilities == other.capabilities
@@ -477,6 +528,77 @@ mod tests {
         Ok(())
     }
 
+    #[test_log::test]
+    fn test_payload_rejects_did_wk_locator() -> anyhow::Result<()> {
+        let deserialize = |iss: &str, aud: &str| {
+            let serialized = format!(
+                r#"{{"ucv":"0.10.0-alpha.1","iss":"{iss}","aud":"{aud}","exp":null,"cap":{{}}}}"#
+            );
+
+            UcanPayload::deserialize_with(
+                &mut serde_json::Deserializer::from_str(&serialized),
+                PlaceholderStore,
+            )
+        };
+
+        let did = "did:wk:z6MkktN9TYbYWDPFBhEEZXeD9MyZyUZ2yRNSj5BzDyLBKLkd";
+        let did_with_locator = format!("{did}@steve.zerocore.ai:8080/public");
+
+        assert!(deserialize(did, did).is_ok());
+
+        for (iss, aud) in [
+            (did_with_locator.as_str(), did),
+            (did, did_with_locator.as_str()),
+        ] {
+            let err = deserialize(iss, aud).unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("Unsupported did:wk with locator component"));
+        }
+
+        Ok(())
+    }
+
+    #[test_log::test]
+    fn test_payload_time_remaining() -> anyhow::Result<()> {
+        let payload = |expiration| UcanPayload {
+            issuer: WrappedDidWebKey::from_str(
+                "did:wk:z6MkktN9TYbYWDPFBhEEZXeD9MyZyUZ2yRNSj5BzDyLBKLkd",
+            )
+            .unwrap(),
+            audience: WrappedDidWebKey::from_str(
+                "did:wk:m7QEI0Bnl9ShoGr1rc0+TQY64QH5hWC011zNh+CS96kg5Vw",
+            )
+            .unwrap(),
+            expiration,
+            not_before: None,
+            nonce: None,
+            facts: None,
+            capabilities: Capabilities::default(),
+            proofs: Proofs::default(),
+            store: PlaceholderStore,
+        };
+
+        // Expired
+        let expired = payload(Some(SystemTime::now() - Duration::from_secs(60)));
+        assert!(expired.is_expired());
+        assert_eq!(expired.time_remaining(), Some(Duration::ZERO));
+
+        // Expires in the future
+        let future = payload(Some(SystemTime::now() + Duration::from_secs(3600)));
+        assert!(!future.is_expired());
+        let remaining = future.time_remaining().unwrap();
+        assert!(remaining > Duration::from_secs(3500) && remaining <= Duration::from_secs(3601));
+        assert_eq!(remaining.subsec_nanos(), 0);
+
+        // Never expires
+        let no_expiry = payload(None);
+        assert!(!no_expiry.is_expired());
+        assert_eq!(no_expiry.time_remaining(), None);
+
+        Ok(())
+    }
+
     #[test_log::test]
     fn test_payload_display() -> anyhow::Result<()> {
         let issuer =
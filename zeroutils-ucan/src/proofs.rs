@@ -1,6 +1,9 @@
 use std::{
-    collections::{BTreeMap, BTreeSet},
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::Debug,
+    future::Future,
+    time::{Duration, Instant},
 };
 
 use async_once_cell::OnceCell;
@@ -10,6 +13,13 @@ use zeroutils_store::cas::{IpldStore, IpldStoreExt, PlaceholderStore};
 
 use crate::{SignedUcan, UcanError, UcanResult};
 
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The time a proof CID found missing during a resolution is not fetched again.
+pub const DEFAULT_MISS_TTL: Duration = Duration::from_secs(30);
+
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
@@ -35,10 +45,84 @@ where
     cache: &'a CachedUcan<S>,
 }
 
+/// Resolves proofs in the default way, by reading the encoded UCAN from the store by its CID.
+#[derive(Debug, Clone)]
+pub struct StoreResolver<S>
+where
+    S: IpldStore,
+{
+    store: S,
+}
+
+/// The proof CIDs found missing during a single resolution.
+///
+/// Each resolution creates its own cache and drops it when done, so known-missing CIDs are not
+/// fetched again within the resolution. Entries expire after a TTL, after which the CID is fetched
+/// again.
+#[derive(Debug)]
+pub(crate) struct MissCache {
+    entries: RefCell<HashMap<Cid, Instant>>,
+    ttl: Duration,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Traits
+//--------------------------------------------------------------------------------------------------
+
+/// A trait for fetching the UCAN of a proof CID.
+///
+/// This allows proofs to be served from somewhere other than the store, like an HTTP endpoint or a
+/// DHT.
+pub trait ProofResolver<S>
+where
+    S: IpldStore,
+{
+    /// Resolves the UCAN with the given CID.
+    fn resolve(&self, cid: &Cid) -> impl Future<Output = UcanResult<SignedUcan<'static, S>>>;
+}
+
 //--------------------------------------------------------------------------------------------------
 // Methods
 //--------------------------------------------------------------------------------------------------
 
+impl<S> StoreResolver<S>
+where
+    S: IpldStore,
+{
+    /// Creates a resolver that reads proofs from the given store.
+    pub fn new(store: S) -> Self {
+        Self { store }
+    }
+}
+
+impl MissCache {
+    /// Creates a cache that remembers missing CIDs for `ttl` each.
+    pub(crate) fn new(ttl: Duration) -> Self {
+        Self {
+            entries: RefCell::new(HashMap::new()),
+            ttl,
+        }
+    }
+
+    /// Checks if `cid` was recently found missing.
+    pub(crate) fn contains(&self, cid: &Cid) -> bool {
+        let mut entries = self.entries.borrow_mut();
+        match entries.get(cid) {
+            Some(missed_at) if missed_at.elapsed() < self.ttl => true,
+            Some(_) => {
+                entries.remove(cid);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Remembers that `cid` is missing.
+    pub(crate) fn insert(&self, cid: Cid) {
+        self.entries.borrow_mut().insert(cid, Instant::now());
+    }
+}
+
 impl<S> Proofs<S>
 where
     S: IpldStore,
@@ -53,15 +137,21 @@ where
         &'b self,
         cid: &Cid,
         store: &'b S,
+    ) -> UcanResult<&'b SignedUcan<S>> {
+        self.fetch_ucan_with(cid, &StoreResolver::new(store.clone()))
+            .await
+    }
+
+    /// Fetches the UCAN associated with the given proof CID using `resolver`.
+    pub async fn fetch_ucan_with<'b>(
+        &'b self,
+        cid: &Cid,
+        resolver: &impl ProofResolver<S>,
     ) -> UcanResult<&'b SignedUcan<S>> {
         self.0
             .get(cid)
             .ok_or(UcanError::ProofCidNotFound(*cid))?
-            .get_or_try_init(async {
-                let bytes = store.read_all(cid).await?;
-                let ucan_str = std::str::from_utf8(&bytes)?;
-                SignedUcan::try_from_str(ucan_str, store.clone())
-            })
+            .get_or_try_init(resolver.resolve(cid))
             .await
     }
 
@@ -89,6 +179,27 @@ where
     pub fn get<'b>(&'b self, cid: &Cid) -> Option<Proof<'b, S>> {
         self.0.get(cid).map(|cache| Proof { cid: *cid, cache })
     }
+
+    /// Rebinds the proofs to another store.
+    ///
+    /// UCANs that were already fetched stay cached and are rebound along with their own proofs, so
+    /// they don't have to be fetched again from the new store.
+    pub fn use_store<T>(self, store: &T) -> Proofs<T>
+    where
+        T: IpldStore,
+    {
+        self.0
+            .into_iter()
+            .map(|(cid, cache)| {
+                let cache = match cache.into_inner() {
+                    Some(ucan) => OnceCell::from(ucan.use_store(store.clone())),
+                    None => OnceCell::new(),
+                };
+
+                (cid, cache)
+            })
+            .collect()
+    }
 }
 
 impl<S> Proof<'_, S>
@@ -97,12 +208,17 @@ where
 {
     /// Fetches the UCAN associated with the proof from the store.
     pub async fn fetch_ucan<'b>(&'b self, store: &'b S) -> UcanResult<&'b SignedUcan<S>> {
+        self.fetch_ucan_with(&StoreResolver::new(store.clone()))
+            .await
+    }
+
+    /// Fetches the UCAN associated with the proof using `resolver`.
+    pub async fn fetch_ucan_with(
+        &self,
+        resolver: &impl ProofResolver<S>,
+    ) -> UcanResult<&SignedUcan<S>> {
         self.cache
-            .get_or_try_init(async {
-                let bytes = store.read_all(&self.cid).await?;
-                let ucan_str = std::str::from_utf8(&bytes)?;
-                SignedUcan::try_from_str(ucan_str, store.clone())
-            })
+            .get_or_try_init(resolver.resolve(&self.cid))
             .await
     }
 
@@ -110,6 +226,32 @@ where
     pub fn cid(&self) -> &Cid {
         &self.cid
     }
+
+    /// Gets the UCAN associated with the proof if it has already been fetched.
+    pub fn cached_ucan(&self) -> Option<&SignedUcan<'static, S>> {
+        self.cache.get()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations: StoreResolver
+//--------------------------------------------------------------------------------------------------
+
+impl<S> ProofResolver<S> for StoreResolver<S>
+where
+    S: IpldStore,
+{
+    async fn resolve(&self, cid: &Cid) -> UcanResult<SignedUcan<'static, S>> {
+        let bytes = self.store.read_all(cid).await?;
+        let ucan_str = std::str::from_utf8(&bytes)?;
+        SignedUcan::try_from_str(ucan_str, self.store.clone())
+    }
+}
+
+impl Default for MissCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_MISS_TTL)
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -303,6 +445,22 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_miss_cache_expires() -> anyhow::Result<()> {
+        let cid = Cid::from_str("bafkreih43byuv2f6ils5kpsj2qwzbwgdd2pqzs6anwm3nhfrhlagqjektm")?;
+
+        let misses = MissCache::default();
+        assert!(!misses.contains(&cid));
+        misses.insert(cid);
+        assert!(misses.contains(&cid));
+
+        let misses = MissCache::new(Duration::ZERO);
+        misses.insert(cid);
+        assert!(!misses.contains(&cid));
+
+        Ok(())
+    }
+
     #[test]
     fn test_proofs_serde() -> anyhow::Result<()> {
         let proofs = Proofs::from_iter(vec![
@@ -1,7 +1,7 @@
 mod resolution;
 mod resolved;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
 mod unresolved;
 
 //--------------------------------------------------------------------------------------------------
@@ -4,14 +4,15 @@ use std::{collections::HashSet, iter};
 
 use async_recursion::async_recursion;
 use libipld::Cid;
-use zeroutils_did::did_wk::WrappedDidWebKey;
+use zeroutils_did::{did_wk::WrappedDidWebKey, Base};
 use zeroutils_key::{GetPublicKey, IntoOwned};
-use zeroutils_store::cas::IpldStore;
+use zeroutils_store::cas::{IpldStore, StoreError};
 
 use crate::{
-    AttenuationError, CapabilityTuple, ProofReference, ResolvedCapabilities,
-    ResolvedCapabilityTuple, ResourceUri, SignedUcan, UcanError, UcanResult, Unresolved,
-    UnresolvedCapWithRootIss, UnresolvedUcanWithAud, UnresolvedUcanWithCid,
+    proofs::MissCache, AttenuationError, CapabilityTuple, ProofReference, ProofResolver,
+    ResolvedCapabilities, ResolvedCapabilityTuple, ResourceUri, SignedUcan, StoreResolver,
+    UcanError, UcanResult, Unresolved, UnresolvedCapWithRootIss, UnresolvedUcanWithAud,
+    UnresolvedUcanWithCid,
 };
 
 //--------------------------------------------------------------------------------------------------
@@ -30,12 +31,35 @@ where
     S: IpldStore,
 {
     /// Resolves the capabilities of a UCAN to their final form.
+    ///
+    /// Proofs are read from the store of the UCAN with a [`StoreResolver`].
     pub async fn resolve_capabilities(
         &self,
         root_key: &impl GetPublicKey,
     ) -> UcanResult<&ResolvedCapabilities> {
+        let resolver = StoreResolver::new(self.payload.store.clone());
+        self.resolve_capabilities_with_resolver(root_key, &resolver)
+            .await
+    }
+
+    /// Resolves the capabilities of a UCAN to their final form, fetching proofs with `resolver`.
+    ///
+    /// Proof CIDs found missing are remembered for [`DEFAULT_MISS_TTL`][crate::DEFAULT_MISS_TTL]
+    /// during the resolution, so a chain referencing the same missing proof more than once does not
+    /// fetch it again.
+    pub async fn resolve_capabilities_with_resolver<R>(
+        &self,
+        root_key: &impl GetPublicKey,
+        resolver: &R,
+    ) -> UcanResult<&ResolvedCapabilities>
+    where
+        R: ProofResolver<S>,
+    {
         self.resolved_capabilities
-            .get_or_try_init(
+            .get_or_try_init(async {
+                // The root DID is derived once and then compared by reference along the whole chain.
+                // DIDs compare equal regardless of their base, so any base works here.
+                let root_did = WrappedDidWebKey::from_key(root_key, Base::Base58Btc)?;
                 self.resolve_capabilities_with(
                     (
                         [
@@ -47,26 +71,44 @@ where
                         HashSet::new(),
                         HashSet::new(),
                     ),
-                    root_key,
+                    &root_did,
+                    resolver,
+                    &MissCache::default(),
+                    None,
                     vec![],
-                ),
-            )
+                )
+                .await
+            })
             .await
     }
 
     #[async_recursion(?Send)]
-    async fn resolve_capabilities_with(
+    async fn resolve_capabilities_with<R>(
         &self,
         (ucan_with_cids, ucan_with_auds, cap_with_root_iss): (
             HashSet<UnresolvedUcanWithCid>,
             HashSet<UnresolvedUcanWithAud>,
             HashSet<UnresolvedCapWithRootIss>,
         ),
-        root_key: &impl GetPublicKey,
+        root_did: &WrappedDidWebKey<'_>,
+        resolver: &R,
+        misses: &MissCache,
+        parent: Option<&SignedUcan<'_, S>>,
         trace: Trace,
-    ) -> UcanResult<ResolvedCapabilities> {
-        // Validate the UCAN.
-        self.validate()?;
+    ) -> UcanResult<ResolvedCapabilities>
+    where
+        R: ProofResolver<S>,
+    {
+        // Validate the UCAN on its own before checking it against the UCAN it is a proof of, if any.
+        // The trace starts with the UCAN's own `Cid` if it is a proof.
+        self.validate().map_err(|e| match (e, trace.first()) {
+            (UcanError::Expired(_), Some(cid)) => UcanError::ProofExpired(*cid),
+            (e, _) => e,
+        })?;
+
+        if let Some(parent) = parent {
+            parent.validate_proof_constraints(self)?;
+        }
 
         // Partition the `UcanWithAud` capabilities into validated and unvalidated.
         let (ucan_with_auds_validated, ucan_with_auds_unvalidated) =
@@ -124,7 +166,7 @@ where
             .into_iter()
             .filter_map(|unresolved| {
                 if self
-                    .validate_cap_with_root_iss_constraint(&unresolved, root_key, &trace)
+                    .validate_cap_with_root_iss_constraint(&unresolved, root_did, &trace)
                     .is_ok()
                 {
                     resolved.insert(ResolvedCapabilityTuple::from(unresolved.tuple.clone()));
@@ -190,14 +232,25 @@ where
                 continue;
             }
 
-            let ucan = proof.fetch_ucan(&self.payload.store).await?;
-
-            self.validate_proof_constraints(ucan)?;
-
-            let trace = iter::once(*proof.cid())
+            let trace: Trace = iter::once(*proof.cid())
                 .chain(trace.iter().cloned())
                 .collect();
 
+            // Errors from fetching the proof are about this UCAN, so they are returned as they are.
+            if misses.contains(proof.cid()) {
+                return Err(StoreError::BlockNotFound(*proof.cid()).into());
+            }
+
+            let ucan = match proof.fetch_ucan_with(resolver).await {
+                Ok(ucan) => ucan,
+                Err(UcanError::IpldStoreError(StoreError::BlockNotFound(cid))) => {
+                    misses.insert(*proof.cid());
+                    return Err(StoreError::BlockNotFound(cid).into());
+                }
+                Err(e) => return Err(e),
+            };
+
+            // Errors from the proof and further down the chain are tagged with where they occurred.
             let result = ucan
                 .resolve_capabilities_with(
                     (
@@ -205,10 +258,14 @@ where
                         new_ucan_with_auds.clone(),
                         new_cap_with_root_iss.clone(),
                     ),
-                    root_key,
-                    trace,
+                    root_did,
+                    resolver,
+                    misses,
+                    Some(self),
+                    trace.clone(),
                 )
-                .await?;
+                .await
+                .map_err(|e| e.context(*proof.cid(), trace.clone()))?;
 
             resolved.extend(result);
         }
@@ -234,9 +291,8 @@ where
         if let Some(scheme) = &unresolved.scheme {
             if !self.payload.capabilities.iter().any(|(resource_uri, _)| {
                 if let ResourceUri::Other(uri) = resource_uri {
-                    return uri
-                        .scheme()
-                        .map_or(false, |s| s.to_lowercase() == scheme.to_lowercase());
+                    // Both schemes are normalized to lowercase when parsed.
+                    return uri.scheme().map_or(false, |s| s.as_str() == scheme);
                 }
                 false
             }) {
@@ -254,7 +310,7 @@ where
     fn validate_cap_with_root_iss_constraint(
         &self,
         unresolved: &UnresolvedCapWithRootIss,
-        root_key: &impl GetPublicKey,
+        root_did: &WrappedDidWebKey<'_>,
         trace: &Trace,
     ) -> UcanResult<()> {
         let CapabilityTuple(uri, ability, caveats) = &unresolved.tuple;
@@ -274,8 +330,7 @@ where
         }
 
         // Checks if the capability is delegated by the root issuer.
-        if self.payload.issuer != WrappedDidWebKey::from_key(root_key, self.payload.issuer.base())?
-        {
+        if self.payload.issuer != *root_did {
             return Err(AttenuationError::CapabilityNotDelegatedByRootIssuer(
                 unresolved.tuple.clone(),
                 trace.clone(),
@@ -312,15 +367,13 @@ where
                         unresolved_ucan_with_auds.insert(unresolved);
                     }
                     ProofReference::AllUcansTransient => {
+                        let issuer = self.payload.issuer.clone().into_owned();
+                        let resolved = ResolvedCapabilityTuple::ucan_all(issuer.clone());
                         let unresolved = UnresolvedUcanWithAud {
-                            did: self.payload.issuer.clone().into_owned(),
+                            did: issuer,
                             scheme: None,
                         };
 
-                        let resolved = ResolvedCapabilityTuple::ucan_all(
-                            self.payload.issuer.clone().into_owned(),
-                        );
-
                         unresolved_ucan_with_auds.insert(unresolved);
                         resolved_capabilities.insert(resolved);
                     }
@@ -46,6 +46,23 @@ impl ResolvedCapabilities {
         let requested = requested.into();
         self.0.iter().any(|c| c.permits(&requested))
     }
+
+    /// Returns the capabilities in a stable order, by resource, then ability, then caveats.
+    ///
+    /// This is useful for logging and snapshot testing, as iterating the set itself yields an
+    /// arbitrary order.
+    pub fn sorted(&self) -> Vec<&ResolvedCapabilityTuple> {
+        let mut sorted = self.0.iter().collect::<Vec<_>>();
+        sorted.sort_by_cached_key(|ResolvedCapabilityTuple(resource, ability, caveats)| {
+            (
+                resource.to_string(),
+                ability.to_string(),
+                caveats.to_string(),
+            )
+        });
+
+        sorted
+    }
 }
 
 impl ResolvedCapabilityTuple {
@@ -0,0 +1,120 @@
+//! Fixtures shared by the tests of UCAN delegation chains.
+
+use std::time::{Duration, SystemTime};
+
+use libipld::Cid;
+use rand::thread_rng;
+use zeroutils_did::{did_wk::WrappedDidWebKey, Base};
+use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate};
+use zeroutils_store::cas::{IpldStore, Storable};
+
+use crate::{caps, Capabilities, SignedUcan, Ucan};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// The keys of the principals `p0` to `p3` and their `did:wk`s in base58btc.
+pub(crate) struct Principals {
+    pub(crate) keys: [Ed25519KeyPair<'static>; 4],
+    pub(crate) dids: [WrappedDidWebKey<'static>; 4],
+}
+
+/// A two-level delegation chain.
+///
+/// In `proof`, `p0` delegates some capabilities to `p1`. In `ucan`, `p1` delegates everything it was
+/// delegated to `p2` with `proof` as its only proof, which is stored in the store of the chain.
+pub(crate) struct Chain<S>
+where
+    S: IpldStore,
+{
+    pub(crate) principals: Principals,
+    pub(crate) proof: SignedUcan<'static, S>,
+    pub(crate) proof_cid: Cid,
+    pub(crate) ucan: SignedUcan<'static, S>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Generates the [`Principals`] `p0` to `p3`.
+pub(crate) fn principals() -> anyhow::Result<Principals> {
+    let keys = [
+        Ed25519KeyPair::generate(&mut thread_rng())?,
+        Ed25519KeyPair::generate(&mut thread_rng())?,
+        Ed25519KeyPair::generate(&mut thread_rng())?,
+        Ed25519KeyPair::generate(&mut thread_rng())?,
+    ];
+
+    let dids = [
+        WrappedDidWebKey::from_key(&keys[0], Base::Base58Btc)?,
+        WrappedDidWebKey::from_key(&keys[1], Base::Base58Btc)?,
+        WrappedDidWebKey::from_key(&keys[2], Base::Base58Btc)?,
+        WrappedDidWebKey::from_key(&keys[3], Base::Base58Btc)?,
+    ];
+
+    Ok(Principals { keys, dids })
+}
+
+/// Signs a UCAN in which `issuer` delegates everything it was delegated in `proofs` to `audience`.
+pub(crate) fn delegate<S>(
+    issuer: &Ed25519KeyPair,
+    audience: &WrappedDidWebKey<'static>,
+    expiration: SystemTime,
+    proofs: impl IntoIterator<Item = Cid>,
+    store: S,
+) -> anyhow::Result<SignedUcan<'static, S>>
+where
+    S: IpldStore,
+{
+    Ok(Ucan::builder()
+        .audience(audience.clone())
+        .expiration(expiration)
+        .capabilities(caps! {
+            "ucan:./*": { "ucan/*": [{}] }
+        }?)
+        .store(store)
+        .proofs(proofs)
+        .sign(issuer)?)
+}
+
+/// Creates a [`Chain`] in `store` in which `p0` delegates `capabilities`.
+pub(crate) async fn chain<S>(
+    store: S,
+    capabilities: Capabilities<'static>,
+) -> anyhow::Result<Chain<S>>
+where
+    S: IpldStore,
+{
+    let principals = principals()?;
+    let Principals {
+        keys: [p0, p1, ..],
+        dids: [_, p1_did, p2_did, _],
+    } = &principals;
+
+    let now = SystemTime::now();
+
+    let proof = Ucan::builder()
+        .audience(p1_did.clone())
+        .expiration(now + Duration::from_secs(50))
+        .capabilities(capabilities)
+        .store(store.clone())
+        .sign(p0)?;
+
+    let proof_cid = proof.store().await?;
+    let ucan = delegate(
+        p1,
+        p2_did,
+        now + Duration::from_secs(25),
+        [proof_cid],
+        store,
+    )?;
+
+    Ok(Chain {
+        principals,
+        proof,
+        proof_cid,
+        ucan,
+    })
+}
@@ -1,2 +1,3 @@
+pub(crate) mod fixtures;
 mod proptest_chain;
 mod test_chain;
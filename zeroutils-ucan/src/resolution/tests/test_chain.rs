@@ -1,14 +1,130 @@
 use std::{
+    collections::{HashMap, HashSet},
+    pin::Pin,
     str::FromStr,
+    sync::{Arc, Mutex},
     time::{Duration, SystemTime},
 };
 
+use bytes::Bytes;
+use libipld::Cid;
 use rand::thread_rng;
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::io::AsyncRead;
 use zeroutils_did::{did_wk::WrappedDidWebKey, Base};
 use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate};
-use zeroutils_store::cas::{MemoryStore, Storable};
+use zeroutils_store::cas::{
+    Codec, IpldReferences, IpldStore, MemoryStore, PlaceholderStore, Storable, StoreError,
+    StoreResult,
+};
+
+use super::fixtures::{chain, delegate, principals, Chain, Principals};
+use crate::{
+    caps, Ability, Caveats, ProofResolver, ResolvedResource, SignedUcan, Ucan, UcanError,
+    UcanResult,
+};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// Serves encoded UCANs from a map instead of a store.
+struct MapResolver(HashMap<Cid, String>);
+
+/// A memory store that counts how many times the bytes of each CID are fetched.
+#[derive(Clone, Default)]
+struct CountingStore {
+    inner: MemoryStore,
+    fetches: Arc<Mutex<HashMap<Cid, usize>>>,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl CountingStore {
+    fn fetches(&self, cid: &Cid) -> usize {
+        self.fetches
+            .lock()
+            .unwrap()
+            .get(cid)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+impl ProofResolver<PlaceholderStore> for MapResolver {
+    async fn resolve(&self, cid: &Cid) -> UcanResult<SignedUcan<'static, PlaceholderStore>> {
+        let ucan = self.0.get(cid).ok_or(UcanError::ProofCidNotFound(*cid))?;
+        SignedUcan::try_from_str(ucan, PlaceholderStore)
+    }
+}
 
-use crate::{caps, Ability, Caveats, ResolvedResource, Ucan};
+impl IpldStore for CountingStore {
+    async fn put_node<T>(&self, data: &T) -> StoreResult<Cid>
+    where
+        T: Serialize + IpldReferences + Sync,
+    {
+        self.inner.put_node(data).await
+    }
+
+    async fn put_bytes<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+    ) -> StoreResult<Cid> {
+        self.inner.put_bytes(reader).await
+    }
+
+    async fn cid_of_bytes<'a>(
+        &'a self,
+        reader: impl AsyncRead + Send + Sync + 'a,
+    ) -> StoreResult<Cid> {
+        self.inner.cid_of_bytes(reader).await
+    }
+
+    async fn put_raw_block(&self, bytes: impl Into<Bytes> + Send) -> StoreResult<Cid> {
+        self.inner.put_raw_block(bytes).await
+    }
+
+    async fn get_node<D>(&self, cid: &Cid) -> StoreResult<D>
+    where
+        D: DeserializeOwned + Send,
+    {
+        self.inner.get_node(cid).await
+    }
+
+    async fn get_bytes<'a>(
+        &'a self,
+        cid: &'a Cid,
+    ) -> StoreResult<Pin<Box<dyn AsyncRead + Send + Sync + 'a>>> {
+        *self.fetches.lock().unwrap().entry(*cid).or_default() += 1;
+        self.inner.get_bytes(cid).await
+    }
+
+    async fn get_raw_block(&self, cid: &Cid) -> StoreResult<Bytes> {
+        self.inner.get_raw_block(cid).await
+    }
+
+    async fn has(&self, cid: &Cid) -> bool {
+        self.inner.has(cid).await
+    }
+
+    fn get_supported_codecs(&self) -> HashSet<Codec> {
+        self.inner.get_supported_codecs()
+    }
+
+    fn get_node_block_max_size(&self) -> Option<u64> {
+        self.inner.get_node_block_max_size()
+    }
+
+    fn get_raw_block_max_size(&self) -> Option<u64> {
+        self.inner.get_raw_block_max_size()
+    }
+}
 
 //--------------------------------------------------------------------------------------------------
 // Tests
@@ -65,3 +181,375 @@ async fn test_ucan_resolve_capabilities() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_sorted() -> anyhow::Result<()> {
+    let store = MemoryStore::default();
+    let Chain {
+        principals, ucan, ..
+    } = chain(
+        store.clone(),
+        caps! {
+            "zerodb://": {
+                "db/table/write": [{}],
+                "db/table/read": [{}]
+            },
+            "zerofs://": { "fs/read": [{}] },
+            "zeroapi://": { "api/call": [{}] }
+        }?,
+    )
+    .await?;
+
+    let [p0, ..] = &principals.keys;
+
+    // Resolve two independent copies of the same UCAN.
+    let copy = SignedUcan::try_from_str(ucan.to_string(), store)?;
+    let resolved_0 = ucan.resolve_capabilities(p0).await?.sorted();
+    let resolved_1 = copy.resolve_capabilities(p0).await?.sorted();
+
+    assert_eq!(resolved_0, resolved_1);
+    assert_eq!(
+        resolved_0
+            .iter()
+            .map(|tuple| (tuple.0.to_string(), tuple.1.to_string()))
+            .collect::<Vec<_>>(),
+        [
+            ("zeroapi://", "api/call"),
+            ("zerodb://", "db/table/read"),
+            ("zerodb://", "db/table/write"),
+            ("zerofs://", "fs/read"),
+        ]
+        .map(|(resource, ability)| (resource.to_string(), ability.to_string()))
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_with_mixed_case_scheme() -> anyhow::Result<()> {
+    let Chain {
+        principals, ucan, ..
+    } = chain(
+        MemoryStore::default(),
+        caps! {
+            "ZeroDB://": { "db/table/read": [{}] }
+        }?,
+    )
+    .await?;
+
+    let resolved = ucan.resolve_capabilities(&principals.keys[0]).await?;
+
+    assert_eq!(resolved.len(), 1);
+    assert!(resolved.permits((
+        ResolvedResource::from_str("ZERODB://")?,
+        Ability::from_str("db/table/read")?,
+        Caveats::any(),
+    )));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_fails_on_placeholder_store() -> anyhow::Result<()> {
+    let Chain {
+        principals, ucan, ..
+    } = chain(
+        MemoryStore::default(),
+        caps! {
+            "zerodb://": { "db/table/read": [{}] }
+        }?,
+    )
+    .await?;
+
+    // The proof is in the store of the chain, but not in the store of the UCAN.
+    let ucan1 = SignedUcan::try_from_str(ucan.to_string(), PlaceholderStore)?;
+
+    let result = ucan1.resolve_capabilities(&principals.keys[0]).await;
+    assert!(matches!(
+        result,
+        Err(UcanError::IpldStoreError(StoreError::PlaceholderStoreUsed(
+            "get_bytes"
+        )))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_with_custom_resolver() -> anyhow::Result<()> {
+    let Chain {
+        principals,
+        proof,
+        proof_cid: cid0,
+        ucan,
+    } = chain(
+        MemoryStore::default(),
+        caps! {
+            "zerodb://": { "db/table/read": [{}] }
+        }?,
+    )
+    .await?;
+
+    let [p0, ..] = &principals.keys;
+
+    let ucan1 = SignedUcan::try_from_str(ucan.to_string(), PlaceholderStore)?;
+
+    // The proof is not in the store.
+    let unresolved = SignedUcan::try_from_str(ucan.to_string(), PlaceholderStore)?;
+    assert!(matches!(
+        unresolved.resolve_capabilities(p0).await,
+        Err(UcanError::IpldStoreError(StoreError::PlaceholderStoreUsed(
+            "get_bytes"
+        )))
+    ));
+
+    // The proof is served by the resolver instead.
+    let resolver = MapResolver([(cid0, proof.to_string())].into_iter().collect());
+    let resolved = ucan1
+        .resolve_capabilities_with_resolver(p0, &resolver)
+        .await?;
+
+    assert_eq!(resolved.len(), 1);
+    assert!(resolved.permits((
+        ResolvedResource::from_str("zerodb://")?,
+        Ability::from_str("db/table/read")?,
+        Caveats::any(),
+    )));
+
+    // Unknown proofs are reported by the resolver.
+    let resolver = MapResolver(HashMap::new());
+    let ucan1 = SignedUcan::try_from_str(ucan.to_string(), PlaceholderStore)?;
+    assert!(matches!(
+        ucan1
+            .resolve_capabilities_with_resolver(p0, &resolver)
+            .await,
+        Err(UcanError::ProofCidNotFound(cid)) if cid == cid0
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_fetches_missing_proof_once() -> anyhow::Result<()> {
+    let Chain {
+        principals,
+        proof_cid: missing_cid,
+        ucan,
+        ..
+    } = chain(
+        MemoryStore::default(),
+        caps! {
+            "zerodb://": { "db/table/read": [{}] }
+        }?,
+    )
+    .await?;
+
+    // The proof is never stored in `store`.
+    let store = CountingStore::default();
+    let ucan1 = SignedUcan::try_from_str(ucan.to_string(), store.clone())?;
+
+    let result = ucan1.resolve_capabilities(&principals.keys[0]).await;
+    assert!(matches!(
+        result,
+        Err(UcanError::IpldStoreError(StoreError::BlockNotFound(cid))) if cid == missing_cid
+    ));
+
+    assert_eq!(store.fetches(&missing_cid), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_fails_on_expired_proof() -> anyhow::Result<()> {
+    let store = MemoryStore::default();
+    let Chain {
+        principals,
+        proof_cid: cid0,
+        ..
+    } = chain(
+        store.clone(),
+        caps! {
+            "zerodb://": { "db/table/read": [{}] }
+        }?,
+    )
+    .await?;
+
+    let [p0, p1, p2, _] = &principals.keys;
+    let [_, _, p2_did, p3_did] = &principals.dids;
+    let now = SystemTime::now();
+
+    // Mid-chain proof that has already expired.
+    let ucan1 = delegate(
+        p1,
+        p2_did,
+        now - Duration::from_secs(10),
+        [cid0],
+        store.clone(),
+    )?;
+    let cid1 = ucan1.store().await?;
+
+    let ucan2 = delegate(p2, p3_did, now + Duration::from_secs(25), [cid1], store)?;
+
+    let result = ucan2.resolve_capabilities(p0).await;
+    assert!(matches!(
+        result.as_ref().map_err(UcanError::root_cause),
+        Err(UcanError::ProofExpired(cid)) if *cid == cid1
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_fails_on_tampered_proof_signature() -> anyhow::Result<()> {
+    let store = MemoryStore::default();
+    let Chain {
+        principals, proof, ..
+    } = chain(
+        store.clone(),
+        caps! {
+            "zerodb://": { "db/table/read": [{}] }
+        }?,
+    )
+    .await?;
+
+    let [p0, p1, ..] = &principals.keys;
+    let [_, _, p2_did, _] = &principals.dids;
+    let now = SystemTime::now();
+
+    // Replace the signature of the proof with one over different content.
+    let other = Ucan::builder()
+        .audience(p2_did.clone())
+        .expiration(now + Duration::from_secs(50))
+        .capabilities(caps!()?)
+        .store(store.clone())
+        .sign(p0)?;
+
+    let encoded = proof.to_string();
+    let (unsigned, _) = encoded.rsplit_once('.').unwrap();
+    let other_encoded = other.to_string();
+    let (_, other_signature) = other_encoded.rsplit_once('.').unwrap();
+    let tampered = format!("{unsigned}.{other_signature}");
+
+    let cid0 = store.put_bytes(tampered.as_bytes()).await?;
+
+    let ucan1 = delegate(p1, p2_did, now + Duration::from_secs(25), [cid0], store)?;
+
+    let result = ucan1.resolve_capabilities(p0).await;
+    assert!(matches!(
+        result.as_ref().map_err(UcanError::root_cause),
+        Err(UcanError::KeyError(_))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_with_root_issuer_in_other_base() -> anyhow::Result<()> {
+    let store = MemoryStore::default();
+    let Principals {
+        keys: [p0, p1, ..],
+        dids: [_, p1_did, p2_did, _],
+    } = principals()?;
+
+    let now = SystemTime::now();
+
+    // The root issuer is not encoded in the base the root DID is derived with.
+    let ucan0 = Ucan::builder()
+        .issuer(WrappedDidWebKey::from_key(&p0, Base::Base32Lower)?)
+        .audience(p1_did)
+        .expiration(now + Duration::from_secs(50))
+        .capabilities(caps! {
+            "zerodb://": { "db/table/read": [{}] },
+            "zerofs://": { "fs/read": [{}] }
+        }?)
+        .store(store.clone())
+        .sign(&p0)?;
+
+    let cid0 = ucan0.store().await?;
+    let ucan1 = delegate(
+        &p1,
+        &p2_did,
+        now + Duration::from_secs(25),
+        [cid0],
+        store.clone(),
+    )?;
+
+    let resolved = ucan1.resolve_capabilities(&p0).await?;
+    assert_eq!(
+        resolved
+            .sorted()
+            .iter()
+            .map(|tuple| (tuple.0.to_string(), tuple.1.to_string()))
+            .collect::<Vec<_>>(),
+        [("zerodb://", "db/table/read"), ("zerofs://", "fs/read")]
+            .map(|(resource, ability)| (resource.to_string(), ability.to_string()))
+    );
+
+    // The capabilities are not delegated by any other root.
+    let copy = SignedUcan::try_from_str(ucan1.to_string(), store.clone())?;
+    assert!(matches!(
+        copy.resolve_capabilities(&p1)
+            .await
+            .as_ref()
+            .map_err(UcanError::root_cause),
+        Err(UcanError::UnresolvedCapabilities(..))
+    ));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ucan_resolve_capabilities_reports_proof_path() -> anyhow::Result<()> {
+    let store = MemoryStore::default();
+    let Principals {
+        keys: [p0, p1, p2, _],
+        dids: [_, p1_did, p2_did, p3_did],
+    } = principals()?;
+
+    let now = SystemTime::now();
+
+    // Root proof, two levels below the UCAN being resolved, that has already expired.
+    let ucan0 = Ucan::builder()
+        .audience(p1_did)
+        .expiration(now - Duration::from_secs(10))
+        .capabilities(caps! {
+            "zerodb://": { "db/table/read": [{}] }
+        }?)
+        .store(store.clone())
+        .sign(&p0)?;
+
+    let cid0 = ucan0.store().await?;
+    let ucan1 = delegate(
+        &p1,
+        &p2_did,
+        now + Duration::from_secs(50),
+        [cid0],
+        store.clone(),
+    )?;
+
+    let cid1 = ucan1.store().await?;
+    let ucan2 = delegate(
+        &p2,
+        &p3_did,
+        now + Duration::from_secs(25),
+        [cid1],
+        store.clone(),
+    )?;
+
+    let error = ucan2.resolve_capabilities(&p0).await.unwrap_err();
+    assert_eq!(error.proof_path(), [cid1, cid0]);
+    assert!(matches!(
+        error.root_cause(),
+        UcanError::ProofExpired(cid) if *cid == cid0
+    ));
+    assert!(matches!(
+        &error,
+        UcanError::InProof { trace, .. } if *trace == [cid1]
+    ));
+    assert_eq!(
+        error.to_string(),
+        format!("In proof {cid1}: In proof {cid0}: Proof UCAN expired: {cid0}")
+    );
+
+    Ok(())
+}
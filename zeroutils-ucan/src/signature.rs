@@ -2,8 +2,9 @@ use std::{fmt::Display, ops::Deref, str::FromStr};
 
 use base64::prelude::{Engine, BASE64_URL_SAFE_NO_PAD};
 use serde::{Deserialize, Serialize};
+use zeroutils_key::JwsAlgorithm;
 
-use crate::UcanError;
+use crate::{UcanError, UcanResult};
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -22,7 +23,30 @@ pub struct UcanSignature(Vec<u8>);
 //--------------------------------------------------------------------------------------------------
 
 impl UcanSignature {
+    /// Creates a signature from its raw bytes, checking that their length matches `alg`.
+    ///
+    /// ECDSA signatures are expected in their fixed-size `r || s` encoding, which is 64 bytes for
+    /// `EdDSA`, `ES256` and `ES256K`, and 96 bytes for `ES384`.
+    pub fn from_bytes(alg: JwsAlgorithm, bytes: &[u8]) -> UcanResult<Self> {
+        let expected = match alg {
+            JwsAlgorithm::EdDSA | JwsAlgorithm::ES256 | JwsAlgorithm::ES256K => 64,
+            JwsAlgorithm::ES384 => 96,
+        };
+
+        if bytes.len() != expected {
+            return Err(UcanError::InvalidSignatureLength(
+                alg,
+                expected,
+                bytes.len(),
+            ));
+        }
+
+        Ok(UcanSignature(bytes.to_vec()))
+    }
+
     /// Returns the raw bytes of the signature.
+    ///
+    /// For ECDSA algorithms these are the fixed-size `r || s` bytes.
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
@@ -98,8 +122,44 @@ impl Deref for UcanSignature {
 
 #[cfg(test)]
 mod tests {
+    use zeroutils_key::{
+        Ed25519KeyPair, KeyPairGenerate, P256KeyPair, P384KeyPair, Secp256k1KeyPair, Sign, Verify,
+    };
+
     use super::*;
 
+    fn check_bytes_roundtrip<K>(key_pair: &K, alg: JwsAlgorithm) -> anyhow::Result<()>
+    where
+        K: Sign + Verify,
+    {
+        let signature = UcanSignature::from(key_pair.sign(b"hello")?);
+        let parsed = UcanSignature::from_bytes(alg, signature.as_bytes())?;
+        assert_eq!(parsed, signature);
+        key_pair.verify(b"hello", parsed.as_bytes())?;
+
+        assert!(matches!(
+            UcanSignature::from_bytes(alg, &signature[1..]),
+            Err(UcanError::InvalidSignatureLength(_, _, _))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_signature_bytes_roundtrip() -> anyhow::Result<()> {
+        let rng = &mut rand::thread_rng();
+        check_bytes_roundtrip(&Ed25519KeyPair::generate(rng)?, JwsAlgorithm::EdDSA)?;
+        check_bytes_roundtrip(&P256KeyPair::generate(rng)?, JwsAlgorithm::ES256)?;
+        check_bytes_roundtrip(&P384KeyPair::generate(rng)?, JwsAlgorithm::ES384)?;
+        check_bytes_roundtrip(&Secp256k1KeyPair::generate(rng)?, JwsAlgorithm::ES256K)?;
+
+        // 64 byte signatures are not valid for `ES384`.
+        let signature = Ed25519KeyPair::generate(rng)?.sign(b"hello")?;
+        assert!(UcanSignature::from_bytes(JwsAlgorithm::ES384, &signature).is_err());
+
+        Ok(())
+    }
+
     #[test_log::test]
     fn test_signature_serde() {
         let signature = UcanSignature::from(vec![1, 2, 3, 4, 5]);
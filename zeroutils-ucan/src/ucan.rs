@@ -1,26 +1,38 @@
 use std::{
+    collections::HashSet,
     fmt::{Debug, Display},
     marker::PhantomData,
+    time::SystemTime,
 };
 
 use async_once_cell::OnceCell;
+use async_recursion::async_recursion;
 use libipld::Cid;
 use serde::{
     de::{self, DeserializeSeed},
     Deserialize, Deserializer, Serialize,
 };
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt};
 
-use zeroutils_did::did_wk::WrappedDidWebKey;
-use zeroutils_key::{GetPublicKey, JwsAlgName, JwsAlgorithm, Sign, Verify};
+use zeroutils_did::{did_wk::WrappedDidWebKey, Base};
+use zeroutils_key::{GetPublicKey, IntoOwned, JwsAlgName, JwsAlgorithm, Sign, Verify};
 use zeroutils_store::cas::{
     IpldStore, IpldStoreExt, PlaceholderStore, Storable, StoreError, StoreResult,
 };
 
 use crate::{
-    DefaultUcanBuilder, ResolvedCapabilities, ResolvedCapabilityTuple, UcanBuilder, UcanError,
-    UcanHeader, UcanPayload, UcanPayloadSerializable, UcanResult, UcanSignature,
+    Capabilities, DefaultUcanBuilder, NonceStore, ResolvedCapabilities, ResolvedCapabilityTuple,
+    UcanBuilder, UcanError, UcanHeader, UcanPayload, UcanPayloadSerializable, UcanResult,
+    UcanSignature,
 };
 
+//--------------------------------------------------------------------------------------------------
+// Constants
+//--------------------------------------------------------------------------------------------------
+
+/// The default maximum length in bytes of a token read with [`SignedUcan::from_reader`].
+pub const DEFAULT_MAX_TOKEN_LEN: usize = 64 * 1024;
+
 //--------------------------------------------------------------------------------------------------
 // Types
 //--------------------------------------------------------------------------------------------------
@@ -33,6 +45,12 @@ use crate::{
 ///
 /// NOTE: This implementation currently only supports the `did:wk` DID method.
 ///
+/// ## Stores
+///
+/// A UCAN is bound to the store its proofs are fetched from. An encoded UCAN is parsed and bound to a
+/// store with [`SignedUcan::with_store`], and a UCAN is moved to another store with
+/// [`use_store`](Self::use_store), which rebinds its cached proofs too.
+///
 /// [ucan]: https://github.com/ucan-wg/spec
 pub struct Ucan<'a, S, H = (), V = (), R = ()>
 where
@@ -113,6 +131,28 @@ impl Ucan<'_, PlaceholderStore> {
     pub fn builder() -> DefaultUcanBuilder {
         UcanBuilder::default()
     }
+
+    /// Creates a self-signed root UCAN where both the issuer and the audience are the keypair's DID.
+    ///
+    /// Capabilities in a root UCAN are delegated by the root issuer itself, so they resolve against
+    /// the same keypair as the root key.
+    pub fn root<'a, K>(
+        keypair: &K,
+        capabilities: Capabilities<'a>,
+        expiration: impl Into<Option<SystemTime>>,
+    ) -> UcanResult<SignedUcan<'a, PlaceholderStore>>
+    where
+        K: Sign + JwsAlgName + GetPublicKey + IntoOwned,
+    {
+        let did = WrappedDidWebKey::from_key(keypair, Base::Base58Btc)?;
+        UcanBuilder::default()
+            .store(PlaceholderStore)
+            .issuer(did.clone())
+            .audience(did)
+            .expiration(expiration)
+            .capabilities(capabilities)
+            .sign(keypair)
+    }
 }
 
 impl<'a, S, H, V, R> Ucan<'a, S, H, V, R>
@@ -138,6 +178,22 @@ where
     pub fn addressed_to(&self, did: &WrappedDidWebKey) -> bool {
         self.payload.audience() == did
     }
+
+    /// Rebinds the UCAN to another store.
+    ///
+    /// Proof UCANs that were already fetched are rebound recursively and stay cached, so only proofs
+    /// that were never fetched are read from the new store.
+    pub fn use_store<T>(self, store: T) -> Ucan<'a, T, H, V, R>
+    where
+        T: IpldStore,
+    {
+        Ucan {
+            header: self.header,
+            payload: self.payload.use_store(store),
+            signature: self.signature,
+            resolved_capabilities: self.resolved_capabilities,
+        }
+    }
 }
 
 impl<'a, S, H, V> Ucan<'a, S, H, V>
@@ -219,6 +275,34 @@ impl<'a, S> UnsignedUcan<'a, S, UcanHeader>
 where
     S: IpldStore,
 {
+    /// Returns the signing input of the UCAN, which is the encoded `header.payload` that a signature
+    /// is computed over.
+    ///
+    /// This is useful when the signature is produced elsewhere, for example by a hardware key, and later
+    /// attached with [`attach_signature`](Self::attach_signature).
+    pub fn signing_input(&self) -> String {
+        self.to_string()
+    }
+
+    /// Attaches an externally produced signature to the UCAN without re-signing it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the signature was not produced by the issuer over the
+    /// [`signing_input`](Self::signing_input).
+    pub fn attach_signature(self, signature: UcanSignature) -> UcanResult<SignedUcan<'a, S>> {
+        let ucan = Ucan {
+            header: self.header,
+            payload: self.payload,
+            signature,
+            resolved_capabilities: OnceCell::new(),
+        };
+
+        ucan.verify_signature()?;
+
+        Ok(ucan)
+    }
+
     /// Attempts to create a `UnsignedUcan` instance by parsing provided Base64 encoded string.
     pub fn try_from_str(string: impl AsRef<str>, store: S) -> UcanResult<Self> {
         let parts: Vec<&str> = string.as_ref().split('.').collect();
@@ -273,12 +357,113 @@ where
         })
     }
 
+    /// Computes the CID the UCAN would be stored under, without storing it.
+    ///
+    /// This is the CID that [`store`](Storable::store) returns, so it can be used to reference the UCAN
+    /// as a proof before or without persisting it.
+    pub async fn cid(&self) -> StoreResult<Cid> {
+        let encoded = self.to_string();
+        self.payload.store.cid_of_bytes(encoded.as_bytes()).await
+    }
+
+    /// Stores the UCAN along with all of its proof UCANs, so the whole chain can be loaded later by the
+    /// CID of the UCAN.
+    ///
+    /// Proofs are stored recursively before the UCAN itself. Proofs that have not been fetched yet are
+    /// only referenced by CID and are expected to be in the store already, so they are skipped.
+    pub async fn store_recursive(&self) -> StoreResult<Cid> {
+        self.store_recursive_in(&self.payload.store).await
+    }
+
+    /// Stores the UCAN and its fetched proof UCANs in `store`.
+    #[async_recursion(?Send)]
+    async fn store_recursive_in(&self, store: &S) -> StoreResult<Cid> {
+        for proof in self.payload.proofs.iter() {
+            if let Some(ucan) = proof.cached_ucan() {
+                ucan.store_recursive_in(store).await?;
+            }
+        }
+
+        let encoded = self.to_string();
+        store.put_bytes(encoded.as_bytes()).await
+    }
+
+    /// Parses an encoded `SignedUcan` and binds it to `store`.
+    ///
+    /// This is the same as [`try_from_str`](Self::try_from_str). Use [`use_store`](Self::use_store)
+    /// to move a UCAN that is already parsed to another store.
+    pub fn with_store(string: impl AsRef<str>, store: S) -> UcanResult<Self> {
+        Self::try_from_str(string, store)
+    }
+
+    /// Reads a single encoded `SignedUcan` from `reader` and parses it.
+    ///
+    /// The token is read up to the next newline or the end of the stream, so several newline-delimited
+    /// tokens can be read from the same reader one after another. Any bytes after the newline are left
+    /// in the reader.
+    ///
+    /// At most [`DEFAULT_MAX_TOKEN_LEN`] bytes are read. Use
+    /// [`from_reader_with_limit`](Self::from_reader_with_limit) to read longer tokens.
+    pub async fn from_reader(reader: impl AsyncBufRead + Unpin, store: S) -> UcanResult<Self> {
+        Self::from_reader_with_limit(reader, DEFAULT_MAX_TOKEN_LEN, store).await
+    }
+
+    /// Reads a single encoded `SignedUcan` of at most `max_len` bytes from `reader` and parses it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`UcanError::TokenTooLong`] if no newline or end of stream is found within `max_len`
+    /// bytes. The bytes read up to that point are consumed.
+    pub async fn from_reader_with_limit(
+        mut reader: impl AsyncBufRead + Unpin,
+        max_len: usize,
+        store: S,
+    ) -> UcanResult<Self> {
+        let mut buf = Vec::new();
+        let limit = u64::try_from(max_len).map_or(u64::MAX, |len| len.saturating_add(1));
+        (&mut reader)
+            .take(limit)
+            .read_until(b'\n', &mut buf)
+            .await?;
+
+        let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        if line.len() > max_len {
+            return Err(UcanError::TokenTooLong(max_len));
+        }
+
+        let token = std::str::from_utf8(line)?.trim_end_matches('\r');
+        Self::try_from_str(token, store)
+    }
+
     /// Validates the UCAN, ensuring that it is well-formed.
     pub fn validate(&self) -> UcanResult<()> {
         self.payload.validate_time_bounds()?;
         self.verify_signature()
     }
 
+    /// Validates the UCAN like [`validate`](Self::validate) and also rejects it if its nonce was
+    /// already seen from the same issuer.
+    ///
+    /// The nonce is only recorded in `nonce_store` once the UCAN is otherwise valid, and only until
+    /// the UCAN expires. UCANs without a nonce are not checked for replays.
+    pub async fn validate_with_nonce_store(&self, nonce_store: &impl NonceStore) -> UcanResult<()> {
+        self.validate()?;
+
+        if let Some(nonce) = self.payload.nonce() {
+            if !nonce_store
+                .insert(&self.payload.issuer, nonce, self.payload.expiration())
+                .await?
+            {
+                return Err(UcanError::ReplayedNonce(
+                    self.payload.issuer.to_string(),
+                    nonce.to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Checks if the UCAN does not exceed the constraints of the proof UCAN.
     pub fn validate_proof_constraints<'b>(
         &self,
@@ -310,6 +495,27 @@ where
         Ok(())
     }
 
+    /// Returns the issuers of the UCANs at the leaves of the proof chain.
+    ///
+    /// These are the root issuers that the capabilities of the UCAN ultimately rely on, which helps
+    /// decide which root key to resolve the capabilities against. A UCAN without proofs is its own root.
+    #[async_recursion(?Send)]
+    pub async fn root_issuers(&self, store: &S) -> UcanResult<HashSet<WrappedDidWebKey<'static>>> {
+        if self.payload.proofs.is_empty() {
+            return Ok([self.payload.issuer.clone().into_owned()]
+                .into_iter()
+                .collect());
+        }
+
+        let mut issuers = HashSet::new();
+        for proof in self.payload.proofs.iter() {
+            let ucan = proof.fetch_ucan(store).await?;
+            issuers.extend(ucan.root_issuers(store).await?);
+        }
+
+        Ok(issuers)
+    }
+
     /// Verifies the signature is truly signed by the issuer.
     pub fn verify_signature(&self) -> UcanResult<()> {
         let unsigned_ucan = UnsignedUcan::from_parts(self.header.clone(), self.payload.clone(), ());
@@ -470,13 +676,21 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+    use std::{
+        io::Cursor,
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use std::str::FromStr;
 
-    use zeroutils_did::Base;
     use zeroutils_key::{Ed25519KeyPair, KeyPairGenerate};
     use zeroutils_store::cas::MemoryStore;
 
-    use crate::caps;
+    use crate::{
+        caps,
+        resolution::tests::fixtures::{chain, delegate, Chain},
+        Ability, Caveats, MemoryNonceStore, ResolvedResource,
+    };
 
     use super::*;
 
@@ -565,6 +779,125 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_ucan_from_reader() -> anyhow::Result<()> {
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let ucan = Ucan::builder()
+            .store(PlaceholderStore)
+            .issuer(WrappedDidWebKey::from_key(&keypair, Base::Base58Btc)?)
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .expiration(None)
+            .capabilities(caps!()?)
+            .sign(&keypair)?;
+
+        let mut reader = Cursor::new(format!("{ucan}\r\ntrailing bytes"));
+        let decoded = SignedUcan::from_reader(&mut reader, PlaceholderStore).await?;
+        assert_eq!(decoded, ucan);
+
+        // The trailing bytes are left in the reader.
+        let mut rest = String::new();
+        reader.read_to_string(&mut rest).await?;
+        assert_eq!(rest, "trailing bytes");
+
+        // A token at the end of the stream without a newline.
+        let decoded =
+            SignedUcan::from_reader(Cursor::new(ucan.to_string()), PlaceholderStore).await?;
+        assert_eq!(decoded, ucan);
+
+        // An empty stream.
+        assert!(matches!(
+            SignedUcan::from_reader(Cursor::new(""), PlaceholderStore).await,
+            Err(UcanError::UnableToParse)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_from_reader_with_limit() -> anyhow::Result<()> {
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let ucan = Ucan::builder()
+            .store(PlaceholderStore)
+            .issuer(WrappedDidWebKey::from_key(&keypair, Base::Base58Btc)?)
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .expiration(None)
+            .capabilities(caps!()?)
+            .sign(&keypair)?;
+
+        let encoded = ucan.to_string();
+
+        // A token of exactly the limit, with or without a newline.
+        let decoded = SignedUcan::from_reader_with_limit(
+            Cursor::new(format!("{encoded}\n")),
+            encoded.len(),
+            PlaceholderStore,
+        )
+        .await?;
+        assert_eq!(decoded, ucan);
+
+        let decoded = SignedUcan::from_reader_with_limit(
+            Cursor::new(encoded.clone()),
+            encoded.len(),
+            PlaceholderStore,
+        )
+        .await?;
+        assert_eq!(decoded, ucan);
+
+        // A token longer than the limit.
+        assert!(matches!(
+            SignedUcan::from_reader_with_limit(
+                Cursor::new(encoded.clone()),
+                encoded.len() - 1,
+                PlaceholderStore,
+            )
+            .await,
+            Err(UcanError::TokenTooLong(_))
+        ));
+
+        // An unbounded stream without a newline stops at the limit.
+        assert!(matches!(
+            SignedUcan::from_reader_with_limit(
+                tokio::io::BufReader::new(tokio::io::repeat(b'a')),
+                1024,
+                PlaceholderStore,
+            )
+            .await,
+            Err(UcanError::TokenTooLong(1024))
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ucan_attach_signature() -> anyhow::Result<()> {
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let unsigned = Ucan::builder()
+            .store(PlaceholderStore)
+            .issuer(WrappedDidWebKey::from_key(&keypair, Base::Base58Btc)?)
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .expiration(None)
+            .capabilities(caps!()?)
+            .build()
+            .use_alg(keypair.alg());
+
+        // Sign externally.
+        let signature = keypair.sign(unsigned.signing_input().as_bytes())?;
+        let signed = unsigned.clone().attach_signature(signature.into())?;
+
+        assert_eq!(signed, unsigned.clone().sign(&keypair)?);
+        signed.validate()?;
+
+        // A signature by another key is rejected.
+        let other = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let signature = other.sign(unsigned.signing_input().as_bytes())?;
+        assert!(matches!(
+            unsigned.attach_signature(signature.into()),
+            Err(UcanError::KeyError(_))
+        ));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_ucan_stores_and_loads() -> anyhow::Result<()> {
         let now = SystemTime::now();
@@ -594,4 +927,277 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_ucan_cid_without_storing() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let ucan = Ucan::root(
+            &key_pair,
+            caps! {
+                "zerodb://": { "db/table/read": [{}] }
+            }?,
+            SystemTime::now() + Duration::from_secs(60),
+        )?
+        .use_store(store.clone());
+
+        let cid = ucan.cid().await?;
+        assert!(!store.has(&cid).await);
+
+        assert_eq!(ucan.store().await?, cid);
+        assert!(store.has(&cid).await);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_root() -> anyhow::Result<()> {
+        let root_key = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let root_did = WrappedDidWebKey::from_key(&root_key, Base::Base58Btc)?;
+
+        let ucan = Ucan::root(
+            &root_key,
+            caps! {
+                "zerodb://": { "db/table/read": [{}] }
+            }?,
+            SystemTime::now() + Duration::from_secs(60),
+        )?;
+
+        assert_eq!(ucan.payload().issuer(), &root_did);
+        assert!(ucan.addressed_to(&root_did));
+        assert!(
+            ucan.permits(
+                (
+                    ResolvedResource::from_str("zerodb://")?,
+                    Ability::from_str("db/table/read")?,
+                    Caveats::any(),
+                ),
+                &root_key,
+            )
+            .await?
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_root_issuers() -> anyhow::Result<()> {
+        let store = MemoryStore::default();
+        let Chain {
+            principals,
+            proof,
+            ucan,
+            ..
+        } = chain(
+            store.clone(),
+            caps! {
+                "zerodb://": { "db/read": [{}] }
+            }?,
+        )
+        .await?;
+
+        let [_, _, p2, _] = &principals.keys;
+        let [p0_did, _, p2_did, _] = &principals.dids;
+
+        let cid1 = ucan.store().await?;
+        let ucan2 = delegate(
+            p2,
+            p2_did,
+            SystemTime::now() + Duration::from_secs(10),
+            [cid1],
+            store.clone(),
+        )?;
+
+        let issuers = ucan2.root_issuers(&store).await?;
+        assert_eq!(issuers, [p0_did.clone()].into_iter().collect());
+
+        // A UCAN without proofs is its own root.
+        let issuers = proof.root_issuers(&store).await?;
+        assert_eq!(issuers.len(), 1);
+        assert!(issuers.contains(&proof.payload().issuer().clone().into_owned()));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_use_store() -> anyhow::Result<()> {
+        let store_a = MemoryStore::default();
+        let store_b = MemoryStore::default();
+        let Chain {
+            principals,
+            proof,
+            proof_cid,
+            ucan,
+        } = chain(
+            store_a.clone(),
+            caps! {
+                "zerodb://": { "db/read": [{}] }
+            }?,
+        )
+        .await?;
+
+        let [p0, ..] = &principals.keys;
+
+        let capability = || -> anyhow::Result<_> {
+            Ok((
+                ResolvedResource::from_str("zerodb://")?,
+                Ability::from_str("db/read")?,
+                Caveats::any(),
+            ))
+        };
+
+        // Fetch the proof from the first store, then move the UCAN to the empty second store.
+        ucan.root_issuers(&store_a).await?;
+        let encoded = ucan.to_string();
+        let ucan = ucan.use_store(store_b.clone());
+
+        // The cached proof moves along with the UCAN.
+        assert!(ucan.permits(capability()?, p0).await?);
+
+        // A freshly parsed UCAN fetches its proofs from the new store.
+        let ucan = SignedUcan::with_store(&encoded, store_b.clone())?;
+        assert!(ucan.permits(capability()?, p0).await.is_err());
+
+        let ucan = SignedUcan::with_store(&encoded, store_b.clone())?;
+        assert_eq!(proof.use_store(store_b.clone()).store().await?, proof_cid);
+        assert!(ucan.permits(capability()?, p0).await?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ucan_sub_second_times_roundtrip() -> anyhow::Result<()> {
+        let keypair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let now = UNIX_EPOCH
+            + Duration::from_secs(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs());
+        let expiration = now + Duration::from_millis(3_600_750);
+        let not_before = now - Duration::from_nanos(1_500_000_001);
+
+        let ucan = Ucan::builder()
+            .store(PlaceholderStore)
+            .audience("did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti")
+            .expiration(expiration)
+            .not_before(not_before)
+            .capabilities(caps!()?)
+            .sign(&keypair)?;
+
+        // Times are truncated to whole seconds when built.
+        assert_eq!(
+            ucan.payload().expiration(),
+            Some(now + Duration::from_secs(3_600))
+        );
+        assert_eq!(
+            ucan.payload().not_before(),
+            Some(now - Duration::from_secs(2))
+        );
+
+        // And are the same after parsing the encoded UCAN.
+        let encoded = ucan.to_string();
+        let decoded = SignedUcan::try_from_str(&encoded, PlaceholderStore)?;
+        assert_eq!(decoded.payload().expiration(), ucan.payload().expiration());
+        assert_eq!(decoded.payload().not_before(), ucan.payload().not_before());
+        assert_eq!(decoded.to_string(), encoded);
+
+        ucan.validate()?;
+        decoded.validate()?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_store_recursive() -> anyhow::Result<()> {
+        let store_a = MemoryStore::default();
+        let store_b = MemoryStore::default();
+        let Chain {
+            principals, ucan, ..
+        } = chain(
+            store_a.clone(),
+            caps! {
+                "zerodb://": { "db/read": [{}] }
+            }?,
+        )
+        .await?;
+
+        let [p0, _, p2, _] = &principals.keys;
+        let [.., p3_did] = &principals.dids;
+
+        let cid1 = ucan.store().await?;
+        let ucan2 = delegate(
+            p2,
+            p3_did,
+            SystemTime::now() + Duration::from_secs(10),
+            [cid1],
+            store_a.clone(),
+        )?;
+
+        let capability = || -> anyhow::Result<_> {
+            Ok((
+                ResolvedResource::from_str("zerodb://")?,
+                Ability::from_str("db/read")?,
+                Caveats::any(),
+            ))
+        };
+
+        // Proofs that were never fetched are skipped.
+        let encoded = ucan2.to_string();
+        let unfetched = SignedUcan::with_store(&encoded, store_b.clone())?;
+        let cid2 = unfetched.store_recursive().await?;
+        assert!(SignedUcan::load(&cid2, store_b.clone())
+            .await?
+            .permits(capability()?, p0)
+            .await
+            .is_err());
+
+        // Fetch the whole chain from the first store, then store it in the second one.
+        ucan2.root_issuers(&store_a).await?;
+        let ucan2 = ucan2.use_store(store_b.clone());
+        assert_eq!(ucan2.store_recursive().await?, cid2);
+
+        let loaded = SignedUcan::load(&cid2, store_b.clone()).await?;
+        assert_eq!(loaded, ucan2);
+        assert!(loaded.permits(capability()?, p0).await?);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_ucan_validate_with_nonce_store() -> anyhow::Result<()> {
+        let nonce_store = MemoryNonceStore::new();
+        let key_pair = Ed25519KeyPair::generate(&mut rand::thread_rng())?;
+        let issuer = WrappedDidWebKey::from_key(&key_pair, Base::Base58Btc)?;
+        let audience = "did:wk:b5ua5l4wgcp46zrtn3ihjjmu5gbyhusmyt5bianl5ov2yrvj7wnh4vti";
+        let expiration = SystemTime::now() + Duration::from_secs(3_600);
+
+        let ucan = Ucan::builder()
+            .store(PlaceholderStore)
+            .issuer(issuer.clone())
+            .audience(audience)
+            .expiration(expiration)
+            .nonce("1100263a4012")
+            .capabilities(caps!()?)
+            .sign(&key_pair)?;
+
+        ucan.validate_with_nonce_store(&nonce_store).await?;
+        assert!(matches!(
+            ucan.validate_with_nonce_store(&nonce_store).await,
+            Err(UcanError::ReplayedNonce(iss, nnc)) if iss == issuer.to_string() && nnc == "1100263a4012"
+        ));
+
+        // Validation without a nonce store does not check for replays.
+        ucan.validate()?;
+
+        // UCANs without a nonce are not tracked.
+        let ucan = Ucan::builder()
+            .store(PlaceholderStore)
+            .issuer(issuer)
+            .audience(audience)
+            .expiration(expiration)
+            .capabilities(caps!()?)
+            .sign(&key_pair)?;
+
+        ucan.validate_with_nonce_store(&nonce_store).await?;
+        ucan.validate_with_nonce_store(&nonce_store).await?;
+
+        Ok(())
+    }
 }
@@ -1,6 +1,11 @@
 //! Pollable trait for objects that can be polled for readiness.
 
-use std::{any::Any, collections::HashMap, pin::Pin, task::Poll};
+use std::{
+    any::Any,
+    collections::HashMap,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 use anyhow::Ok;
 use async_trait::async_trait;
@@ -28,6 +33,16 @@ pub struct PollableHandle {
     drop_fn: Option<fn(&mut ResourceTable, u32) -> wasmtime::Result<()>>,
 }
 
+/// A host-side handle that becomes ready when its resource is ready, mirroring
+/// `wasi:io/poll.pollable`.
+///
+/// It is a future, so it can be awaited directly, and several of them can be awaited together with
+/// [`poll_ready`].
+pub struct Pollable<'a> {
+    wait: PollableFuture<'a>,
+    done: bool,
+}
+
 //--------------------------------------------------------------------------------------------------
 // Traits
 //--------------------------------------------------------------------------------------------------
@@ -75,12 +90,75 @@ pub trait Subscribe: Await {
     }
 }
 
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl<'a> Pollable<'a> {
+    /// Creates a pollable that is ready once `wait` completes.
+    pub fn new(wait: PollableFuture<'a>) -> Self {
+        Self { wait, done: false }
+    }
+
+    /// Checks if the resource is ready without waiting.
+    pub async fn ready(&mut self) -> bool {
+        future::poll_immediate(self).await.is_some()
+    }
+
+    /// Waits until the resource is ready.
+    pub async fn block(self) {
+        self.await
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Waits until one or more of `pollables` are ready and returns their indices.
+///
+/// This mirrors `wasi:io/poll.poll`. It returns immediately with no indices if `pollables` is empty.
+pub async fn poll_ready(pollables: &mut [Pollable<'_>]) -> Vec<usize> {
+    if pollables.is_empty() {
+        return Vec::new();
+    }
+
+    future::poll_fn(|cx| {
+        let indices = pollables
+            .iter_mut()
+            .enumerate()
+            .filter_map(|(index, pollable)| Pin::new(pollable).poll(cx).is_ready().then_some(index))
+            .collect::<Vec<_>>();
+
+        if indices.is_empty() {
+            Poll::Pending
+        } else {
+            Poll::Ready(indices)
+        }
+    })
+    .await
+}
+
 //--------------------------------------------------------------------------------------------------
 // Trait Implementations
 //--------------------------------------------------------------------------------------------------
 
 impl<T> Subscribe for T where T: Await {}
 
+impl Future for Pollable<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        // The wait future must not be polled again once it completed.
+        if !self.done {
+            futures::ready!(self.wait.as_mut().poll(cx));
+            self.done = true;
+        }
+
+        Poll::Ready(())
+    }
+}
+
 #[async_trait]
 impl<T> poll::HostPollable for T
 where
@@ -30,6 +30,10 @@ pub enum StreamError {
     )]
     WriteTooLarge(u64),
 
+    /// Bytes to be written to stream are more than the stream currently permits.
+    #[error("Bytes to be written ({0}) exceed the write permit ({1})")]
+    WriteNotPermitted(u64, u64),
+
     /// An error that occurred while using the standard library's io module.
     #[error("I/O error: {0}")]
     IoError(#[from] std::io::Error),
@@ -4,7 +4,7 @@ use wasmtime::component::Resource;
 
 use crate::{
     bindgen::streams,
-    io::{Await, PollableHandle, Subscribe},
+    io::{Await, Pollable, PollableHandle, Subscribe},
     state::WasiTableState,
 };
 
@@ -35,8 +35,30 @@ pub trait InputStream: Await {
     /// Returns a [`StreamError`] if the stream is closed.
     fn read(&mut self, len: u64) -> Result<Bytes, StreamError>;
 
+    /// Waits for the stream to have bytes to read and reads up to `len` of them.
+    ///
+    /// This yields instead of returning an empty buffer while the stream is empty.
+    async fn blocking_read(&mut self, len: u64) -> Result<Bytes, StreamError> {
+        self.wait().await;
+        self.read(len)
+    }
+
+    /// Returns a pollable that becomes ready when a read would make progress, i.e. bytes are
+    /// available or the stream is closed.
+    fn subscribe(&mut self) -> Pollable<'_> {
+        Pollable::new(self.wait())
+    }
+
     /// Same as `read` except the bytes get skipped and the number of bytes skipped is returned.
-    fn skip(&mut self, len: u64) -> Result<u64, StreamError>;
+    ///
+    /// Fewer than `len` bytes are skipped if the stream has fewer available.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StreamError`] if the stream is closed.
+    fn skip(&mut self, len: u64) -> Result<u64, StreamError> {
+        self.read(len).map(|bytes| bytes.len() as u64)
+    }
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -70,8 +92,7 @@ where
         len: u64,
     ) -> Result<Vec<u8>, StreamError> {
         let stream: &mut Box<dyn InputStream> = self.table_mut().get_mut(&stream)?;
-        stream.wait().await;
-        stream.read(len).map(|bytes| bytes.to_vec())
+        stream.blocking_read(len).await.map(|bytes| bytes.to_vec())
     }
 
     fn skip(&mut self, stream: Resource<InputStreamHandle>, len: u64) -> Result<u64, StreamError> {
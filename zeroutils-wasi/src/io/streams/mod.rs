@@ -4,6 +4,7 @@ mod constant;
 mod error;
 mod input;
 mod output;
+mod pipe;
 mod streams;
 
 //--------------------------------------------------------------------------------------------------
@@ -14,3 +15,4 @@ pub use constant::*;
 pub use error::*;
 pub use input::*;
 pub use output::*;
+pub use pipe::*;
@@ -4,11 +4,11 @@ use wasmtime::component::Resource;
 
 use crate::{
     bindgen::streams,
-    io::{Await, PollableHandle, Subscribe},
+    io::{Await, Pollable, PollableHandle, Subscribe},
     state::WasiTableState,
 };
 
-use super::{constant, InputStreamHandle, StreamError};
+use super::{constant, InputStream, InputStreamHandle, StreamError};
 
 //--------------------------------------------------------------------------------------------------
 // Types
@@ -50,6 +50,21 @@ pub trait OutputStream: Await {
     /// - caller performed an illegal operation (e.g. wrote more bytes than were permitted)
     fn flush(&mut self) -> Result<(), StreamError>;
 
+    /// Flushes the stream and waits until it is ready for writing again.
+    ///
+    /// The write permit is checked once the stream is ready, so that an error from pushing out the
+    /// flushed bytes is surfaced here rather than on the next write.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StreamError`] if the flush or the write permit check after it fails.
+    async fn blocking_flush(&mut self) -> Result<(), StreamError> {
+        self.flush()?;
+        self.wait().await;
+        self.write_permit()?;
+        Ok(())
+    }
+
     /// A non-blocking check for the number of bytes the stream can currently accept to
     /// be written to it.
     ///
@@ -67,6 +82,63 @@ pub trait OutputStream: Await {
         self.write_permit()
     }
 
+    /// Writes all of `bytes` to the stream, flushing it and waiting for a write permit whenever the
+    /// stream cannot accept more.
+    ///
+    /// Streams with a bounded buffer use this to apply backpressure, as the write only makes progress
+    /// as fast as the stream is drained.
+    async fn blocking_write(&mut self, mut bytes: Bytes) -> Result<(), StreamError> {
+        while !bytes.is_empty() {
+            let write_len = self.flushing_write_permit().await? as usize;
+            let chunk = bytes.split_to(bytes.len().min(write_len));
+            self.write(chunk)?;
+        }
+
+        Ok(())
+    }
+
+    /// Copies up to `len` bytes from `src` to the stream and returns the number of bytes copied.
+    ///
+    /// This is a non-blocking operation. It copies no more than the stream currently permits and
+    /// what `src` has available, so it may copy fewer than `len` bytes, or none.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`StreamError`] if either stream is closed. A closed `src` is only reported when
+    /// the stream could accept bytes.
+    fn splice(&mut self, src: &mut dyn InputStream, len: u64) -> Result<u64, StreamError> {
+        let min_write_len = len.min(self.write_permit()?);
+        if min_write_len == 0 {
+            return Ok(0);
+        }
+
+        let bytes = src.read(min_write_len)?;
+        let bytes_len = bytes.len() as u64;
+        if bytes_len > 0 {
+            self.write(bytes)?;
+        }
+
+        Ok(bytes_len)
+    }
+
+    /// Returns the write permit, first flushing the stream and waiting for it to be ready if it
+    /// cannot accept any bytes.
+    async fn flushing_write_permit(&mut self) -> Result<u64, StreamError> {
+        match self.write_permit()? {
+            0 => {
+                self.flush()?;
+                self.blocking_write_permit().await
+            }
+            write_len => Ok(write_len),
+        }
+    }
+
+    /// Returns a pollable that becomes ready when a write would make progress, i.e. the stream can
+    /// accept bytes or is closed.
+    fn subscribe(&mut self) -> Pollable<'_> {
+        Pollable::new(self.wait())
+    }
+
     /// Same as `write` but writes `len` amount of zeroes to the stream.
     fn write_zeroes(&mut self, len: u64) -> Result<(), StreamError> {
         self.write(Bytes::from(vec![0; len as usize]))
@@ -114,19 +186,9 @@ where
             return Err(StreamError::WriteTooLarge(byte_len));
         }
 
-        // Write bytes in chunks of permitted size. The loop makes it a blocking operation.
-        let mut bytes = Bytes::from(bytes);
-        while !bytes.is_empty() {
-            let write_len = stream.write_permit()? as usize;
-            let min_write_len = bytes.len().min(write_len);
-            let chunk = bytes.split_to(min_write_len);
-            stream.write(chunk)?;
-        }
-
-        stream.flush()?; // Flush the stream.
-        stream.wait().await; // Wait for the stream to be ready again to be sure all data is written.
-
-        Ok(())
+        // Write bytes in chunks of permitted size, waiting for the stream to accept more.
+        stream.blocking_write(Bytes::from(bytes)).await?;
+        stream.blocking_flush().await
     }
 
     fn write_zeroes(
@@ -149,19 +211,16 @@ where
             return Err(StreamError::WriteTooLarge(len));
         }
 
-        // Write zeroes in chunks of permitted size. The loop makes it a blocking operation.
+        // Write zeroes in chunks of permitted size, waiting for the stream to accept more.
         let mut len = len;
         while len > 0 {
-            let write_len = stream.write_permit()?;
+            let write_len = stream.flushing_write_permit().await?;
             let min_write_len = len.min(write_len);
             stream.write_zeroes(min_write_len)?;
             len -= min_write_len;
         }
 
-        stream.flush()?; // Flush the stream.
-        stream.wait().await; // Wait for the stream to be ready again to be sure all data is written.
-
-        Ok(())
+        stream.blocking_flush().await
     }
 
     fn flush(&mut self, stream: Resource<OutputStreamHandle>) -> Result<(), StreamError> {
@@ -172,10 +231,7 @@ where
         &mut self,
         stream: Resource<OutputStreamHandle>,
     ) -> Result<(), StreamError> {
-        let stream = self.table_mut().get_mut(&stream)?;
-        stream.flush()?;
-        stream.wait().await; // Wait for the stream to be ready again to be sure all data is written.
-        Ok(())
+        self.table_mut().get_mut(&stream)?.blocking_flush().await
     }
 
     async fn splice(
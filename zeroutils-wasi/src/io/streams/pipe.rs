@@ -0,0 +1,558 @@
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
+use tokio::sync::Notify;
+
+use crate::io::Await;
+
+use super::{constant, InputStream, OutputStream, StreamError};
+
+//--------------------------------------------------------------------------------------------------
+// Types
+//--------------------------------------------------------------------------------------------------
+
+/// The writing end of a bounded in-memory pipe created with [`pipe`] or [`buffered_pipe`].
+///
+/// The pipe buffers at most `capacity` bytes. Once the buffer is full, the write permit drops to zero
+/// and the stream only becomes ready again when the reader makes room, so a slow reader throttles the
+/// writer instead of letting the buffer grow.
+///
+/// Bytes written to a buffered pipe count towards its capacity but only reach the reader when the
+/// stream is flushed. Unflushed bytes are discarded when the stream is dropped.
+pub struct PipeOutputStream {
+    pipe: Arc<Pipe>,
+}
+
+/// The reading end of a bounded in-memory pipe created with [`pipe`] or [`buffered_pipe`].
+///
+/// The stream is ready when there are buffered bytes to read or the writer is gone.
+pub struct PipeInputStream {
+    pipe: Arc<Pipe>,
+}
+
+struct Pipe {
+    state: Mutex<PipeState>,
+    readable: Notify,
+    writable: Notify,
+}
+
+struct PipeState {
+    buffer: BytesMut,
+    staged: Option<BytesMut>,
+    capacity: usize,
+    closed: bool,
+}
+
+//--------------------------------------------------------------------------------------------------
+// Functions
+//--------------------------------------------------------------------------------------------------
+
+/// Creates an in-memory pipe that buffers at most `capacity` bytes between its ends.
+///
+/// A `capacity` of zero is raised to one, since the writer could otherwise never make progress.
+///
+/// The ends can be boxed into an [`InputStreamHandle`][super::InputStreamHandle] and an
+/// [`OutputStreamHandle`][super::OutputStreamHandle], which lets stream code be exercised without OS
+/// handles, e.g. in tests.
+pub fn pipe(capacity: usize) -> (PipeInputStream, PipeOutputStream) {
+    new_pipe(capacity, None)
+}
+
+/// Creates an in-memory pipe like [`pipe`] whose written bytes only reach the reader on flush.
+pub fn buffered_pipe(capacity: usize) -> (PipeInputStream, PipeOutputStream) {
+    new_pipe(capacity, Some(BytesMut::new()))
+}
+
+fn new_pipe(capacity: usize, staged: Option<BytesMut>) -> (PipeInputStream, PipeOutputStream) {
+    let capacity = capacity.max(1);
+    let pipe = Arc::new(Pipe {
+        state: Mutex::new(PipeState {
+            buffer: BytesMut::with_capacity(capacity),
+            staged,
+            capacity,
+            closed: false,
+        }),
+        readable: Notify::new(),
+        writable: Notify::new(),
+    });
+
+    (
+        PipeInputStream { pipe: pipe.clone() },
+        PipeOutputStream { pipe },
+    )
+}
+
+//--------------------------------------------------------------------------------------------------
+// Methods
+//--------------------------------------------------------------------------------------------------
+
+impl Pipe {
+    fn state(&self) -> MutexGuard<'_, PipeState> {
+        // The state is always left valid, so a poisoned lock can be recovered.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn close(&self) {
+        self.state().closed = true;
+        self.readable.notify_one();
+        self.writable.notify_one();
+    }
+}
+
+impl PipeState {
+    fn len(&self) -> usize {
+        self.buffer.len() + self.staged.as_ref().map_or(0, BytesMut::len)
+    }
+
+    fn available(&self) -> usize {
+        self.capacity.saturating_sub(self.len())
+    }
+}
+
+impl PipeOutputStream {
+    /// Returns the number of bytes written but not yet read, including unflushed ones.
+    pub fn buffered(&self) -> usize {
+        self.pipe.state().len()
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Trait Implementations
+//--------------------------------------------------------------------------------------------------
+
+#[async_trait]
+impl Await for PipeOutputStream {
+    async fn wait(&mut self) {
+        loop {
+            // A notification sent after the check is kept as a permit, so it is not missed.
+            {
+                let state = self.pipe.state();
+                if state.closed || state.available() > 0 {
+                    return;
+                }
+            }
+
+            self.pipe.writable.notified().await;
+        }
+    }
+}
+
+#[async_trait]
+impl OutputStream for PipeOutputStream {
+    fn write(&mut self, bytes: Bytes) -> Result<(), StreamError> {
+        let mut state = self.pipe.state();
+        if state.closed {
+            return Err(StreamError::Closed);
+        }
+
+        let byte_len = bytes.len() as u64;
+        let permit = state.available() as u64;
+        if byte_len > permit {
+            return Err(StreamError::WriteNotPermitted(byte_len, permit));
+        }
+
+        // Bytes written to a buffered pipe wait for a flush.
+        if let Some(staged) = &mut state.staged {
+            staged.extend_from_slice(&bytes);
+            return Ok(());
+        }
+
+        state.buffer.extend_from_slice(&bytes);
+        drop(state);
+
+        self.pipe.readable.notify_one();
+        Ok(())
+    }
+
+    fn flush(&mut self) -> Result<(), StreamError> {
+        let mut state = self.pipe.state();
+        if state.closed {
+            return Err(StreamError::Closed);
+        }
+
+        // Bytes written to an unbuffered pipe are already visible to the reader.
+        let staged = match &mut state.staged {
+            Some(staged) if !staged.is_empty() => staged.split(),
+            _ => return Ok(()),
+        };
+
+        state.buffer.unsplit(staged);
+        drop(state);
+
+        self.pipe.readable.notify_one();
+        Ok(())
+    }
+
+    fn write_permit(&mut self) -> Result<u64, StreamError> {
+        let state = self.pipe.state();
+        if state.closed {
+            return Err(StreamError::Closed);
+        }
+
+        Ok((state.available() as u64).min(constant::MAX_WRITE_SIZE))
+    }
+}
+
+#[async_trait]
+impl Await for PipeInputStream {
+    async fn wait(&mut self) {
+        loop {
+            {
+                let state = self.pipe.state();
+                if state.closed || !state.buffer.is_empty() {
+                    return;
+                }
+            }
+
+            self.pipe.readable.notified().await;
+        }
+    }
+}
+
+#[async_trait]
+impl InputStream for PipeInputStream {
+    fn read(&mut self, len: u64) -> Result<Bytes, StreamError> {
+        let mut state = self.pipe.state();
+        if state.buffer.is_empty() && state.closed {
+            return Err(StreamError::Closed);
+        }
+
+        let len = state.buffer.len().min(len as usize);
+        let bytes = state.buffer.split_to(len).freeze();
+        drop(state);
+
+        if !bytes.is_empty() {
+            self.pipe.writable.notify_one();
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl Drop for PipeOutputStream {
+    fn drop(&mut self) {
+        self.pipe.close();
+    }
+}
+
+impl Drop for PipeInputStream {
+    fn drop(&mut self) {
+        self.pipe.close();
+    }
+}
+
+//--------------------------------------------------------------------------------------------------
+// Tests
+//--------------------------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use wasmtime::component::Resource;
+
+    use crate::{
+        bindgen::streams::{HostInputStream, HostOutputStream},
+        io::{poll_ready, InputStreamHandle, OutputStreamHandle},
+    };
+
+    use super::*;
+
+    mod fixtures {
+        use wasmtime::component::ResourceTable;
+
+        use crate::state::WasiTableState;
+
+        //--------------------------------------------------------------------------------------------------
+        // Types
+        //--------------------------------------------------------------------------------------------------
+
+        #[derive(Default)]
+        pub struct TableState {
+            pub table: ResourceTable,
+        }
+
+        //--------------------------------------------------------------------------------------------------
+        // Trait Implementations
+        //--------------------------------------------------------------------------------------------------
+
+        impl WasiTableState for TableState {
+            fn table(&self) -> &ResourceTable {
+                &self.table
+            }
+
+            fn table_mut(&mut self) -> &mut ResourceTable {
+                &mut self.table
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pipe_slow_reader_throttles_writer() -> anyhow::Result<()> {
+        let (mut input, mut output) = pipe(1024);
+        let data = (0..64 * 1024).map(|i| i as u8).collect::<Vec<_>>();
+
+        let expected = data.clone();
+        let writer = tokio::spawn(async move {
+            output.blocking_write(Bytes::from(data)).await?;
+            anyhow::Ok(output)
+        });
+
+        // Without a reader, the writer stops once the buffer is full.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!writer.is_finished());
+
+        let mut received = Vec::new();
+        while received.len() < expected.len() {
+            let bytes = input.blocking_read(256).await?;
+            assert!(bytes.len() <= 256);
+            received.extend(bytes);
+            tokio::time::sleep(Duration::from_micros(100)).await;
+        }
+
+        let output = writer.await??;
+        assert_eq!(output.buffered(), 0);
+        assert_eq!(received, expected);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_write_permit() -> anyhow::Result<()> {
+        let (mut input, mut output) = pipe(8);
+
+        assert_eq!(output.write_permit()?, 8);
+        output.write(Bytes::from_static(b"hello"))?;
+        assert_eq!(output.write_permit()?, 3);
+        assert!(matches!(
+            output.write(Bytes::from_static(b"world")),
+            Err(StreamError::WriteNotPermitted(5, 3))
+        ));
+
+        assert_eq!(input.read(2)?, b"he"[..]);
+        assert_eq!(output.write_permit()?, 5);
+
+        // The reader drains the buffer before it sees the closed stream.
+        drop(output);
+        assert_eq!(input.blocking_read(16).await?, b"llo"[..]);
+        assert!(matches!(input.read(16), Err(StreamError::Closed)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_zero_capacity() -> anyhow::Result<()> {
+        let (mut input, mut output) = pipe(0);
+        assert_eq!(output.write_permit()?, 1);
+
+        let writer = tokio::spawn(async move {
+            output.blocking_write(Bytes::from_static(b"hi")).await?;
+            anyhow::Ok(())
+        });
+
+        assert_eq!(input.blocking_read(16).await?, b"h"[..]);
+        assert_eq!(input.blocking_read(16).await?, b"i"[..]);
+        writer.await??;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipe_skip() -> anyhow::Result<()> {
+        let (mut input, mut output) = pipe(16);
+        output.write(Bytes::from_static(b"hello world"))?;
+
+        assert_eq!(input.skip(6)?, 6);
+        assert_eq!(input.read(3)?, b"wor"[..]);
+
+        // Skipping past the end only skips what is there.
+        assert_eq!(input.skip(10)?, 2);
+        assert_eq!(input.skip(10)?, 0);
+
+        drop(output);
+        assert!(matches!(input.skip(10), Err(StreamError::Closed)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_pipe_splice() -> anyhow::Result<()> {
+        let (mut src, mut src_output) = pipe(16);
+        let (mut dest_input, mut dest) = pipe(4);
+        src_output.write(Bytes::from_static(b"hello world"))?;
+
+        // Limited by `len`.
+        assert_eq!(dest.splice(&mut src, 2)?, 2);
+        assert_eq!(dest_input.read(16)?, b"he"[..]);
+
+        // Limited by the write permit of the destination.
+        assert_eq!(dest.splice(&mut src, 16)?, 4);
+        assert_eq!(dest.splice(&mut src, 16)?, 0);
+        assert_eq!(dest_input.read(16)?, b"llo "[..]);
+
+        // Limited by what the source has.
+        assert_eq!(dest.splice(&mut src, 16)?, 4);
+        assert_eq!(dest_input.read(16)?, b"worl"[..]);
+        assert_eq!(dest.splice(&mut src, 16)?, 1);
+        assert_eq!(dest.splice(&mut src, 16)?, 0);
+        assert_eq!(dest_input.read(16)?, b"d"[..]);
+
+        // The end of the source is reported once it is drained.
+        drop(src_output);
+        assert!(matches!(
+            dest.splice(&mut src, 16),
+            Err(StreamError::Closed)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_buffered_pipe_flush() -> anyhow::Result<()> {
+        let (mut input, mut output) = buffered_pipe(8);
+
+        // Written bytes take up capacity but are not readable yet.
+        output.write(Bytes::from_static(b"hello"))?;
+        assert_eq!(output.write_permit()?, 3);
+        assert!(input.read(8)?.is_empty());
+        assert!(!input.subscribe().ready().await);
+
+        output.flush()?;
+        assert_eq!(input.read(8)?, b"hello"[..]);
+
+        output.write(Bytes::from_static(b"world"))?;
+        assert!(input.read(8)?.is_empty());
+        output.blocking_flush().await?;
+        assert_eq!(input.read(8)?, b"world"[..]);
+
+        // Writes larger than the capacity are flushed as they go.
+        let reader = tokio::spawn(async move {
+            let mut received = Vec::new();
+            while let Ok(bytes) = input.blocking_read(8).await {
+                received.extend(bytes);
+            }
+
+            received
+        });
+
+        output
+            .blocking_write(Bytes::from_static(b"a longer message"))
+            .await?;
+        output.blocking_flush().await?;
+        drop(output);
+        assert_eq!(reader.await?, b"a longer message");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_blocking_flush_closed() -> anyhow::Result<()> {
+        let (input, mut output) = buffered_pipe(8);
+        output.write(Bytes::from_static(b"hello"))?;
+
+        drop(input);
+        assert!(matches!(
+            output.blocking_flush().await,
+            Err(StreamError::Closed)
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_empty_read_yields() -> anyhow::Result<()> {
+        let (mut input, mut output) = pipe(8);
+
+        assert!(input.read(8)?.is_empty());
+
+        let reader = tokio::spawn(async move { input.blocking_read(8).await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!reader.is_finished());
+
+        output.write(Bytes::from_static(b"hi"))?;
+        assert_eq!(reader.await??, b"hi"[..]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_subscribe() -> anyhow::Result<()> {
+        let (mut input_0, mut output_0) = pipe(4);
+        let (mut input_1, mut output_1) = pipe(4);
+
+        assert!(!input_0.subscribe().ready().await);
+        assert!(output_0.subscribe().ready().await);
+
+        output_0.write(Bytes::from_static(b"full"))?;
+        assert!(input_0.subscribe().ready().await);
+        assert!(!output_0.subscribe().ready().await);
+        input_0.read(4)?;
+
+        // Only the stream that gets written to becomes ready.
+        let writer = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            output_1.write(Bytes::from_static(b"hi"))?;
+            anyhow::Ok(output_1)
+        });
+
+        let mut pollables = [input_0.subscribe(), input_1.subscribe()];
+        assert_eq!(poll_ready(&mut pollables).await, [1]);
+        drop(pollables);
+
+        let _output_1 = writer.await??;
+        assert_eq!(input_1.read(4)?, b"hi"[..]);
+        assert!(poll_ready(&mut []).await.is_empty());
+
+        // A closed stream is ready, as a read reports the end of the stream.
+        drop(output_0);
+        input_0.subscribe().block().await;
+        assert!(matches!(input_0.read(4), Err(StreamError::Closed)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_host_streams() -> anyhow::Result<()> {
+        let (input, output) = pipe(16);
+        let mut state = fixtures::TableState::default();
+        let output = state.table.push::<OutputStreamHandle>(Box::new(output))?;
+        let input = state.table.push::<InputStreamHandle>(Box::new(input))?;
+        let (output_rep, input_rep) = (output.rep(), input.rep());
+
+        HostOutputStream::blocking_write_and_flush(
+            &mut state,
+            Resource::new_borrow(output_rep),
+            b"hello world".to_vec(),
+        )
+        .await?;
+        assert_eq!(
+            HostOutputStream::check_write(&mut state, Resource::new_borrow(output_rep))?,
+            5
+        );
+
+        assert_eq!(
+            HostInputStream::read(&mut state, Resource::new_borrow(input_rep), 5)?,
+            b"hello"
+        );
+        assert_eq!(
+            HostInputStream::skip(&mut state, Resource::new_borrow(input_rep), 1)?,
+            1
+        );
+        assert_eq!(
+            HostInputStream::blocking_read(&mut state, Resource::new_borrow(input_rep), 16).await?,
+            b"world"
+        );
+
+        // The reader sees the end of the stream once the writer is dropped.
+        HostOutputStream::drop(&mut state, output)?;
+        assert!(matches!(
+            HostInputStream::blocking_read(&mut state, Resource::new_borrow(input_rep), 16).await,
+            Err(StreamError::Closed)
+        ));
+
+        HostInputStream::drop(&mut state, input)?;
+
+        Ok(())
+    }
+}